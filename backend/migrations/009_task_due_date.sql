@@ -0,0 +1,4 @@
+-- Optional deadline for a task
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS due_date TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_due_date ON tasks(due_date);
@@ -0,0 +1,4 @@
+-- Deleted tasks are kept for audit and statistics; deleted_at marks them as hidden
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_not_deleted ON tasks(created_at) WHERE deleted_at IS NULL;
@@ -0,0 +1,3 @@
+-- Usernames are matched case-insensitively at login, so they must also be unique
+-- regardless of case. Stored values keep the case they were entered with.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_username_lower ON users (LOWER(username));
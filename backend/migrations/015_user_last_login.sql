@@ -0,0 +1,2 @@
+-- Time of the last successful login; NULL for accounts that never logged in
+ALTER TABLE users ADD COLUMN IF NOT EXISTS last_login_at TIMESTAMP;
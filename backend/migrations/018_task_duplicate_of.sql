@@ -0,0 +1,5 @@
+-- A task marked as a duplicate points at the canonical task; duplicates are hidden
+-- from task lists unless requested
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS duplicate_of UUID REFERENCES tasks(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_duplicate_of ON tasks(duplicate_of) WHERE duplicate_of IS NOT NULL;
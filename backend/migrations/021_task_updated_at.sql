@@ -0,0 +1,7 @@
+-- When a task last changed; existing tasks start from their creation time
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP;
+
+UPDATE tasks SET updated_at = created_at WHERE updated_at IS NULL;
+
+ALTER TABLE tasks ALTER COLUMN updated_at SET DEFAULT NOW();
+ALTER TABLE tasks ALTER COLUMN updated_at SET NOT NULL;
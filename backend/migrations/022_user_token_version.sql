@@ -0,0 +1,2 @@
+-- Access tokens carry the version they were issued at; bumping it signs the user out everywhere
+ALTER TABLE users ADD COLUMN IF NOT EXISTS token_version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Created-date filters and keyset pagination over all tasks, including deleted ones
+CREATE INDEX IF NOT EXISTS idx_tasks_created_at ON tasks(created_at, id);
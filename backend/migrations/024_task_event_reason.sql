@@ -0,0 +1,2 @@
+-- Why a change was made, for transitions that require one (e.g. reopening)
+ALTER TABLE task_events ADD COLUMN IF NOT EXISTS reason TEXT;
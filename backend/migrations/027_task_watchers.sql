@@ -0,0 +1,16 @@
+-- Users following a task's comments and status changes
+CREATE TABLE IF NOT EXISTS task_watchers (
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (task_id, user_id)
+);
+
+-- Creators and testers of existing tasks follow them, as they do for new ones
+INSERT INTO task_watchers (task_id, user_id)
+SELECT id, assigned_by FROM tasks
+UNION
+SELECT id, tester_id FROM tasks WHERE tester_id IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+ALTER TYPE notification_kind ADD VALUE IF NOT EXISTS 'task_status_changed';
@@ -0,0 +1,15 @@
+-- Effort tracking: an estimate per task and the hours logged against it
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS estimate_hours DOUBLE PRECISION;
+
+CREATE TABLE IF NOT EXISTS time_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    hours DOUBLE PRECISION NOT NULL CHECK (hours > 0),
+    note TEXT,
+    logged_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_entries_task_id ON time_entries(task_id, logged_at);
+CREATE INDEX IF NOT EXISTS idx_time_entries_user_id ON time_entries(user_id, logged_at);
@@ -0,0 +1,24 @@
+-- Reusable task blueprints, optionally instantiated on a schedule
+DO $$ BEGIN
+    CREATE TYPE template_recurrence AS ENUM ('daily', 'weekly', 'monthly');
+EXCEPTION WHEN duplicate_object THEN NULL;
+END $$;
+
+CREATE TABLE IF NOT EXISTS task_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    urgency task_urgency NOT NULL DEFAULT 'medium',
+    acceptance_criteria TEXT,
+    evaluation_criteria TEXT,
+    tester_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    -- Author of the template, and of the tasks the scheduler creates from it
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recurrence template_recurrence,
+    next_run_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_templates_next_run_at ON task_templates(next_run_at)
+    WHERE recurrence IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Service accounts that pull the task change feed (GET /api/sync/tasks) and nothing else
+ALTER TYPE user_role ADD VALUE IF NOT EXISTS 'sync';
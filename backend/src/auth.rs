@@ -1,13 +1,14 @@
 use axum::{
     extract::FromRequestParts,
-    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap},
 };
 use chrono::Utc;
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::errors::AppError;
+use crate::config::AppConfig;
+use crate::errors::{codes, AppError};
 use crate::models::UserRole;
 use crate::AppState;
 
@@ -18,16 +19,102 @@ pub struct Claims {
     pub role: String,
     pub exp: usize,
     pub iat: usize,
+    /// Not valid before; equal to `iat`. Absent only in tokens issued by older versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<usize>,
+    /// Unique token id used for revocation. Older tokens without it stay valid until
+    /// they expire, they just cannot be revoked individually.
+    #[serde(default)]
+    pub jti: Option<Uuid>,
+    /// User's `token_version` at issue time; the token dies once the version moves on.
+    /// Absent in older tokens, which count as version 0.
+    #[serde(default)]
+    pub token_version: i32,
 }
 
+/// Signing and verification keys for access tokens.
+///
+/// HS256 with `JWT_SECRET` by default; RS256 when `JWT_PRIVATE_KEY_PEM` is set, in which
+/// case the public half is published as a JWKS so other services can verify tokens.
+#[derive(Clone)]
+pub struct JwtKeys {
+    algorithm: Algorithm,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    /// `kid` header and public JWK, RS256 only
+    public_jwk: Option<(String, serde_json::Value)>,
+}
+
+impl JwtKeys {
+    pub fn hs256(secret: &str) -> Self {
+        Self {
+            algorithm: Algorithm::HS256,
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            public_jwk: None,
+        }
+    }
+
+    /// Accepts a PKCS#1 or PKCS#8 PEM; literal `\n` sequences are allowed so the
+    /// key fits in a single-line environment variable.
+    pub fn rs256(private_key_pem: &str) -> Self {
+        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        use base64::Engine;
+        use rsa::pkcs1::DecodeRsaPrivateKey;
+        use rsa::pkcs8::DecodePrivateKey;
+        use rsa::traits::PublicKeyParts;
+        use sha2::{Digest, Sha256};
+
+        let pem = private_key_pem.replace("\\n", "\n");
+        let private_key = rsa::RsaPrivateKey::from_pkcs8_pem(&pem)
+            .or_else(|_| rsa::RsaPrivateKey::from_pkcs1_pem(&pem))
+            .expect("JWT_PRIVATE_KEY_PEM must be an RSA private key in PEM format");
+
+        let n = URL_SAFE_NO_PAD.encode(private_key.n().to_bytes_be());
+        let e = URL_SAFE_NO_PAD.encode(private_key.e().to_bytes_be());
+        let kid: String = format!("{:x}", Sha256::digest(n.as_bytes()))
+            .chars()
+            .take(16)
+            .collect();
+
+        let jwk = serde_json::json!({
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": kid,
+            "n": n,
+            "e": e,
+        });
+
+        Self {
+            algorithm: Algorithm::RS256,
+            encoding: EncodingKey::from_rsa_pem(pem.as_bytes())
+                .expect("JWT_PRIVATE_KEY_PEM must be an RSA private key in PEM format"),
+            decoding: DecodingKey::from_rsa_components(&n, &e)
+                .expect("RSA public key components are valid"),
+            public_jwk: Some((kid, jwk)),
+        }
+    }
+
+    /// Public keys for `/.well-known/jwks.json`; `None` in HS256 mode
+    pub fn jwks(&self) -> Option<serde_json::Value> {
+        self.public_jwk
+            .as_ref()
+            .map(|(_, jwk)| serde_json::json!({ "keys": [jwk] }))
+    }
+}
+
+/// Issues an access token; its lifetime depends on the role (see `JWT_EXPIRATION_HOURS_<ROLE>`)
 pub fn create_token(
     user_id: Uuid,
     username: &str,
     role: &UserRole,
-    secret: &str,
-    expiration_hours: i64,
+    token_version: i32,
+    config: &AppConfig,
 ) -> Result<String, AppError> {
+    let keys = &config.jwt_keys;
     let now = Utc::now();
+    let expiration_hours = config.jwt_expiration_hours_for(role);
     let exp = (now + chrono::Duration::hours(expiration_hours)).timestamp() as usize;
     let iat = now.timestamp() as usize;
 
@@ -37,32 +124,97 @@ pub fn create_token(
         role: role.to_string(),
         exp,
         iat,
+        nbf: Some(iat),
+        jti: Some(Uuid::new_v4()),
+        token_version,
     };
 
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
+    let mut header = Header::new(keys.algorithm);
+    header.kid = keys.public_jwk.as_ref().map(|(kid, _)| kid.clone());
+
+    encode(&header, &claims, &keys.encoding)
+        .map_err(|e| AppError::Internal(format!("Token creation failed: {}", e)))
+}
+
+/// Issues an opaque refresh token and stores its SHA-256 hash.
+/// The raw token is returned once and never persisted.
+pub async fn create_refresh_token<'e, E: sqlx::PgExecutor<'e>>(
+    db: E,
+    user_id: Uuid,
+    expiration_days: i64,
+) -> Result<String, AppError> {
+    let token = generate_opaque_token();
+    let expires_at = (Utc::now() + chrono::Duration::days(expiration_days)).naive_utc();
+
+    sqlx::query(
+        "INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
+         VALUES ($1, $2, $3)",
     )
-    .map_err(|e| AppError::Internal(format!("Token creation failed: {}", e)))
+    .bind(user_id)
+    .bind(hash_opaque_token(&token))
+    .bind(expires_at)
+    .execute(db)
+    .await?;
+
+    Ok(token)
 }
 
-pub fn verify_token(token: &str, secret: &str) -> Result<Claims, AppError> {
-    decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::default(),
+/// Signs the user out everywhere: outstanding access tokens fail the `token_version`
+/// check and refresh tokens are revoked, so only a new login gets back in.
+pub async fn revoke_all_sessions(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query("UPDATE users SET token_version = token_version + 1 WHERE id = $1")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query(
+        "UPDATE refresh_tokens SET revoked_at = NOW()
+         WHERE user_id = $1 AND revoked_at IS NULL",
     )
+    .bind(user_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// 256-bit random hex token for refresh and password-reset flows
+pub fn generate_opaque_token() -> String {
+    use argon2::password_hash::rand_core::{OsRng, RngCore};
+
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Opaque tokens are stored only as their SHA-256 hash
+pub fn hash_opaque_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub fn verify_token(token: &str, keys: &JwtKeys) -> Result<Claims, AppError> {
+    let mut validation = Validation::new(keys.algorithm);
+    validation.validate_nbf = true;
+
+    decode::<Claims>(token, &keys.decoding, &validation)
     .map(|data| data.claims)
-    .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))
+    .map_err(|e| AppError::Unauthorized(codes::INVALID_TOKEN, format!("Invalid token: {}", e)))
 }
 
 // Extractor for authenticated user
 #[derive(Debug, Clone)]
 pub struct AuthUser {
     pub user_id: Uuid,
+    #[allow(dead_code)]
     pub username: String,
     pub role: UserRole,
+    /// Id and expiry of the presented token, used for revocation on logout
+    pub jti: Option<Uuid>,
+    pub token_exp: usize,
 }
 
 impl AuthUser {
@@ -76,7 +228,7 @@ impl AuthUser {
 }
 
 impl FromRequestParts<AppState> for AuthUser {
-    type Rejection = (StatusCode, axum::Json<serde_json::Value>);
+    type Rejection = AppError;
 
     async fn from_request_parts(
         parts: &mut Parts,
@@ -87,43 +239,196 @@ impl FromRequestParts<AppState> for AuthUser {
             .get(AUTHORIZATION)
             .and_then(|v| v.to_str().ok())
             .ok_or_else(|| {
-                (
-                    StatusCode::UNAUTHORIZED,
-                    axum::Json(serde_json::json!({"error": "Missing Authorization header"})),
+                AppError::Unauthorized(
+                    codes::MISSING_TOKEN,
+                    "Missing Authorization header".to_string(),
                 )
             })?;
 
         let token = auth_header.strip_prefix("Bearer ").ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                axum::Json(serde_json::json!({"error": "Invalid Authorization header format. Use: Bearer <token>"})),
+            AppError::Unauthorized(
+                codes::INVALID_TOKEN,
+                "Invalid Authorization header format. Use: Bearer <token>".to_string(),
             )
         })?;
 
-        let claims = verify_token(token, &state.config.jwt_secret).map_err(|e| {
-            (
-                StatusCode::UNAUTHORIZED,
-                axum::Json(serde_json::json!({"error": e.to_string()})),
-            )
-        })?;
+        authenticate(state, token, parts.uri.path()).await
+    }
+}
+
+/// The access token from `Authorization: Bearer`, or else from a `token` query parameter,
+/// for streaming endpoints whose browser clients (WebSocket, EventSource) cannot set headers
+pub fn token_from(headers: &HeaderMap, query_token: Option<String>) -> Result<String, AppError> {
+    query_token
+        .or_else(|| {
+            headers
+                .get(AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .map(str::to_string)
+        })
+        .ok_or_else(|| {
+            AppError::Unauthorized(codes::MISSING_TOKEN, "Missing access token".to_string())
+        })
+}
 
-        let role = match claims.role.as_str() {
-            "admin" => UserRole::Admin,
-            "manager" => UserRole::Manager,
-            "tester" => UserRole::Tester,
-            "developer" => UserRole::Developer,
-            _ => {
-                return Err((
-                    StatusCode::UNAUTHORIZED,
-                    axum::Json(serde_json::json!({"error": "Invalid role in token"})),
-                ));
+/// Verifies a bearer token for a request to `path` and checks it against the user's current
+/// state. Used by the extractor and by endpoints that take the token another way (e.g.
+/// WebSockets), so the role restrictions below hold for both.
+pub async fn authenticate(
+    state: &AppState,
+    token: &str,
+    path: &str,
+) -> Result<AuthUser, AppError> {
+    let claims = verify_token(token, &state.config.jwt_keys)?;
+
+    // One round trip: deactivating (or deleting) a user cuts off their live tokens
+    // too, not just new logins. The role is read here rather than from the claim, so a
+    // role change applies to live tokens as well.
+    let row: Option<(bool, i32, bool, UserRole)> = sqlx::query_as(
+        "SELECT u.is_active, u.token_version,
+                EXISTS(SELECT 1 FROM revoked_tokens WHERE jti = $2), u.role
+         FROM users u WHERE u.id = $1",
+    )
+    .bind(claims.sub)
+    .bind(claims.jti)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some((true, token_version, revoked, role)) = row else {
+        return Err(AppError::Unauthorized(
+            codes::ACCOUNT_DEACTIVATED,
+            "Account is deactivated".to_string(),
+        ));
+    };
+    if revoked || claims.token_version != token_version {
+        return Err(AppError::Unauthorized(
+            codes::TOKEN_REVOKED,
+            "Token has been revoked".to_string(),
+        ));
+    }
+
+    // Service accounts only pull the change feed (and may log out)
+    if role == UserRole::Sync && !path.starts_with("/api/sync/") && path != "/api/auth/logout" {
+        return Err(AppError::Forbidden(
+            codes::SYNC_ACCOUNT_RESTRICTED,
+            "Sync accounts can only use the sync API".to_string(),
+        ));
+    }
+
+    Ok(AuthUser {
+        user_id: claims.sub,
+        username: claims.username,
+        role,
+        jti: claims.jti,
+        token_exp: claims.exp,
+    })
+}
+
+/// Periodically purges revoked, refresh and password-reset token rows (and idempotency keys)
+/// that are past their expiry.
+pub fn spawn_token_cleanup(db: sqlx::PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            if let Err(e) = sqlx::query("DELETE FROM revoked_tokens WHERE expires_at < NOW()")
+                .execute(&db)
+                .await
+            {
+                tracing::warn!("Revoked token cleanup failed: {:?}", e);
+            }
+            if let Err(e) = sqlx::query("DELETE FROM refresh_tokens WHERE expires_at < NOW()")
+                .execute(&db)
+                .await
+            {
+                tracing::warn!("Refresh token cleanup failed: {:?}", e);
+            }
+            if let Err(e) =
+                sqlx::query("DELETE FROM password_reset_tokens WHERE expires_at < NOW()")
+                    .execute(&db)
+                    .await
+            {
+                tracing::warn!("Password reset token cleanup failed: {:?}", e);
             }
+            if let Err(e) = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at < NOW()")
+                .execute(&db)
+                .await
+            {
+                tracing::warn!("Idempotency key cleanup failed: {:?}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::user_handler;
+    use crate::test_support::{self, create_user};
+    use axum::{extract::{Path, State}, Json};
+    use sqlx::PgPool;
+
+    #[test]
+    fn token_expiry_follows_the_role_override() {
+        let mut config = test_support::config();
+        config.jwt_expiration_hours_by_role = vec![(UserRole::Admin, 2)];
+        let hours = |role: UserRole| {
+            let token = create_token(Uuid::new_v4(), "user", &role, 0, &config).unwrap();
+            let claims = verify_token(&token, &config.jwt_keys).unwrap();
+            (claims.exp - claims.iat) / 3600
         };
 
-        Ok(AuthUser {
-            user_id: claims.sub,
-            username: claims.username,
-            role,
-        })
+        assert_eq!(hours(UserRole::Admin), 2);
+        assert_eq!(hours(UserRole::Tester), 24);
+    }
+
+    #[sqlx::test]
+    async fn deactivation_cuts_off_a_live_session(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let admin = create_user(&pool, "admin", UserRole::Admin).await;
+        let user = create_user(&pool, "dev", UserRole::Developer).await;
+        let token = create_token(user, "dev", &UserRole::Developer, 0, &state.config).unwrap();
+        assert_eq!(authenticate(&state, &token, "/api/tasks").await.unwrap().user_id, user);
+
+        let request = serde_json::from_value(serde_json::json!({ "is_active": false })).unwrap();
+        let admin = test_support::auth(admin, UserRole::Admin);
+        let _ = user_handler::update_user(State(state.clone()), admin, Path(user), Json(request))
+            .await
+            .unwrap();
+
+        let err = authenticate(&state, &token, "/api/tasks").await.err().unwrap();
+        assert!(matches!(err, AppError::Unauthorized(codes::ACCOUNT_DEACTIVATED, _)));
+    }
+
+    #[sqlx::test]
+    async fn demotion_applies_to_a_live_session(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let admin = create_user(&pool, "admin", UserRole::Admin).await;
+        let user = create_user(&pool, "second", UserRole::Admin).await;
+        let token = create_token(user, "second", &UserRole::Admin, 0, &state.config).unwrap();
+        assert!(authenticate(&state, &token, "/api/users").await.unwrap().is_admin());
+
+        let request = serde_json::from_value(serde_json::json!({ "role": "tester" })).unwrap();
+        let admin = test_support::auth(admin, UserRole::Admin);
+        let _ = user_handler::update_user(State(state.clone()), admin, Path(user), Json(request))
+            .await
+            .unwrap();
+
+        let auth = authenticate(&state, &token, "/api/users").await.unwrap();
+        assert_eq!(auth.role, UserRole::Tester);
+    }
+
+    #[sqlx::test]
+    async fn sync_accounts_cannot_open_streams(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let user = create_user(&pool, "warehouse", UserRole::Sync).await;
+        let token = create_token(user, "warehouse", &UserRole::Sync, 0, &state.config).unwrap();
+        assert!(authenticate(&state, &token, "/api/sync/tasks").await.is_ok());
+
+        for path in ["/ws/tasks", "/api/notifications/stream"] {
+            let err = authenticate(&state, &token, path).await.err().unwrap();
+            assert!(matches!(err, AppError::Forbidden(codes::SYNC_ACCOUNT_RESTRICTED, _)));
+        }
     }
 }
@@ -1,29 +1,393 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use sqlx::postgres::PgPoolOptions;
-use sqlx::PgPool;
+use sqlx::{Executor, PgPool};
+
+use crate::auth::JwtKeys;
+use crate::models::UserRole;
+
+/// Optional task fields that can be hidden from some roles
+pub const RESTRICTABLE_TASK_FIELDS: &[&str] = &[
+    "description",
+    "acceptance_criteria",
+    "evaluation_criteria",
+    "comment",
+];
 
 #[derive(Clone)]
 pub struct AppConfig {
-    pub jwt_secret: String,
+    pub jwt_keys: JwtKeys,
     pub jwt_expiration_hours: i64,
+    /// Roles whose access tokens live longer or shorter than `jwt_expiration_hours`
+    pub jwt_expiration_hours_by_role: Vec<(UserRole, i64)>,
+    pub refresh_token_expiration_days: i64,
+    pub password_reset_token_minutes: i64,
+    /// Task field name -> roles allowed to see it. Fields not listed are visible to everyone.
+    pub task_field_visibility: HashMap<String, Vec<UserRole>>,
+    pub password_policy: PasswordPolicy,
+    pub password_breach_check: bool,
+    pub password_breach_api_url: String,
+    pub password_breach_timeout_secs: u64,
+    /// Secret appended to every password before hashing; changing it invalidates all hashes
+    pub password_pepper: Option<String>,
+    /// Explicit CORS origins; `None` means any origin (without credentials)
+    pub cors_allowed_origins: Option<Vec<String>>,
+    /// Time a handler has to produce the response head; 0 disables the limit
+    pub request_timeout_secs: u64,
+    /// gzip/brotli response compression for clients that accept it
+    pub compression_enabled: bool,
+    /// Failed logins per client IP / username allowed inside the window; 0 disables the limit
+    pub login_max_failures: usize,
+    pub login_failure_window_secs: u64,
+    /// Consecutive bad passwords that lock an account; 0 disables the lockout
+    pub account_lockout_threshold: i32,
+    pub account_lockout_minutes: i32,
+    pub attachments_dir: PathBuf,
+    pub attachment_max_size_bytes: u64,
+    /// Cap on every other request body; larger ones get 413
+    pub request_body_max_size_bytes: usize,
+    pub notifications_enabled: bool,
+    pub smtp: SmtpConfig,
+    pub webhook_timeout_secs: u64,
+    /// Extra delivery attempts after the first failure
+    pub webhook_max_retries: u32,
+    /// AES-256 key for stored TOTP secrets (SHA-256 of `TOTP_ENCRYPTION_KEY`, else of `JWT_SECRET`)
+    pub totp_encryption_key: [u8; 32],
+    /// Issuer shown in authenticator apps
+    pub totp_issuer: String,
+    /// Refuse to move a task to done/closed while a task blocking it is unfinished
+    pub dependencies_block_completion: bool,
+    /// Refuse to move a task to done while one of its checklist items is unchecked
+    pub checklist_blocks_completion: bool,
+    /// Lets admins create, edit and delete tasks like managers (by default they cannot)
+    pub admins_can_manage_tasks: bool,
+    /// Reject out-of-range `page`/`per_page` with 400 instead of clamping them
+    pub strict_pagination: bool,
+    /// Prepended to `task_number` to form `display_number` (e.g. `TF-142`)
+    pub task_number_prefix: String,
+    /// External URL of the API (e.g. behind a reverse proxy), advertised as the OpenAPI server
+    pub public_base_url: Option<String>,
+    pub api_contact_name: Option<String>,
+    pub api_contact_email: Option<String>,
+    pub api_license_name: Option<String>,
+    pub api_license_url: Option<String>,
+}
+
+/// Requirements every new password must meet (`PASSWORD_*`)
+#[derive(Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    /// Any character that is neither a letter nor a digit
+    pub require_symbol: bool,
+}
+
+#[derive(Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    /// `starttls`, `tls` (implicit TLS) or `none`
+    pub tls: String,
 }
 
 pub async fn create_db_pool() -> PgPool {
     let database_url =
         std::env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env file");
 
+    // Defaults match sqlx's own (except max_connections, which was always 10)
+    let max_connections: u32 = std::env::var("DB_MAX_CONNECTIONS")
+        .unwrap_or_else(|_| "10".to_string())
+        .parse()
+        .expect("DB_MAX_CONNECTIONS must be a number");
+    let min_connections: u32 = std::env::var("DB_MIN_CONNECTIONS")
+        .unwrap_or_else(|_| "0".to_string())
+        .parse()
+        .expect("DB_MIN_CONNECTIONS must be a number");
+    let acquire_timeout_secs: u64 = std::env::var("DB_ACQUIRE_TIMEOUT_SECS")
+        .unwrap_or_else(|_| "30".to_string())
+        .parse()
+        .expect("DB_ACQUIRE_TIMEOUT_SECS must be a number");
+    let idle_timeout_secs: u64 = std::env::var("DB_IDLE_TIMEOUT_SECS")
+        .unwrap_or_else(|_| "600".to_string())
+        .parse()
+        .expect("DB_IDLE_TIMEOUT_SECS must be a number");
+    let statement_timeout_secs: u64 = std::env::var("DB_STATEMENT_TIMEOUT_SECS")
+        .unwrap_or_else(|_| "30".to_string())
+        .parse()
+        .expect("DB_STATEMENT_TIMEOUT_SECS must be a number");
+
+    assert!(
+        min_connections <= max_connections,
+        "DB_MIN_CONNECTIONS must not exceed DB_MAX_CONNECTIONS"
+    );
+
     PgPoolOptions::new()
-        .max_connections(10)
+        .max_connections(max_connections)
+        .min_connections(min_connections)
+        .acquire_timeout(std::time::Duration::from_secs(acquire_timeout_secs))
+        .idle_timeout(std::time::Duration::from_secs(idle_timeout_secs))
+        // Postgres cancels any statement running longer than this (0 = no limit)
+        .after_connect(move |conn, _meta| {
+            Box::pin(async move {
+                let sql = format!("SET statement_timeout = '{}s'", statement_timeout_secs);
+                conn.execute(sql.as_str()).await?;
+                Ok(())
+            })
+        })
         .connect(&database_url)
         .await
         .expect("Failed to connect to PostgreSQL")
 }
 
 pub fn load_config() -> AppConfig {
+    let jwt_secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+    let totp_key_source = std::env::var("TOTP_ENCRYPTION_KEY").unwrap_or_else(|_| jwt_secret.clone());
+
     AppConfig {
-        jwt_secret: std::env::var("JWT_SECRET").expect("JWT_SECRET must be set"),
+        // RS256 when a private key is configured, HS256 with JWT_SECRET otherwise
+        jwt_keys: match std::env::var("JWT_PRIVATE_KEY_PEM") {
+            Ok(pem) if !pem.trim().is_empty() => JwtKeys::rs256(&pem),
+            _ => JwtKeys::hs256(&jwt_secret),
+        },
         jwt_expiration_hours: std::env::var("JWT_EXPIRATION_HOURS")
             .unwrap_or_else(|_| "24".to_string())
             .parse()
             .expect("JWT_EXPIRATION_HOURS must be a number"),
+        jwt_expiration_hours_by_role: [
+            UserRole::Admin,
+            UserRole::Manager,
+            UserRole::Tester,
+            UserRole::Developer,
+            UserRole::Sync,
+        ]
+        .into_iter()
+        .filter_map(|role| {
+            let var = format!("JWT_EXPIRATION_HOURS_{}", role.to_string().to_uppercase());
+            let hours = std::env::var(&var)
+                .ok()?
+                .parse()
+                .unwrap_or_else(|_| panic!("{} must be a number", var));
+            Some((role, hours))
+        })
+        .collect(),
+        refresh_token_expiration_days: std::env::var("REFRESH_TOKEN_EXPIRATION_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .expect("REFRESH_TOKEN_EXPIRATION_DAYS must be a number"),
+        password_reset_token_minutes: std::env::var("PASSWORD_RESET_TOKEN_MINUTES")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .expect("PASSWORD_RESET_TOKEN_MINUTES must be a number"),
+        task_field_visibility: parse_field_visibility(
+            &std::env::var("TASK_FIELD_VISIBILITY")
+                .unwrap_or_else(|_| "evaluation_criteria:admin,manager".to_string()),
+        ),
+        password_policy: PasswordPolicy {
+            min_length: std::env::var("PASSWORD_MIN_LENGTH")
+                .unwrap_or_else(|_| "6".to_string())
+                .parse()
+                .expect("PASSWORD_MIN_LENGTH must be a number"),
+            require_uppercase: std::env::var("PASSWORD_REQUIRE_UPPERCASE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .expect("PASSWORD_REQUIRE_UPPERCASE must be true or false"),
+            require_lowercase: std::env::var("PASSWORD_REQUIRE_LOWERCASE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .expect("PASSWORD_REQUIRE_LOWERCASE must be true or false"),
+            require_digit: std::env::var("PASSWORD_REQUIRE_DIGIT")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .expect("PASSWORD_REQUIRE_DIGIT must be true or false"),
+            require_symbol: std::env::var("PASSWORD_REQUIRE_SYMBOL")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .expect("PASSWORD_REQUIRE_SYMBOL must be true or false"),
+        },
+        password_breach_check: std::env::var("PASSWORD_BREACH_CHECK")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .expect("PASSWORD_BREACH_CHECK must be true or false"),
+        password_breach_api_url: std::env::var("PASSWORD_BREACH_API_URL")
+            .unwrap_or_else(|_| "https://api.pwnedpasswords.com/range/".to_string()),
+        password_breach_timeout_secs: std::env::var("PASSWORD_BREACH_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()
+            .expect("PASSWORD_BREACH_TIMEOUT_SECS must be a number"),
+        password_pepper: std::env::var("PASSWORD_PEPPER").ok().filter(|p| !p.is_empty()),
+        cors_allowed_origins: std::env::var("CORS_ALLOWED_ORIGINS").ok().and_then(|raw| {
+            let origins: Vec<String> = raw
+                .split(',')
+                .map(|o| o.trim().to_string())
+                .filter(|o| !o.is_empty())
+                .collect();
+            (!origins.is_empty()).then_some(origins)
+        }),
+        request_timeout_secs: std::env::var("REQUEST_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()
+            .expect("REQUEST_TIMEOUT_SECS must be a number"),
+        compression_enabled: std::env::var("COMPRESSION_ENABLED")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .expect("COMPRESSION_ENABLED must be true or false"),
+        login_max_failures: std::env::var("LOGIN_MAX_FAILURES")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .expect("LOGIN_MAX_FAILURES must be a number"),
+        login_failure_window_secs: std::env::var("LOGIN_FAILURE_WINDOW_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .expect("LOGIN_FAILURE_WINDOW_SECS must be a number"),
+        account_lockout_threshold: std::env::var("ACCOUNT_LOCKOUT_THRESHOLD")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .expect("ACCOUNT_LOCKOUT_THRESHOLD must be a number"),
+        account_lockout_minutes: std::env::var("ACCOUNT_LOCKOUT_MINUTES")
+            .unwrap_or_else(|_| "15".to_string())
+            .parse()
+            .expect("ACCOUNT_LOCKOUT_MINUTES must be a number"),
+        attachments_dir: std::env::var("ATTACHMENTS_DIR")
+            .unwrap_or_else(|_| "./uploads".to_string())
+            .into(),
+        attachment_max_size_bytes: std::env::var("ATTACHMENT_MAX_SIZE_BYTES")
+            .unwrap_or_else(|_| "10485760".to_string())
+            .parse()
+            .expect("ATTACHMENT_MAX_SIZE_BYTES must be a number"),
+        request_body_max_size_bytes: std::env::var("REQUEST_BODY_MAX_SIZE_BYTES")
+            .unwrap_or_else(|_| "1048576".to_string())
+            .parse()
+            .expect("REQUEST_BODY_MAX_SIZE_BYTES must be a number"),
+        notifications_enabled: std::env::var("NOTIFICATIONS_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .expect("NOTIFICATIONS_ENABLED must be true or false"),
+        smtp: SmtpConfig {
+            host: std::env::var("SMTP_HOST").unwrap_or_else(|_| "localhost".to_string()),
+            port: std::env::var("SMTP_PORT")
+                .unwrap_or_else(|_| "587".to_string())
+                .parse()
+                .expect("SMTP_PORT must be a number"),
+            username: std::env::var("SMTP_USERNAME").ok().filter(|u| !u.is_empty()),
+            password: std::env::var("SMTP_PASSWORD").ok(),
+            from: std::env::var("SMTP_FROM")
+                .unwrap_or_else(|_| "TestFlow <noreply@testflow.local>".to_string()),
+            tls: std::env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string()),
+        },
+        webhook_timeout_secs: std::env::var("WEBHOOK_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .expect("WEBHOOK_TIMEOUT_SECS must be a number"),
+        webhook_max_retries: std::env::var("WEBHOOK_MAX_RETRIES")
+            .unwrap_or_else(|_| "2".to_string())
+            .parse()
+            .expect("WEBHOOK_MAX_RETRIES must be a number"),
+        totp_encryption_key: {
+            use sha2::{Digest, Sha256};
+            Sha256::digest(totp_key_source.as_bytes()).into()
+        },
+        totp_issuer: std::env::var("TOTP_ISSUER").unwrap_or_else(|_| "TestFlow".to_string()),
+        dependencies_block_completion: std::env::var("DEPENDENCIES_BLOCK_COMPLETION")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .expect("DEPENDENCIES_BLOCK_COMPLETION must be true or false"),
+        checklist_blocks_completion: std::env::var("CHECKLIST_BLOCKS_COMPLETION")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .expect("CHECKLIST_BLOCKS_COMPLETION must be true or false"),
+        admins_can_manage_tasks: std::env::var("ADMINS_CAN_MANAGE_TASKS")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .expect("ADMINS_CAN_MANAGE_TASKS must be true or false"),
+        strict_pagination: std::env::var("STRICT_PAGINATION")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .expect("STRICT_PAGINATION must be true or false"),
+        task_number_prefix: std::env::var("TASK_NUMBER_PREFIX").unwrap_or_else(|_| "TF-".to_string()),
+        public_base_url: std::env::var("PUBLIC_BASE_URL")
+            .ok()
+            .map(|u| u.trim().trim_end_matches('/').to_string())
+            .filter(|u| !u.is_empty()),
+        api_contact_name: std::env::var("API_CONTACT_NAME").ok().filter(|v| !v.is_empty()),
+        api_contact_email: std::env::var("API_CONTACT_EMAIL").ok().filter(|v| !v.is_empty()),
+        api_license_name: std::env::var("API_LICENSE_NAME").ok().filter(|v| !v.is_empty()),
+        api_license_url: std::env::var("API_LICENSE_URL").ok().filter(|v| !v.is_empty()),
+    }
+}
+
+impl AppConfig {
+    /// Access token lifetime for `role`: its override if set, the global value otherwise
+    pub fn jwt_expiration_hours_for(&self, role: &UserRole) -> i64 {
+        self.jwt_expiration_hours_by_role
+            .iter()
+            .find(|(r, _)| r == role)
+            .map_or(self.jwt_expiration_hours, |(_, hours)| *hours)
+    }
+
+    /// Human-friendly task reference, e.g. `TF-142`
+    pub fn display_number(&self, task_number: i32) -> String {
+        format!("{}{}", self.task_number_prefix, task_number)
+    }
+
+    /// Accepts either the bare number (`142`) or the display form (`TF-142`, any case)
+    pub fn parse_task_number(&self, raw: &str) -> Option<i32> {
+        let raw = raw.trim();
+        let prefix = &self.task_number_prefix;
+        let digits = match raw.get(..prefix.len()) {
+            Some(head) if !prefix.is_empty() && head.eq_ignore_ascii_case(prefix) => &raw[prefix.len()..],
+            _ => raw,
+        };
+        digits.parse().ok().filter(|n: &i32| *n > 0)
+    }
+}
+
+/// Parses `field:role,role;field:role` into a field -> allowed roles map.
+fn parse_field_visibility(raw: &str) -> HashMap<String, Vec<UserRole>> {
+    raw.split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (field, roles) = entry
+                .split_once(':')
+                .expect("TASK_FIELD_VISIBILITY entries must look like field:role,role");
+            let field = field.trim();
+            if !RESTRICTABLE_TASK_FIELDS.contains(&field) {
+                panic!("TASK_FIELD_VISIBILITY: unknown task field '{}'", field);
+            }
+            let roles = roles
+                .split(',')
+                .map(str::trim)
+                .filter(|r| !r.is_empty())
+                .map(|r| {
+                    r.parse::<UserRole>()
+                        .unwrap_or_else(|e| panic!("TASK_FIELD_VISIBILITY: {}", e))
+                })
+                .collect();
+            (field.to_string(), roles)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn role_override_wins_over_the_global_expiration() {
+        let mut config = test_support::config();
+        config.jwt_expiration_hours = 24;
+        config.jwt_expiration_hours_by_role = vec![(UserRole::Admin, 2), (UserRole::Tester, 72)];
+
+        assert_eq!(config.jwt_expiration_hours_for(&UserRole::Admin), 2);
+        assert_eq!(config.jwt_expiration_hours_for(&UserRole::Tester), 72);
+        assert_eq!(config.jwt_expiration_hours_for(&UserRole::Manager), 24);
+        assert_eq!(config.jwt_expiration_hours_for(&UserRole::Developer), 24);
     }
 }
@@ -1,49 +1,155 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::models::{TaskStatus, TaskUrgency, UserRole};
+use crate::models::{
+    NotificationKind, TaskStatus, TaskUrgency, TemplateRecurrence, UserRole, WebhookEvent,
+};
 
 // ── Auth ──
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct LoginRequest {
+    /// Username (any case) or email address
     #[validate(length(min = 1, message = "Username is required"))]
     pub username: String,
     #[validate(length(min = 1, message = "Password is required"))]
     pub password: String,
+    /// Current authenticator code or an unused recovery code; required once 2FA is enabled
+    pub totp_code: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct LoginResponse {
     pub token: String,
     pub token_type: String,
+    pub refresh_token: String,
     pub user: UserResponse,
 }
 
-// ── User DTOs ──
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RefreshRequest {
+    #[validate(length(min = 1, message = "Refresh token is required"))]
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LogoutRequest {
+    /// Refresh token of this session, revoked together with the access token
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RefreshResponse {
+    pub token: String,
+    pub token_type: String,
+    pub refresh_token: String,
+}
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ForgotPasswordRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResetPasswordRequest {
+    #[validate(length(min = 1, message = "Reset token is required"))]
+    pub token: String,
+    /// Checked against the password policy
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TwoFactorSetupResponse {
+    /// Base32 secret for manual entry
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code
+    pub otpauth_url: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VerifyTwoFactorRequest {
+    #[validate(length(equal = 6, message = "Code must be 6 digits"))]
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryCodesResponse {
+    /// Single-use codes accepted in place of `totp_code`; shown only once
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessageResponse {
+    pub message: String,
+}
+
+// ── User DTOs ──
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateUserRequest {
     #[validate(length(min = 3, max = 50, message = "Username must be 3-50 characters"))]
     pub username: String,
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
-    #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
+    /// Checked against the password policy
     pub password: String,
     #[validate(length(min = 1, max = 100, message = "Full name is required"))]
     pub full_name: String,
     pub role: UserRole,
 }
 
+/// One row of `POST /api/users/import`; the initial password is generated
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ImportUserRow {
+    #[validate(length(min = 3, max = 50, message = "Username must be 3-50 characters"))]
+    pub username: String,
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    #[validate(length(min = 1, max = 100, message = "Full name is required"))]
+    pub full_name: String,
+    pub role: UserRole,
+}
+
+/// What happens to the rest of an import when one row fails
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UserImportMode {
+    /// Nothing is created unless every row succeeds (default)
+    #[default]
+    Atomic,
+    /// Valid rows are created, failed ones are reported
+    Continue,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UserImportParams {
+    pub mode: Option<UserImportMode>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserImportResult {
+    /// 1-based position of the row in the upload (the CSV header is not counted)
+    pub row: usize,
+    pub username: Option<String>,
+    pub success: bool,
+    pub user_id: Option<Uuid>,
+    /// Generated password, shown only in this response
+    pub initial_password: Option<String>,
+    /// Why the row was not imported, when `success` is false
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateUserRequest {
     #[validate(length(min = 3, max = 50, message = "Username must be 3-50 characters"))]
     pub username: Option<String>,
     #[validate(email(message = "Invalid email format"))]
     pub email: Option<String>,
-    #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
+    /// Checked against the password policy
     pub password: Option<String>,
     #[validate(length(min = 1, max = 100, message = "Full name is required"))]
     pub full_name: Option<String>,
@@ -51,6 +157,22 @@ pub struct UpdateUserRequest {
     pub is_active: Option<bool>,
 }
 
+/// Fields a user may change on their own account
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateProfileRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: Option<String>,
+    #[validate(length(min = 1, max = 100, message = "Full name is required"))]
+    pub full_name: Option<String>,
+    /// Only read so that an attempt to change it is rejected rather than silently dropped
+    #[serde(default)]
+    #[schema(ignore)]
+    pub role: Option<serde_json::Value>,
+    #[serde(default)]
+    #[schema(ignore)]
+    pub is_active: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserResponse {
     pub id: Uuid,
@@ -61,11 +183,33 @@ pub struct UserResponse {
     pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// `null` if the user never logged in
+    pub last_login_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UserFilterParams {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub role: Option<UserRole>,
+    pub is_active: Option<bool>,
+    /// Case-insensitive substring of username, email or full name
+    pub q: Option<String>,
+    pub sort_by: Option<UserSortField>,
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortField {
+    CreatedAt,
+    LastLoginAt,
+    Username,
 }
 
 // ── Task DTOs ──
 
-#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateTaskRequest {
     #[validate(length(min = 1, max = 255, message = "Title is required"))]
     pub title: String,
@@ -75,25 +219,129 @@ pub struct CreateTaskRequest {
     pub acceptance_criteria: Option<String>,
     pub evaluation_criteria: Option<String>,
     pub comment: Option<String>,
+    /// Deadline, e.g. `2026-10-20T18:00:00`; must not be in the past
+    pub due_date: Option<NaiveDateTime>,
+    /// Expected effort in hours
+    #[validate(range(min = 0.0, max = 10000.0, message = "Estimate must be between 0 and 10000 hours"))]
+    pub estimate_hours: Option<f64>,
 }
 
+/// Distinguishes an explicit `null` (`Some(None)`, clear the field) from an
+/// absent key (`None`, keep the current value). Use with `#[serde(default)]`.
+fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Reads a filter given as a comma-separated string (`status=new,testing` in a query)
+/// or as a list (saved view JSON). An empty value means "no filter".
+fn comma_separated<'de, T, D>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    T: serde::de::DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    use serde::de::{value::StrDeserializer, Error, IntoDeserializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(String),
+    }
+
+    let values = match Option::<OneOrMany<T>>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(OneOrMany::Many(values)) => values,
+        Some(OneOrMany::One(raw)) => raw
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                let de: StrDeserializer<'_, D::Error> = v.into_deserializer();
+                T::deserialize(de).map_err(D::Error::custom)
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    Ok(Some(values).filter(|v| !v.is_empty()))
+}
+
+/// Partial update: omitted fields are left unchanged. Nullable fields
+/// (`description`, `tester_id`, the criteria, `comment`, `due_date`) are
+/// cleared by sending them as `null`.
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateTaskRequest {
     #[validate(length(min = 1, max = 255, message = "Title must not be empty"))]
     pub title: Option<String>,
-    pub description: Option<String>,
-    pub tester_id: Option<Uuid>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<Uuid>)]
+    pub tester_id: Option<Option<Uuid>>,
     pub status: Option<TaskStatus>,
     pub urgency: Option<TaskUrgency>,
-    pub acceptance_criteria: Option<String>,
-    pub evaluation_criteria: Option<String>,
-    pub comment: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub acceptance_criteria: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub evaluation_criteria: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub comment: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<NaiveDateTime>)]
+    pub due_date: Option<Option<NaiveDateTime>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<f64>)]
+    #[validate(range(min = 0.0, max = 10000.0, message = "Estimate must be between 0 and 10000 hours"))]
+    pub estimate_hours: Option<Option<f64>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignTaskRequest {
+    /// New tester; `null` unassigns the task
+    pub tester_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReopenTaskRequest {
+    /// Why the task is being reopened; kept in the task history
+    #[validate(length(min = 1, max = 1000, message = "Reason must be 1-1000 characters"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MarkDuplicateParams {
+    /// Move the duplicate's comments to the canonical task
+    pub move_comments: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct BulkStatusUpdateRequest {
+    #[validate(length(min = 1, max = 500, message = "Provide 1-500 task ids"))]
+    pub ids: Vec<Uuid>,
+    pub status: TaskStatus,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
+pub struct BulkStatusUpdateResult {
+    pub id: Uuid,
+    pub success: bool,
+    /// Why the task was skipped, when `success` is false
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TaskResponse {
     pub id: Uuid,
     pub task_number: i32,
+    /// `task_number` with the configured prefix, e.g. `TF-142`
+    pub display_number: String,
     pub title: String,
     pub description: Option<String>,
     pub assigned_by: Uuid,
@@ -107,15 +355,308 @@ pub struct TaskResponse {
     pub acceptance_criteria: Option<String>,
     pub evaluation_criteria: Option<String>,
     pub comment: Option<String>,
+    pub due_date: Option<String>,
+    pub tags: Vec<String>,
+    pub deleted_at: Option<String>,
+    /// Canonical task this one duplicates
+    pub duplicate_of: Option<Uuid>,
+    /// Last change to the task's own fields
+    pub updated_at: String,
+    /// Tasks that must be finished before this one
+    pub blocked_by: Vec<Uuid>,
+    /// Tasks waiting on this one
+    pub blocks: Vec<Uuid>,
+    /// Expected effort in hours
+    pub estimate_hours: Option<f64>,
+    /// Hours logged against the task so far
+    pub logged_hours: f64,
+    /// Acceptance-criteria checklist, in display order
+    pub checklist: Vec<ChecklistItemResponse>,
+    /// How many checklist items are checked
+    pub checklist_done: usize,
+    /// Whether the caller follows the task; absent in webhook and live event payloads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_watching: Option<bool>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TaskListItem {
     pub id: Uuid,
     pub task_number: i32,
+    pub display_number: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub urgency: TaskUrgency,
+    pub due_date: Option<String>,
+    pub deleted_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OverdueTaskItem {
+    pub id: Uuid,
+    pub task_number: i32,
+    pub display_number: String,
     pub title: String,
     pub status: TaskStatus,
     pub urgency: TaskUrgency,
+    pub tester_id: Option<Uuid>,
+    pub tester_name: Option<String>,
+    pub due_date: String,
+    /// Whole days past the due date (0 if less than a day late)
+    pub days_overdue: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddDependencyRequest {
+    /// Task that must be finished first
+    pub depends_on_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AddTagRequest {
+    #[validate(length(min = 1, max = 50, message = "Tag must be 1-50 characters"))]
+    pub tag: String,
+}
+
+// ── Checklist DTOs ──
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AddChecklistItemRequest {
+    #[validate(length(min = 1, max = 500, message = "Checklist item must be 1-500 characters"))]
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateChecklistItemRequest {
+    #[validate(length(min = 1, max = 500, message = "Checklist item must be 1-500 characters"))]
+    pub text: Option<String>,
+    /// Check or uncheck the item
+    pub done: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReorderChecklistRequest {
+    /// Every item of the task's checklist, in the new order
+    pub ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChecklistItemResponse {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub text: String,
+    pub done: bool,
+    pub position: i32,
+}
+
+// ── Task templates ──
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateTemplateRequest {
+    #[validate(length(min = 1, max = 255, message = "Title is required"))]
+    pub title: String,
+    pub description: Option<String>,
+    pub urgency: Option<TaskUrgency>,
+    pub acceptance_criteria: Option<String>,
+    pub evaluation_criteria: Option<String>,
+    pub tester_id: Option<Uuid>,
+    /// Create a task from the template on this cadence; without it the template is used by hand
+    pub recurrence: Option<TemplateRecurrence>,
+    /// First scheduled run (default: now); requires `recurrence`
+    pub next_run_at: Option<NaiveDateTime>,
+}
+
+/// Partial update like `UpdateTaskRequest`; nullable fields are cleared with `null`.
+/// Clearing `recurrence` stops the schedule.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateTemplateRequest {
+    #[validate(length(min = 1, max = 255, message = "Title must not be empty"))]
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+    pub urgency: Option<TaskUrgency>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub acceptance_criteria: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub evaluation_criteria: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<Uuid>)]
+    pub tester_id: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<TemplateRecurrence>)]
+    pub recurrence: Option<Option<TemplateRecurrence>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<NaiveDateTime>)]
+    pub next_run_at: Option<Option<NaiveDateTime>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TemplateResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub urgency: TaskUrgency,
+    pub acceptance_criteria: Option<String>,
+    pub evaluation_criteria: Option<String>,
+    pub tester_id: Option<Uuid>,
+    /// Author of the template and of the tasks created on schedule
+    pub created_by: Uuid,
+    pub recurrence: Option<TemplateRecurrence>,
+    /// When the scheduler next creates a task; `null` without `recurrence`
+    pub next_run_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// ── Comment DTOs ──
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateCommentRequest {
+    #[validate(length(min = 1, max = 10000, message = "Comment must be 1-10000 characters"))]
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentResponse {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub author_id: Uuid,
+    pub author_name: Option<String>,
+    pub body: String,
+    pub created_at: String,
+}
+
+// ── Time tracking ──
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct LogTimeRequest {
+    #[validate(range(min = 0.01, max = 24.0, message = "Hours must be between 0.01 and 24"))]
+    pub hours: f64,
+    #[validate(length(max = 1000, message = "Note must be at most 1000 characters"))]
+    pub note: Option<String>,
+    /// When the work was done (default: now); must not be in the future
+    pub logged_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TimeEntryResponse {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: Option<String>,
+    pub hours: f64,
+    pub note: Option<String>,
+    pub logged_at: String,
+    pub created_at: String,
+}
+
+// ── Attachment DTOs ──
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttachmentResponse {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub uploaded_by_name: Option<String>,
+    pub filename: String,
+    pub content_type: String,
+    /// Size in bytes
+    pub size: i64,
+    pub download_url: String,
+    pub created_at: String,
+}
+
+// ── Task history ──
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskEventResponse {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// Names of the users in `old_value` / `new_value` when the field holds a user id
+    pub old_value_name: Option<String>,
+    pub new_value_name: Option<String>,
+    /// Why the change was made, when the endpoint asks for one (e.g. reopening)
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+/// One entry of a user's administration history.
+///
+/// `field` is a changed column (`username`, `email`, `full_name`, `role`, `is_active`) or one of
+/// `created`, `deleted` (values hold the username) and `password` (values are never stored).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserEventResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: String,
+}
+
+// ── Notifications ──
+
+/// Something that happened to one of the recipient's tasks
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    pub kind: NotificationKind,
+    pub task_id: Uuid,
+    pub task_number: i32,
+    pub display_number: String,
+    pub title: String,
+    /// Who assigned, commented on, reopened or moved the task
+    pub actor_id: Uuid,
+    /// Why the task was reopened (`task_reopened` only)
+    pub reason: Option<String>,
+    /// Status the task moved to (`task_status_changed` and `task_reopened`)
+    pub status: Option<TaskStatus>,
+    /// `null` while unread
+    pub read_at: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct NotificationFilterParams {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    /// Only notifications not yet marked as read
+    pub unread_only: Option<bool>,
+}
+
+/// A page of the inbox, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationListResponse {
+    pub items: Vec<NotificationResponse>,
+    /// Total number of notifications matching the filters, across all pages
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    /// Unread notifications in the whole inbox, regardless of filters
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnreadCountResponse {
+    pub unread_count: i64,
+}
+
+// ── Health ──
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HealthResponse {
+    /// `ok`, or `unavailable` when a dependency is down
+    pub status: String,
 }
 
 // ── Statistics ──
@@ -127,22 +668,351 @@ pub struct EmployeeStats {
     pub total_tasks: i64,
     pub completed_tasks: i64,
     pub in_progress_tasks: i64,
+    /// Mean hours from creation to completion over done/closed tasks; `null` if none
+    pub avg_completion_hours: Option<f64>,
 }
 
-// ── Pagination ──
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct StatusCounts {
+    pub new: i64,
+    pub in_progress: i64,
+    pub testing: i64,
+    pub done: i64,
+    pub closed: i64,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct UrgencyCounts {
+    pub low: i64,
+    pub medium: i64,
+    pub high: i64,
+    pub critical: i64,
+}
+
+/// Task counts visible to the caller, for dashboards
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct TaskSummaryResponse {
+    pub total: i64,
+    pub by_status: StatusCounts,
+    pub by_urgency: UrgencyCounts,
+}
+
+/// Task counts by status for one urgency level
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UrgencyBreakdown {
+    pub urgency: TaskUrgency,
+    pub new: i64,
+    pub in_progress: i64,
+    pub testing: i64,
+    pub done: i64,
+    pub closed: i64,
+}
+
+/// Hours an employee logged in the requested range
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EmployeeTimeStats {
+    pub user_id: Uuid,
+    pub full_name: String,
+    pub hours_logged: f64,
+    pub entries: i64,
+    /// Distinct tasks the hours were logged against
+    pub tasks: i64,
+}
+
+/// Width of a time series bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeBucket {
+    Day,
+    /// ISO weeks, starting on Monday
+    Week,
+}
+
+impl TimeBucket {
+    /// The `date_trunc` field name
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TimeBucket::Day => "day",
+            TimeBucket::Week => "week",
+        }
+    }
+}
 
 #[derive(Debug, Deserialize, ToSchema)]
-pub struct PaginationParams {
-    pub page: Option<i64>,
-    pub per_page: Option<i64>,
+pub struct TimeseriesParams {
+    /// Range start (default: 30 days before `to`)
+    pub from: Option<NaiveDateTime>,
+    /// Range end, inclusive (default: now)
+    pub to: Option<NaiveDateTime>,
+    /// Bucket width (default: day)
+    pub bucket: Option<TimeBucket>,
+}
+
+/// Tasks created and finished within one bucket
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TimeseriesPoint {
+    /// First day of the bucket, e.g. `2026-10-12`
+    pub bucket_start: String,
+    pub created: i64,
+    /// Tasks whose `closed_at` (done or closed) falls in the bucket
+    pub closed: i64,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
+pub struct EmployeeStatsParams {
+    /// Count only tasks created (completed tasks: closed) at or after this moment
+    pub from: Option<NaiveDateTime>,
+    /// ...and at or before this moment
+    pub to: Option<NaiveDateTime>,
+    /// Only members of this team
+    pub team_id: Option<Uuid>,
+    /// Only users with this role
+    pub role: Option<UserRole>,
+    /// Leave out deactivated users
+    pub active_only: Option<bool>,
+    /// Leave out users without tasks created or completed in the range
+    pub exclude_empty: Option<bool>,
+}
+
+// ── Pagination ──
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    /// Total number of rows matching the filters, across all pages
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    /// Pass as `after` to fetch the next page in keyset order; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 pub struct TaskFilterParams {
     pub page: Option<i64>,
     pub per_page: Option<i64>,
-    pub status: Option<TaskStatus>,
-    pub urgency: Option<TaskUrgency>,
+    /// One or more statuses, comma-separated: `new,in_progress,testing`
+    #[serde(default, deserialize_with = "comma_separated")]
+    #[schema(value_type = Option<Vec<TaskStatus>>)]
+    pub status: Option<Vec<TaskStatus>>,
+    /// One or more urgencies, comma-separated: `high,critical`
+    #[serde(default, deserialize_with = "comma_separated")]
+    #[schema(value_type = Option<Vec<TaskUrgency>>)]
+    pub urgency: Option<Vec<TaskUrgency>>,
     pub tester_id: Option<Uuid>,
     pub assigned_by: Option<Uuid>,
+    /// A single task by number, bare (`142`) or with the prefix (`TF-142`)
+    pub task_number: Option<String>,
+    /// Only tasks due strictly before this moment
+    pub due_before: Option<NaiveDateTime>,
+    /// Only tasks due at or after this moment
+    pub due_after: Option<NaiveDateTime>,
+    /// Only tasks created at or after this moment (RFC 3339)
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only tasks created strictly before this moment (RFC 3339)
+    pub created_before: Option<DateTime<Utc>>,
+    /// Comma-separated tags, e.g. `regression,frontend`
+    pub tags: Option<String>,
+    /// Whether a task needs any (default) or all of `tags`
+    pub tags_match: Option<TagMatch>,
+    /// Also return soft-deleted tasks (managers only)
+    pub include_deleted: Option<bool>,
+    /// Also return tasks marked as duplicates
+    pub include_duplicates: Option<bool>,
+    /// Only tasks whose tester or assigner is a member of this team
+    pub team_id: Option<Uuid>,
+    pub sort_by: Option<TaskSortField>,
+    pub order: Option<SortOrder>,
+    /// Opaque cursor from `next_cursor`; switches the list to keyset pagination
+    #[serde(skip_serializing)]
+    pub after: Option<String>,
+    /// Saved view whose filters are applied underneath the explicit ones
+    #[serde(skip_serializing)]
+    pub view: Option<Uuid>,
+}
+
+/// Which side of the assignment `GET /api/tasks/mine` matches on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MyTaskRole {
+    /// Tasks the caller is the tester of
+    Tester,
+    /// Tasks the caller created
+    Assigner,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MyTasksParams {
+    pub role: Option<MyTaskRole>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSortField {
+    CreatedAt,
+    UpdatedAt,
+    TaskNumber,
+    Urgency,
+    Status,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+    Any,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+// ── Saved views ──
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateViewRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    pub filters: TaskFilterParams,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SavedViewResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub filters: TaskFilterParams,
+    pub created_at: String,
+}
+
+// ── Webhooks ──
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateWebhookRequest {
+    #[validate(url(message = "Invalid URL"))]
+    pub url: String,
+    #[validate(length(min = 1, message = "At least one event type is required"))]
+    pub event_types: Vec<WebhookEvent>,
+    /// Shared secret for the `X-TestFlow-Signature` HMAC; never returned by the API
+    #[validate(length(min = 16, message = "Secret must be at least 16 characters"))]
+    pub secret: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateWebhookRequest {
+    #[validate(url(message = "Invalid URL"))]
+    pub url: Option<String>,
+    #[validate(length(min = 1, message = "At least one event type is required"))]
+    pub event_types: Option<Vec<WebhookEvent>>,
+    #[validate(length(min = 16, message = "Secret must be at least 16 characters"))]
+    pub secret: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// ── Teams ──
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateTeamRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateTeamRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddTeamMemberRequest {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TeamResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub member_ids: Vec<Uuid>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// ── Sync ──
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SyncTasksParams {
+    /// `next_cursor` of the previous page; omit to start from the beginning
+    pub since: Option<String>,
+    /// Changes per page (default 500, max 1000)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncChange {
+    /// Created after `since`
+    Created,
+    Updated,
+    /// Soft-deleted; the entry is a tombstone without `task`
+    Deleted,
+}
+
+/// A row of the `tasks` table. Tags, dependencies, the checklist and time entries live in
+/// their own tables and do not move `updated_at`, so the feed leaves them out.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncTask {
+    pub id: Uuid,
+    pub task_number: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub assigned_by: Uuid,
+    pub tester_id: Option<Uuid>,
+    pub status: TaskStatus,
+    pub urgency: TaskUrgency,
+    pub created_at: String,
+    pub closed_at: Option<String>,
+    pub acceptance_criteria: Option<String>,
+    pub evaluation_criteria: Option<String>,
+    pub comment: Option<String>,
+    pub due_date: Option<String>,
+    pub duplicate_of: Option<Uuid>,
+    pub updated_at: String,
+    pub estimate_hours: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncTaskChange {
+    pub change: SyncChange,
+    pub id: Uuid,
+    pub updated_at: String,
+    /// Current state of the task; absent for tombstones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<SyncTask>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncTasksPage {
+    /// Ordered by `(updated_at, id)`
+    pub changes: Vec<SyncTaskChange>,
+    /// Pass as `since` next time; stays the same when nothing changed
+    pub next_cursor: Option<String>,
+    /// Whether more changes are ready right away
+    pub has_more: bool,
 }
@@ -1,59 +1,297 @@
-use axum::http::StatusCode;
+use std::collections::BTreeMap;
+
+use axum::http::{header, HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde_json::json;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::i18n;
+
+/// Machine-readable error codes, sent as `"code"`. They are part of the API: a code is chosen
+/// where the error is raised and never derived from the message, so messages and their
+/// translations can change freely. Never rename a code.
+pub mod codes {
+    // Generic codes, for errors clients have no reason to tell apart from others of their status
+    pub const BAD_REQUEST: &str = "BAD_REQUEST";
+    pub const NOT_FOUND: &str = "NOT_FOUND";
+    pub const PRECONDITION_FAILED: &str = "PRECONDITION_FAILED";
+    pub const INTERNAL_ERROR: &str = "INTERNAL_ERROR";
+    pub const VALIDATION_FAILED: &str = "VALIDATION_FAILED";
+
+    pub const TASK_NOT_FOUND: &str = "TASK_NOT_FOUND";
+    pub const USER_NOT_FOUND: &str = "USER_NOT_FOUND";
+    pub const COMMENT_NOT_FOUND: &str = "COMMENT_NOT_FOUND";
+    pub const ATTACHMENT_NOT_FOUND: &str = "ATTACHMENT_NOT_FOUND";
+    pub const VIEW_NOT_FOUND: &str = "VIEW_NOT_FOUND";
+    pub const WEBHOOK_NOT_FOUND: &str = "WEBHOOK_NOT_FOUND";
+    pub const TEAM_NOT_FOUND: &str = "TEAM_NOT_FOUND";
+    pub const TAG_NOT_FOUND: &str = "TAG_NOT_FOUND";
+    pub const DEPENDENCY_NOT_FOUND: &str = "DEPENDENCY_NOT_FOUND";
+    pub const NOTIFICATION_NOT_FOUND: &str = "NOTIFICATION_NOT_FOUND";
+    pub const CHECKLIST_ITEM_NOT_FOUND: &str = "CHECKLIST_ITEM_NOT_FOUND";
+    pub const TEMPLATE_NOT_FOUND: &str = "TEMPLATE_NOT_FOUND";
+
+    pub const INVALID_CREDENTIALS: &str = "INVALID_CREDENTIALS";
+    pub const ACCOUNT_DEACTIVATED: &str = "ACCOUNT_DEACTIVATED";
+    pub const ACCOUNT_LOCKED: &str = "ACCOUNT_LOCKED";
+    pub const LOGIN_RATE_LIMITED: &str = "LOGIN_RATE_LIMITED";
+    pub const MISSING_TOKEN: &str = "MISSING_TOKEN";
+    pub const INVALID_TOKEN: &str = "INVALID_TOKEN";
+    pub const TOKEN_REVOKED: &str = "TOKEN_REVOKED";
+    pub const INVALID_REFRESH_TOKEN: &str = "INVALID_REFRESH_TOKEN";
+    pub const INVALID_RESET_TOKEN: &str = "INVALID_RESET_TOKEN";
+
+    pub const TWO_FACTOR_REQUIRED: &str = "TWO_FACTOR_REQUIRED";
+    pub const INVALID_TWO_FACTOR_CODE: &str = "INVALID_TWO_FACTOR_CODE";
+    pub const TWO_FACTOR_NOT_STARTED: &str = "TWO_FACTOR_NOT_STARTED";
+    pub const TWO_FACTOR_ALREADY_ENABLED: &str = "TWO_FACTOR_ALREADY_ENABLED";
+
+    pub const FORBIDDEN_ADMIN_EDIT: &str = "FORBIDDEN_ADMIN_EDIT";
+    pub const ADMIN_ONLY: &str = "ADMIN_ONLY";
+    pub const MANAGER_ONLY: &str = "MANAGER_ONLY";
+    pub const SYNC_ONLY: &str = "SYNC_ONLY";
+    pub const SYNC_ACCOUNT_RESTRICTED: &str = "SYNC_ACCOUNT_RESTRICTED";
+    pub const NOT_TASK_CREATOR: &str = "NOT_TASK_CREATOR";
+    pub const NOT_COMMENT_AUTHOR: &str = "NOT_COMMENT_AUTHOR";
+
+    pub const USERNAME_TAKEN: &str = "USERNAME_TAKEN";
+    pub const EMAIL_TAKEN: &str = "EMAIL_TAKEN";
+    pub const USERNAME_AND_EMAIL_TAKEN: &str = "USERNAME_AND_EMAIL_TAKEN";
+    pub const TEAM_NAME_TAKEN: &str = "TEAM_NAME_TAKEN";
+    pub const VIEW_NAME_TAKEN: &str = "VIEW_NAME_TAKEN";
+    pub const LAST_ADMIN: &str = "LAST_ADMIN";
+    pub const CANNOT_DELETE_SELF: &str = "CANNOT_DELETE_SELF";
+
+    pub const INVALID_STATUS_TRANSITION: &str = "INVALID_STATUS_TRANSITION";
+    pub const TASK_NOT_CLOSED: &str = "TASK_NOT_CLOSED";
+    pub const TASK_BLOCKED: &str = "TASK_BLOCKED";
+    pub const CHECKLIST_INCOMPLETE: &str = "CHECKLIST_INCOMPLETE";
+    pub const INVALID_CHECKLIST_ORDER: &str = "INVALID_CHECKLIST_ORDER";
+    pub const TASK_NOT_DELETED: &str = "TASK_NOT_DELETED";
+    pub const DUE_DATE_IN_PAST: &str = "DUE_DATE_IN_PAST";
+    pub const TIME_IN_FUTURE: &str = "TIME_IN_FUTURE";
+    pub const RECURRENCE_REQUIRED: &str = "RECURRENCE_REQUIRED";
+    pub const SELF_REFERENCE: &str = "SELF_REFERENCE";
+    pub const CYCLE: &str = "CYCLE";
+
+    pub const INVALID_CURSOR: &str = "INVALID_CURSOR";
+    pub const INVALID_DATE_RANGE: &str = "INVALID_DATE_RANGE";
+    pub const INVALID_BATCH_SIZE: &str = "INVALID_BATCH_SIZE";
+    pub const INVALID_IMPORT_BODY: &str = "INVALID_IMPORT_BODY";
+    pub const INVALID_TASK_NUMBER: &str = "INVALID_TASK_NUMBER";
+    pub const INVALID_PAGINATION: &str = "INVALID_PAGINATION";
 
+    pub const IDEMPOTENCY_KEY_INVALID: &str = "IDEMPOTENCY_KEY_INVALID";
+    pub const IDEMPOTENCY_KEY_REUSED: &str = "IDEMPOTENCY_KEY_REUSED";
+    pub const IDEMPOTENCY_KEY_IN_USE: &str = "IDEMPOTENCY_KEY_IN_USE";
+    pub const ATTACHMENT_TOO_LARGE: &str = "ATTACHMENT_TOO_LARGE";
+    pub const REQUEST_TIMEOUT: &str = "REQUEST_TIMEOUT";
+}
+
+/// Variants carrying a message take a code from [`codes`] first
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
-    #[error("Not found: {0}")]
-    NotFound(String),
+    #[error("Not found: {1}")]
+    NotFound(&'static str, String),
+
+    #[error("Bad request: {1}")]
+    BadRequest(&'static str, String),
+
+    #[error("Unauthorized: {1}")]
+    Unauthorized(&'static str, String),
+
+    #[error("Forbidden: {1}")]
+    Forbidden(&'static str, String),
 
-    #[error("Bad request: {0}")]
-    BadRequest(String),
+    #[error("Conflict: {1}")]
+    Conflict(&'static str, String),
 
-    #[error("Unauthorized: {0}")]
-    Unauthorized(String),
+    /// The username and/or email of a new or edited user belongs to another user;
+    /// the flags say which, rendered as `"fields"`
+    #[error("Conflict: {}", identity_taken_message(*username, *email))]
+    IdentityTaken { username: bool, email: bool },
 
-    #[error("Forbidden: {0}")]
-    Forbidden(String),
+    /// Password was correct but the account needs a TOTP (or recovery) code too
+    #[error("Two-factor authentication code required")]
+    TwoFactorRequired,
 
-    #[error("Conflict: {0}")]
-    Conflict(String),
+    /// `If-Match` named a version that is no longer current
+    #[error("Precondition failed: {1}")]
+    PreconditionFailed(&'static str, String),
+
+    #[error("Locked: {1}")]
+    Locked(&'static str, String),
+
+    #[error("Payload too large: {1}")]
+    PayloadTooLarge(&'static str, String),
+
+    /// Message and the number of seconds the client should wait (`Retry-After`)
+    #[error("Too many requests: {1}")]
+    TooManyRequests(&'static str, String, u64),
 
     #[error("Internal server error: {0}")]
     Internal(String),
 
+    /// The request could not be served in time
+    #[error("Service unavailable: {1}")]
+    ServiceUnavailable(&'static str, String),
+
     #[error(transparent)]
     Sqlx(#[from] sqlx::Error),
 
-    #[error(transparent)]
-    Validation(#[from] validator::ValidationErrors),
+    /// Messages per field path (`email`, `items[0].title`), rendered as `"errors"`
+    #[error("Validation failed: {0:?}")]
+    Validation(BTreeMap<String, Vec<String>>),
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields = BTreeMap::new();
+        collect_field_errors(&errors, "", &mut fields);
+        AppError::Validation(fields)
+    }
+}
+
+/// Flattens (possibly nested) validator errors into `path -> messages` in the request's
+/// language, falling back to the validator code when a rule has no message.
+fn collect_field_errors(
+    errors: &ValidationErrors,
+    prefix: &str,
+    out: &mut BTreeMap<String, Vec<String>>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = format!("{}{}", prefix, field);
+        match kind {
+            ValidationErrorsKind::Field(errs) => {
+                out.entry(path).or_default().extend(errs.iter().map(|e| {
+                    e.message
+                        .as_ref()
+                        .map(|m| i18n::translate(m))
+                        .unwrap_or_else(|| e.code.to_string())
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => {
+                collect_field_errors(nested, &format!("{}.", path), out);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(nested, &format!("{}[{}].", path, index), out);
+                }
+            }
+        }
+    }
+}
+
+/// Message of `AppError::IdentityTaken`
+pub fn identity_taken_message(username: bool, email: bool) -> &'static str {
+    match (username, email) {
+        (true, true) => "Username and email already exist",
+        (true, false) => "Username already exists",
+        _ => "Email already exists",
+    }
+}
+
+/// SQLSTATE `query_canceled`, raised when `statement_timeout` kills a statement
+const STATEMENT_CANCELLED: &str = "57014";
+
+impl AppError {
+    /// Stable machine-readable code sent as `"code"`, for clients that must not parse messages
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(code, _)
+            | AppError::BadRequest(code, _)
+            | AppError::Unauthorized(code, _)
+            | AppError::Forbidden(code, _)
+            | AppError::Conflict(code, _)
+            | AppError::PreconditionFailed(code, _)
+            | AppError::Locked(code, _)
+            | AppError::PayloadTooLarge(code, _)
+            | AppError::TooManyRequests(code, _, _)
+            | AppError::ServiceUnavailable(code, _) => code,
+            AppError::IdentityTaken { username: true, email: true } => {
+                codes::USERNAME_AND_EMAIL_TAKEN
+            }
+            AppError::IdentityTaken { username: true, .. } => codes::USERNAME_TAKEN,
+            AppError::IdentityTaken { .. } => codes::EMAIL_TAKEN,
+            AppError::TwoFactorRequired => codes::TWO_FACTOR_REQUIRED,
+            AppError::Internal(_) | AppError::Sqlx(_) => codes::INTERNAL_ERROR,
+            AppError::Validation(_) => codes::VALIDATION_FAILED,
+        }
+    }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, message) = match &self {
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
-            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg.clone()),
-            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            AppError::NotFound(_, msg) => (StatusCode::NOT_FOUND, msg.clone()),
+            AppError::BadRequest(_, msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            AppError::Unauthorized(_, msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
+            AppError::Forbidden(_, msg) => (StatusCode::FORBIDDEN, msg.clone()),
+            AppError::Conflict(_, msg) => (StatusCode::CONFLICT, msg.clone()),
+            AppError::IdentityTaken { username, email } => (
+                StatusCode::CONFLICT,
+                identity_taken_message(*username, *email).to_string(),
+            ),
+            AppError::TwoFactorRequired => (StatusCode::UNAUTHORIZED, self.to_string()),
+            AppError::PreconditionFailed(_, msg) => (StatusCode::PRECONDITION_FAILED, msg.clone()),
+            AppError::Locked(_, msg) => (StatusCode::LOCKED, msg.clone()),
+            AppError::PayloadTooLarge(_, msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg.clone()),
+            AppError::TooManyRequests(_, msg, _) => (StatusCode::TOO_MANY_REQUESTS, msg.clone()),
             AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
+            AppError::ServiceUnavailable(_, msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.clone()),
             AppError::Sqlx(e) => {
-                tracing::error!("Database error: {:?}", e);
+                if let sqlx::Error::Database(db) = e
+                    && db.code().as_deref() == Some(STATEMENT_CANCELLED)
+                {
+                    tracing::warn!(
+                        "Database statement cancelled: {}",
+                        db.message()
+                    );
+                } else {
+                    tracing::error!("Database error: {:?}", e);
+                }
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Internal server error".to_string(),
                 )
             }
-            AppError::Validation(e) => (StatusCode::BAD_REQUEST, e.to_string()),
+            AppError::Validation(_) => (StatusCode::BAD_REQUEST, "Validation failed".to_string()),
         };
 
-        let body = json!({
-            "error": message,
+        let mut body = json!({
+            "error": i18n::translate(&message),
+            "code": self.code(),
             "status": status.as_u16(),
         });
+        if let AppError::Validation(fields) = &self {
+            body["errors"] = json!(fields);
+        }
+        if let AppError::IdentityTaken { username, email } = &self {
+            let fields: Vec<&str> = [("username", *username), ("email", *email)]
+                .into_iter()
+                .filter_map(|(field, taken)| taken.then_some(field))
+                .collect();
+            body["fields"] = json!(fields);
+        }
+        if let AppError::TwoFactorRequired = &self {
+            body["two_factor_required"] = json!(true);
+        }
+        if let Some(request_id) = crate::request_id::current() {
+            body["request_id"] = json!(request_id);
+        }
+
+        let mut response = (status, Json(body)).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_LANGUAGE, HeaderValue::from_static(i18n::current()));
+
+        if let AppError::TooManyRequests(_, _, retry_after_secs) = self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+        }
 
-        (status, Json(body)).into_response()
+        response
     }
 }
@@ -0,0 +1,62 @@
+use axum::{
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::errors::{codes, AppError};
+
+/// JSON body with its `ETag` header
+pub type Tagged<T> = ([(HeaderName, String); 1], Json<T>);
+
+/// Strong entity tag of a response body as the caller sees it
+pub fn of<T: Serialize>(body: &T) -> Result<String, AppError> {
+    let bytes = serde_json::to_vec(body)
+        .map_err(|e| AppError::Internal(format!("Response serialization failed: {}", e)))?;
+    Ok(format!("\"{:x}\"", Sha256::digest(&bytes)))
+}
+
+pub fn tagged<T: Serialize>(body: T) -> Result<Tagged<T>, AppError> {
+    Ok(([(header::ETAG, of(&body)?)], Json(body)))
+}
+
+/// Tags listed in a conditional header; `None` if the header is absent or unreadable
+fn listed<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Option<Vec<&'a str>> {
+    let value = headers.get(name)?.to_str().ok()?;
+    Some(value.split(',').map(str::trim).filter(|t| !t.is_empty()).collect())
+}
+
+/// `304 Not Modified` if `If-None-Match` names the current tag (weak comparison),
+/// otherwise the body with its `ETag`.
+pub fn conditional_get<T: Serialize>(headers: &HeaderMap, body: T) -> Result<Response, AppError> {
+    let etag = of(&body)?;
+    let not_modified = listed(headers, &header::IF_NONE_MATCH).is_some_and(|tags| {
+        tags.iter()
+            .any(|t| *t == "*" || t.trim_start_matches("W/") == etag)
+    });
+
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+    Ok(([(header::ETAG, etag)], Json(body)).into_response())
+}
+
+pub fn has_if_match(headers: &HeaderMap) -> bool {
+    headers.contains_key(header::IF_MATCH)
+}
+
+/// Rejects the write with 412 unless `If-Match` names the current tag (strong comparison)
+pub fn ensure_match(headers: &HeaderMap, current: &str) -> Result<(), AppError> {
+    let matches = listed(headers, &header::IF_MATCH)
+        .is_some_and(|tags| tags.iter().any(|t| *t == "*" || *t == current));
+
+    if !matches {
+        return Err(AppError::PreconditionFailed(
+            codes::PRECONDITION_FAILED,
+            "The resource was modified since it was last read".to_string(),
+        ));
+    }
+    Ok(())
+}
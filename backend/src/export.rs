@@ -0,0 +1,74 @@
+use axum::body::Body;
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use sqlx::{PgPool, Postgres, Transaction};
+use tokio::sync::mpsc;
+
+use crate::errors::AppError;
+
+/// Chunks produced by an export task; an `Err` aborts the response mid-stream.
+pub type ExportChunk = Result<Vec<u8>, std::io::Error>;
+
+/// Read transaction for an export query, exempt from `DB_STATEMENT_TIMEOUT_SECS`: the
+/// statement stays open for as long as the client takes to download the rows.
+pub async fn unbounded_transaction(db: &PgPool) -> Result<Transaction<'static, Postgres>, AppError> {
+    let mut tx = db.begin().await?;
+    sqlx::query("SET LOCAL statement_timeout = 0")
+        .execute(&mut *tx)
+        .await?;
+    Ok(tx)
+}
+
+/// Encodes a single CSV line (with proper quoting) into bytes.
+pub fn csv_record<I, T>(fields: I) -> Vec<u8>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    // Writing into a Vec cannot fail
+    writer.write_record(fields).expect("CSV write to memory failed");
+    writer.into_inner().expect("CSV flush to memory failed")
+}
+
+/// Wraps a channel of CSV chunks into a streamed `text/csv` attachment named
+/// `{prefix}_{timestamp}.csv`. Rows are sent by a producer task as they are read,
+/// so large exports are never buffered in memory.
+pub fn csv_response(prefix: &str, rx: mpsc::Receiver<ExportChunk>) -> Response {
+    attachment_response(prefix, "csv", "text/csv; charset=utf-8", rx)
+}
+
+/// Same as [`csv_response`] for a JSON document written piece by piece
+pub fn json_response(prefix: &str, rx: mpsc::Receiver<ExportChunk>) -> Response {
+    attachment_response(prefix, "json", "application/json", rx)
+}
+
+fn attachment_response(
+    prefix: &str,
+    extension: &str,
+    content_type: &str,
+    mut rx: mpsc::Receiver<ExportChunk>,
+) -> Response {
+    let filename = format!(
+        "{}_{}.{}",
+        prefix,
+        chrono::Utc::now().format("%Y%m%d_%H%M%S"),
+        extension
+    );
+
+    // A closed receiver keeps answering `None`, so the stream may be polled past its end
+    // (as the compression layer does)
+    let stream = futures_util::stream::poll_fn(move |cx| rx.poll_recv(cx));
+
+    (
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
@@ -0,0 +1,333 @@
+use axum::{
+    body::Body,
+    extract::{multipart::Field, Multipart, Path, State},
+    http::header::{CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS},
+    response::{IntoResponse, Response},
+    Json,
+};
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::config::AppConfig;
+use crate::dto::AttachmentResponse;
+use crate::errors::{codes, AppError};
+use crate::handlers::watcher_handler::ensure_task_visible;
+use crate::models::TaskAttachment;
+use crate::AppState;
+
+const ATTACHMENT_COLUMNS: &str = "a.id, a.task_id, a.uploaded_by, u.full_name AS uploaded_by_name,
+                a.filename, a.content_type, a.size, a.storage_key, a.created_at";
+
+fn attachment_to_response(a: TaskAttachment) -> AttachmentResponse {
+    AttachmentResponse {
+        download_url: format!("/api/tasks/{}/attachments/{}", a.task_id, a.id),
+        id: a.id,
+        task_id: a.task_id,
+        uploaded_by: a.uploaded_by,
+        uploaded_by_name: a.uploaded_by_name,
+        filename: a.filename,
+        content_type: a.content_type,
+        size: a.size,
+        created_at: a.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+fn storage_error(e: std::io::Error) -> AppError {
+    tracing::error!("Attachment storage error: {:?}", e);
+    AppError::Internal("Attachment storage error".to_string())
+}
+
+/// Keeps only the last path component and drops characters that would break
+/// the `Content-Disposition` header.
+fn sanitize_filename(raw: &str) -> String {
+    let name: String = raw
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(255)
+        .collect();
+
+    if name.trim().is_empty() {
+        "attachment".to_string()
+    } else {
+        name
+    }
+}
+
+/// Streams a multipart field to `path`, failing once it exceeds `max_size` bytes.
+async fn write_field(
+    field: &mut Field<'_>,
+    path: &std::path::Path,
+    max_size: u64,
+) -> Result<u64, AppError> {
+    let mut file = tokio::fs::File::create(path).await.map_err(storage_error)?;
+    let mut size: u64 = 0;
+
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|e| AppError::BadRequest(codes::BAD_REQUEST, e.body_text()))?
+    {
+        size += chunk.len() as u64;
+        if size > max_size {
+            return Err(AppError::PayloadTooLarge(
+                codes::ATTACHMENT_TOO_LARGE,
+                format!(
+                    "Attachment exceeds the maximum size of {} bytes",
+                    max_size
+                ),
+            ));
+        }
+        file.write_all(&chunk).await.map_err(storage_error)?;
+    }
+
+    file.flush().await.map_err(storage_error)?;
+    Ok(size)
+}
+
+/// Best-effort removal of stored attachment files whose rows are already gone
+pub async fn remove_stored_files(config: &AppConfig, storage_keys: &[Uuid]) {
+    for key in storage_keys {
+        let path = config.attachments_dir.join(key.to_string());
+        if let Err(e) = tokio::fs::remove_file(&path).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove attachment file {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Get attachments of a task (oldest first)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/attachments",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task attachments", body = Vec<AttachmentResponse>),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Attachments"
+)]
+pub async fn get_attachments(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<AttachmentResponse>>, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let attachments: Vec<TaskAttachment> = sqlx::query_as(&format!(
+        "SELECT {}
+         FROM task_attachments a
+         LEFT JOIN users u ON u.id = a.uploaded_by
+         WHERE a.task_id = $1
+         ORDER BY a.created_at, a.id",
+        ATTACHMENT_COLUMNS
+    ))
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(
+        attachments.into_iter().map(attachment_to_response).collect(),
+    ))
+}
+
+/// Upload a file to a task (multipart form with a single `file` field)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/attachments",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    request_body(content_type = "multipart/form-data", description = "Form with a `file` field"),
+    responses(
+        (status = 201, description = "Attachment stored", body = AttachmentResponse),
+        (status = 400, description = "Missing or malformed file field"),
+        (status = 404, description = "Task not found"),
+        (status = 413, description = "File exceeds ATTACHMENT_MAX_SIZE_BYTES")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Attachments"
+)]
+pub async fn upload_attachment(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<(axum::http::StatusCode, Json<AttachmentResponse>), AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(codes::BAD_REQUEST, e.body_text()))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = sanitize_filename(field.file_name().unwrap_or_default());
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+
+        tokio::fs::create_dir_all(&state.config.attachments_dir)
+            .await
+            .map_err(storage_error)?;
+
+        let storage_key = Uuid::new_v4();
+        let path = state.config.attachments_dir.join(storage_key.to_string());
+
+        let size = match write_field(&mut field, &path, state.config.attachment_max_size_bytes)
+            .await
+        {
+            Ok(size) => size,
+            Err(e) => {
+                remove_stored_files(&state.config, &[storage_key]).await;
+                return Err(e);
+            }
+        };
+
+        let inserted = sqlx::query_as::<_, TaskAttachment>(&format!(
+            "WITH a AS (
+                 INSERT INTO task_attachments
+                     (task_id, uploaded_by, filename, content_type, size, storage_key)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 RETURNING *
+             )
+             SELECT {} FROM a LEFT JOIN users u ON u.id = a.uploaded_by",
+            ATTACHMENT_COLUMNS
+        ))
+        .bind(task_id)
+        .bind(auth.user_id)
+        .bind(&filename)
+        .bind(&content_type)
+        .bind(size as i64)
+        .bind(storage_key)
+        .fetch_one(&state.db)
+        .await;
+
+        let attachment = match inserted {
+            Ok(attachment) => attachment,
+            Err(e) => {
+                remove_stored_files(&state.config, &[storage_key]).await;
+                return Err(e.into());
+            }
+        };
+
+        return Ok((
+            axum::http::StatusCode::CREATED,
+            Json(attachment_to_response(attachment)),
+        ));
+    }
+
+    Err(AppError::BadRequest(
+        codes::BAD_REQUEST,
+        "Multipart field 'file' is required".to_string(),
+    ))
+}
+
+/// Download an attachment's content
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/attachments/{attachment_id}",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        ("attachment_id" = Uuid, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 200, description = "File content", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 404, description = "Attachment not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Attachments"
+)]
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((task_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let attachment: TaskAttachment = sqlx::query_as(&format!(
+        "SELECT {}
+         FROM task_attachments a
+         LEFT JOIN users u ON u.id = a.uploaded_by
+         WHERE a.id = $1 AND a.task_id = $2",
+        ATTACHMENT_COLUMNS
+    ))
+    .bind(attachment_id)
+    .bind(task_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(codes::ATTACHMENT_NOT_FOUND, "Attachment not found".to_string())
+    })?;
+
+    let path = state
+        .config
+        .attachments_dir
+        .join(attachment.storage_key.to_string());
+    let file = tokio::fs::File::open(&path).await.map_err(storage_error)?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, attachment.content_type),
+            (CONTENT_LENGTH, attachment.size.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", attachment.filename),
+            ),
+            // Uploaded content types are client-supplied; never let browsers sniff
+            (X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        Body::from_stream(tokio_util::io::ReaderStream::new(file)),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserRole;
+    use crate::test_support::{self, create_task, create_user};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn tester_cannot_list_or_download_attachments_of_a_foreign_task(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        let other = create_user(&pool, "other", UserRole::Tester).await;
+        let own = create_task(&pool, manager, Some(tester)).await;
+        let foreign = create_task(&pool, manager, Some(other)).await;
+        let attachment: Uuid = sqlx::query_scalar(
+            "INSERT INTO task_attachments
+                 (task_id, uploaded_by, filename, content_type, size, storage_key)
+             VALUES ($1, $2, 'log.txt', 'text/plain', 3, gen_random_uuid())
+             RETURNING id",
+        )
+        .bind(foreign)
+        .bind(manager)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let auth = test_support::auth(tester, UserRole::Tester);
+
+        assert!(get_attachments(State(state.clone()), auth.clone(), Path(own)).await.is_ok());
+
+        let err = get_attachments(State(state.clone()), auth.clone(), Path(foreign))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+        let err = download_attachment(State(state), auth, Path((foreign, attachment)))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+    }
+}
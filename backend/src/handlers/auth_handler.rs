@@ -1,10 +1,24 @@
-use axum::{extract::State, Json};
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, State},
+    Json,
+};
+use uuid::Uuid;
 use validator::Validate;
 
-use crate::auth::create_token;
-use crate::dto::{LoginRequest, LoginResponse, UserResponse};
-use crate::errors::AppError;
-use crate::models::User;
+use crate::auth::{
+    create_refresh_token, create_token, generate_opaque_token, hash_opaque_token,
+    revoke_all_sessions, AuthUser,
+};
+use crate::dto::{
+    ForgotPasswordRequest, LoginRequest, LoginResponse, LogoutRequest, MessageResponse,
+    RefreshRequest, RefreshResponse, ResetPasswordRequest, UserResponse,
+};
+use crate::errors::{codes, AppError};
+use crate::handlers::{two_factor_handler, user_history_handler};
+use crate::models::{User, UserRole};
+use crate::password;
 use crate::AppState;
 
 /// Login and receive JWT token
@@ -14,50 +28,61 @@ use crate::AppState;
     request_body = LoginRequest,
     responses(
         (status = 200, description = "Login successful", body = LoginResponse),
-        (status = 401, description = "Invalid credentials"),
-        (status = 400, description = "Validation error")
+        (status = 401, description = "Invalid credentials or 2FA code; `two_factor_required: true` when a code must be supplied"),
+        (status = 400, description = "Validation error"),
+        (status = 423, description = "Account temporarily locked"),
+        (status = 429, description = "Too many failed attempts, see Retry-After")
     ),
     tag = "Authentication"
 )]
 pub async fn login(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
     Json(payload): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
     payload.validate()?;
 
-    let user: User = sqlx::query_as(
-        r#"SELECT id, username, email, password_hash, full_name,
-                  role, is_active, created_at, updated_at
-           FROM users WHERE username = $1"#,
-    )
-    .bind(&payload.username)
-    .fetch_optional(&state.db)
-    .await?
-    .ok_or_else(|| AppError::Unauthorized("Invalid username or password".to_string()))?;
+    let username_key = format!("user:{}", payload.username.trim().to_lowercase());
+    let limiter_keys = [format!("ip:{}", peer.ip()), username_key.clone()];
 
-    if !user.is_active {
-        return Err(AppError::Unauthorized("Account is deactivated".to_string()));
+    if let Some(wait) = state.login_limiter.retry_after(&limiter_keys) {
+        return Err(AppError::TooManyRequests(
+            codes::LOGIN_RATE_LIMITED,
+            "Too many failed login attempts, try again later".to_string(),
+            (wait.as_secs_f64().ceil() as u64).max(1),
+        ));
     }
 
-    let parsed_hash = argon2::password_hash::PasswordHash::new(&user.password_hash)
-        .map_err(|_| AppError::Internal("Password hash error".to_string()))?;
-
-    use argon2::PasswordVerifier;
-    argon2::Argon2::default()
-        .verify_password(payload.password.as_bytes(), &parsed_hash)
-        .map_err(|_| AppError::Unauthorized("Invalid username or password".to_string()))?;
+    let (user, token_version) = match verify_credentials(&state, &payload).await {
+        Ok(verified) => verified,
+        Err(e) => {
+            if matches!(e, AppError::Unauthorized(..)) {
+                state.login_limiter.record_failure(&limiter_keys);
+            }
+            return Err(e);
+        }
+    };
+    state.login_limiter.clear(&username_key);
 
     let token = create_token(
         user.id,
         &user.username,
         &user.role,
-        &state.config.jwt_secret,
-        state.config.jwt_expiration_hours,
+        token_version,
+        &state.config,
     )?;
 
+    let refresh_token = create_refresh_token(
+        &state.db,
+        user.id,
+        state.config.refresh_token_expiration_days,
+    )
+    .await?;
+
     Ok(Json(LoginResponse {
         token,
         token_type: "Bearer".to_string(),
+        refresh_token,
         user: UserResponse {
             id: user.id,
             username: user.username,
@@ -67,6 +92,536 @@ pub async fn login(
             is_active: user.is_active,
             created_at: user.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
             updated_at: user.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            last_login_at: user
+                .last_login_at
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
         },
     }))
 }
+
+/// User row plus its lockout and 2FA state, as seen by the login check
+#[derive(sqlx::FromRow)]
+struct LoginCandidate {
+    #[sqlx(flatten)]
+    user: User,
+    is_locked: bool,
+    totp_enabled: bool,
+    totp_secret: Option<String>,
+}
+
+/// The authenticated user and their current `token_version`
+async fn verify_credentials(
+    state: &AppState,
+    payload: &LoginRequest,
+) -> Result<(User, i32), AppError> {
+    let candidate: LoginCandidate = sqlx::query_as(
+        r#"SELECT id, username, email, password_hash, full_name,
+                  role, is_active, created_at, updated_at, last_login_at,
+                  COALESCE(locked_until > NOW(), FALSE) AS is_locked,
+                  totp_enabled, totp_secret
+           FROM users
+           WHERE LOWER(username) = LOWER($1) OR email = $1
+           -- Both branches are index lookups; a username match wins over another account's email
+           ORDER BY LOWER(username) = LOWER($1) DESC
+           LIMIT 1"#,
+    )
+    .bind(&payload.username)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::Unauthorized(
+            codes::INVALID_CREDENTIALS,
+            "Invalid username or password".to_string(),
+        )
+    })?;
+
+    if candidate.is_locked {
+        return Err(AppError::Locked(
+            codes::ACCOUNT_LOCKED,
+            "Account is temporarily locked after too many failed logins, try again later"
+                .to_string(),
+        ));
+    }
+
+    let mut user = candidate.user;
+
+    if !user.is_active {
+        return Err(AppError::Unauthorized(
+            codes::ACCOUNT_DEACTIVATED,
+            "Account is deactivated".to_string(),
+        ));
+    }
+
+    if !password::verify_password(&state.config, &payload.password, &user.password_hash)? {
+        register_failed_login(state, user.id).await?;
+        return Err(AppError::Unauthorized(
+            codes::INVALID_CREDENTIALS,
+            "Invalid username or password".to_string(),
+        ));
+    }
+
+    if candidate.totp_enabled
+        && let Some(stored_secret) = &candidate.totp_secret
+    {
+        // Not a failure: clients ask for the code only after the password was accepted
+        let Some(code) = payload.totp_code.as_deref().filter(|c| !c.trim().is_empty()) else {
+            return Err(AppError::TwoFactorRequired);
+        };
+
+        let valid = two_factor_handler::verify_login_code(
+            state,
+            user.id,
+            &user.username,
+            stored_secret,
+            code,
+        )
+        .await?;
+        if !valid {
+            register_failed_login(state, user.id).await?;
+            return Err(AppError::Unauthorized(
+                codes::INVALID_TWO_FACTOR_CODE,
+                "Invalid two-factor code".to_string(),
+            ));
+        }
+    }
+
+    let (last_login_at, token_version) = sqlx::query_as(
+        "UPDATE users SET failed_login_attempts = 0, locked_until = NULL, last_login_at = NOW()
+         WHERE id = $1
+         RETURNING last_login_at, token_version",
+    )
+    .bind(user.id)
+    .fetch_one(&state.db)
+    .await?;
+    user.last_login_at = last_login_at;
+
+    Ok((user, token_version))
+}
+
+/// Count a bad password; reaching the threshold locks the account and restarts the count.
+async fn register_failed_login(state: &AppState, user_id: Uuid) -> Result<(), AppError> {
+    let threshold = state.config.account_lockout_threshold;
+    if threshold <= 0 {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "UPDATE users SET
+             failed_login_attempts = CASE WHEN failed_login_attempts + 1 >= $2
+                                          THEN 0 ELSE failed_login_attempts + 1 END,
+             locked_until = CASE WHEN failed_login_attempts + 1 >= $2
+                                 THEN NOW() + make_interval(mins => $3) ELSE locked_until END
+         WHERE id = $1",
+    )
+    .bind(user_id)
+    .bind(threshold)
+    .bind(state.config.account_lockout_minutes)
+    .execute(&state.db)
+    .await?;
+
+    Ok(())
+}
+
+/// Exchange a refresh token for a new access token (the refresh token is rotated)
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "Token refreshed", body = RefreshResponse),
+        (status = 401, description = "Refresh token is invalid, expired or revoked"),
+        (status = 400, description = "Validation error")
+    ),
+    tag = "Authentication"
+)]
+pub async fn refresh(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<Json<RefreshResponse>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let stored: (Uuid, Uuid, String, UserRole, bool, i32) = sqlx::query_as(
+        "SELECT rt.id, u.id, u.username, u.role, u.is_active, u.token_version
+         FROM refresh_tokens rt
+         JOIN users u ON u.id = rt.user_id
+         WHERE rt.token_hash = $1 AND rt.revoked_at IS NULL AND rt.expires_at > NOW()
+         FOR UPDATE OF rt",
+    )
+    .bind(hash_opaque_token(&payload.refresh_token))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::Unauthorized(
+            codes::INVALID_REFRESH_TOKEN,
+            "Invalid or expired refresh token".to_string(),
+        )
+    })?;
+
+    let (token_id, user_id, username, role, is_active, token_version) = stored;
+
+    if !is_active {
+        return Err(AppError::Unauthorized(
+            codes::ACCOUNT_DEACTIVATED,
+            "Account is deactivated".to_string(),
+        ));
+    }
+
+    // Rotate: the presented token is single-use
+    sqlx::query("UPDATE refresh_tokens SET revoked_at = NOW() WHERE id = $1")
+        .bind(token_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let refresh_token = create_refresh_token(
+        &mut *tx,
+        user_id,
+        state.config.refresh_token_expiration_days,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let token = create_token(
+        user_id,
+        &username,
+        &role,
+        token_version,
+        &state.config,
+    )?;
+
+    Ok(Json(RefreshResponse {
+        token,
+        token_type: "Bearer".to_string(),
+        refresh_token,
+    }))
+}
+
+/// Logout: revoke the current access token (and optionally its refresh token)
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    request_body(content = Option<LogoutRequest>, description = "Optional refresh token to revoke"),
+    responses(
+        (status = 204, description = "Logged out"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Authentication"
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    payload: Option<Json<LogoutRequest>>,
+) -> Result<axum::http::StatusCode, AppError> {
+    // Tokens issued before jti was introduced cannot be revoked; they simply expire
+    if let Some(jti) = auth.jti {
+        let expires_at = chrono::DateTime::from_timestamp(auth.token_exp as i64, 0)
+            .map(|d| d.naive_utc())
+            .ok_or_else(|| AppError::Internal("Invalid token expiry".to_string()))?;
+
+        sqlx::query(
+            "INSERT INTO revoked_tokens (jti, user_id, expires_at)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (jti) DO NOTHING",
+        )
+        .bind(jti)
+        .bind(auth.user_id)
+        .bind(expires_at)
+        .execute(&state.db)
+        .await?;
+    }
+
+    if let Some(refresh_token) = payload.and_then(|Json(p)| p.refresh_token) {
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = NOW()
+             WHERE token_hash = $1 AND user_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(hash_opaque_token(&refresh_token))
+        .bind(auth.user_id)
+        .execute(&state.db)
+        .await?;
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Request a password reset token (always 200, to avoid revealing which emails exist)
+#[utoipa::path(
+    post,
+    path = "/api/auth/forgot-password",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 200, description = "Reset requested", body = MessageResponse),
+        (status = 400, description = "Validation error")
+    ),
+    tag = "Authentication"
+)]
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    Json(payload): Json<ForgotPasswordRequest>,
+) -> Result<Json<MessageResponse>, AppError> {
+    payload.validate()?;
+
+    let user_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT id FROM users WHERE email = $1 AND is_active = TRUE")
+            .bind(&payload.email)
+            .fetch_optional(&state.db)
+            .await?;
+
+    if let Some(user_id) = user_id {
+        let token = generate_opaque_token();
+        let expires_at = (chrono::Utc::now()
+            + chrono::Duration::minutes(state.config.password_reset_token_minutes))
+        .naive_utc();
+
+        sqlx::query(
+            "INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+             VALUES ($1, $2, $3)",
+        )
+        .bind(user_id)
+        .bind(hash_opaque_token(&token))
+        .bind(expires_at)
+        .execute(&state.db)
+        .await?;
+
+        // No mail delivery yet: the token is only written to the server log
+        tracing::info!(
+            "Password reset token for {}: {} (valid for {} minutes)",
+            payload.email,
+            token,
+            state.config.password_reset_token_minutes
+        );
+    }
+
+    Ok(Json(MessageResponse {
+        message: "If the email is registered, password reset instructions have been sent"
+            .to_string(),
+    }))
+}
+
+/// Set a new password using a reset token
+#[utoipa::path(
+    post,
+    path = "/api/auth/reset-password",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password changed", body = MessageResponse),
+        (status = 400, description = "Validation error or invalid/expired token")
+    ),
+    tag = "Authentication"
+)]
+pub async fn reset_password(
+    State(state): State<AppState>,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> Result<Json<MessageResponse>, AppError> {
+    payload.validate()?;
+    password::validate_password_strength(
+        &state.config.password_policy,
+        "new_password",
+        &payload.new_password,
+    )?;
+
+    let mut tx = state.db.begin().await?;
+
+    let (token_id, user_id): (Uuid, Uuid) = sqlx::query_as(
+        "SELECT id, user_id FROM password_reset_tokens
+         WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+         FOR UPDATE",
+    )
+    .bind(hash_opaque_token(&payload.token))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::BadRequest(
+            codes::INVALID_RESET_TOKEN,
+            "Invalid or expired reset token".to_string(),
+        )
+    })?;
+
+    password::ensure_not_breached(&state, &payload.new_password).await?;
+    let password_hash = password::hash_password(&state.config, &payload.new_password)?;
+
+    sqlx::query(
+        "UPDATE users SET password_hash = $1, failed_login_attempts = 0, locked_until = NULL,
+                          updated_at = NOW()
+         WHERE id = $2",
+    )
+        .bind(&password_hash)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("UPDATE password_reset_tokens SET used_at = NOW() WHERE id = $1")
+        .bind(token_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Existing sessions must not outlive a password reset
+    revoke_all_sessions(&mut tx, user_id).await?;
+    user_history_handler::record_user_event(&mut tx, user_id, user_id, "password", None, None)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(MessageResponse {
+        message: "Password has been reset".to_string(),
+    }))
+}
+
+/// Public keys for verifying access tokens (RS256 mode only)
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    responses(
+        (status = 200, description = "JSON Web Key Set", body = serde_json::Value),
+        (status = 404, description = "Tokens are signed with HS256; there is no public key")
+    ),
+    tag = "Authentication"
+)]
+pub async fn jwks(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    state.config.jwt_keys.jwks().map(Json).ok_or_else(|| {
+        AppError::NotFound(
+            codes::NOT_FOUND,
+            "JWKS is only published when RS256 signing is configured".to_string(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, create_user, create_user_with_email, PASSWORD};
+    use sqlx::PgPool;
+
+    async fn attempt(state: &AppState, username: &str, password: &str) -> Result<User, AppError> {
+        let payload = LoginRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+            totp_code: None,
+        };
+        verify_credentials(state, &payload).await.map(|(user, _)| user)
+    }
+
+    async fn failed_attempts(db: &PgPool, user_id: Uuid) -> i32 {
+        sqlx::query_scalar("SELECT failed_login_attempts FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(db)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn five_bad_passwords_lock_the_account(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let user = create_user(&pool, "dev", UserRole::Developer).await;
+
+        for n in 1..5 {
+            let err = attempt(&state, "dev", "wrong").await.err().unwrap();
+            assert!(matches!(err, AppError::Unauthorized(codes::INVALID_CREDENTIALS, _)));
+            assert_eq!(failed_attempts(&pool, user).await, n);
+        }
+        let err = attempt(&state, "dev", "wrong").await.err().unwrap();
+        assert!(matches!(err, AppError::Unauthorized(codes::INVALID_CREDENTIALS, _)));
+
+        // Locked now: even the right password is refused until the lockout expires
+        let err = attempt(&state, "dev", PASSWORD).await.err().unwrap();
+        assert!(matches!(err, AppError::Locked(codes::ACCOUNT_LOCKED, _)));
+
+        sqlx::query("UPDATE users SET locked_until = NOW() - INTERVAL '1 minute' WHERE id = $1")
+            .bind(user)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(attempt(&state, "dev", PASSWORD).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn successful_login_resets_failed_attempts(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let user = create_user(&pool, "dev", UserRole::Developer).await;
+
+        for _ in 0..3 {
+            assert!(attempt(&state, "dev", "wrong").await.is_err());
+        }
+        assert_eq!(failed_attempts(&pool, user).await, 3);
+
+        assert!(attempt(&state, "dev", PASSWORD).await.is_ok());
+        assert_eq!(failed_attempts(&pool, user).await, 0);
+
+        // The count starts over: four more misses do not lock the account
+        for _ in 0..4 {
+            assert!(attempt(&state, "dev", "wrong").await.is_err());
+        }
+        assert!(attempt(&state, "dev", PASSWORD).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn logs_in_by_username_or_email(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let user = create_user_with_email(&pool, "Dev", "dev@example.com", UserRole::Developer).await;
+
+        assert_eq!(attempt(&state, "Dev", PASSWORD).await.unwrap().id, user);
+        assert_eq!(attempt(&state, "dev", PASSWORD).await.unwrap().id, user);
+        assert_eq!(attempt(&state, "dev@example.com", PASSWORD).await.unwrap().id, user);
+
+        // Same error for a wrong password and an unknown login, whichever path matched
+        for login in ["dev@example.com", "nobody@example.com", "nobody"] {
+            let err = attempt(&state, login, "wrong").await.err().unwrap();
+            assert!(matches!(err, AppError::Unauthorized(codes::INVALID_CREDENTIALS, _)));
+        }
+    }
+
+    #[sqlx::test]
+    async fn username_match_wins_over_another_accounts_email(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        create_user_with_email(&pool, "alice", "alice@example.com", UserRole::Developer).await;
+        let squatter =
+            create_user_with_email(&pool, "alice@example.com", "bob@example.com", UserRole::Tester)
+                .await;
+
+        let user = attempt(&state, "alice@example.com", PASSWORD).await.unwrap();
+        assert_eq!(user.id, squatter);
+        assert_eq!(user.username, "alice@example.com");
+    }
+
+    #[sqlx::test]
+    async fn refresh_rejects_a_deactivated_user(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let user = create_user(&pool, "dev", UserRole::Developer).await;
+        let refresh_token = create_refresh_token(&pool, user, 30).await.unwrap();
+
+        sqlx::query("UPDATE users SET is_active = FALSE WHERE id = $1")
+            .bind(user)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let payload = RefreshRequest { refresh_token };
+        let err = refresh(State(state), Json(payload)).await.err().unwrap();
+        assert!(matches!(err, AppError::Unauthorized(codes::ACCOUNT_DEACTIVATED, _)));
+    }
+
+    #[sqlx::test]
+    async fn login_ignores_username_case(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let user = create_user(&pool, "Admin", UserRole::Admin).await;
+
+        for login in ["Admin", "admin", "ADMIN", "aDmIn"] {
+            let found = attempt(&state, login, PASSWORD).await.unwrap();
+            assert_eq!(found.id, user);
+            // Stored as entered
+            assert_eq!(found.username, "Admin");
+        }
+
+        // Two accounts cannot differ only by case
+        let duplicate = sqlx::query(
+            "INSERT INTO users (username, email, password_hash, full_name)
+             VALUES ('ADMIN', 'other@example.com', 'x', 'Other')",
+        )
+        .execute(&pool)
+        .await;
+        assert!(duplicate.is_err());
+    }
+}
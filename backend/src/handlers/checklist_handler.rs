@@ -0,0 +1,305 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::PgConnection;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::AuthUser;
+use crate::dto::{
+    AddChecklistItemRequest, ChecklistItemResponse, ReorderChecklistRequest,
+    UpdateChecklistItemRequest,
+};
+use crate::errors::{codes, AppError};
+use crate::handlers::watcher_handler::ensure_task_visible;
+use crate::models::ChecklistItem;
+use crate::AppState;
+
+pub fn checklist_item_to_response(item: ChecklistItem) -> ChecklistItemResponse {
+    ChecklistItemResponse {
+        id: item.id,
+        task_id: item.task_id,
+        text: item.text,
+        done: item.done,
+        position: item.position,
+    }
+}
+
+async fn task_checklist(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+) -> Result<Vec<ChecklistItem>, AppError> {
+    let items: Vec<ChecklistItem> = sqlx::query_as(
+        "SELECT id, task_id, text, done, position FROM task_checklist_items
+         WHERE task_id = $1
+         ORDER BY position, created_at",
+    )
+    .bind(task_id)
+    .fetch_all(conn)
+    .await?;
+    Ok(items)
+}
+
+/// Copies the checklist of `from` to `to`, every item unchecked
+pub async fn copy_checklist(
+    conn: &mut PgConnection,
+    from: Uuid,
+    to: Uuid,
+) -> Result<Vec<ChecklistItem>, AppError> {
+    let mut items: Vec<ChecklistItem> = sqlx::query_as(
+        "INSERT INTO task_checklist_items (task_id, text, position)
+         SELECT $2, text, position FROM task_checklist_items WHERE task_id = $1
+         RETURNING id, task_id, text, done, position",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(conn)
+    .await?;
+
+    items.sort_by_key(|item| item.position);
+    Ok(items)
+}
+
+/// Number of checklist items of a task that are not checked yet
+pub async fn unchecked_items(conn: &mut PgConnection, task_id: Uuid) -> Result<i64, AppError> {
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM task_checklist_items WHERE task_id = $1 AND NOT done",
+    )
+    .bind(task_id)
+    .fetch_one(conn)
+    .await?;
+    Ok(count)
+}
+
+/// Get the checklist of a task, in display order
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/checklist",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Checklist items", body = Vec<ChecklistItemResponse>),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn get_checklist(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<ChecklistItemResponse>>, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let items = task_checklist(&mut *state.db.acquire().await?, task_id).await?;
+    Ok(Json(items.into_iter().map(checklist_item_to_response).collect()))
+}
+
+/// Add an unchecked item to the end of a task's checklist
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/checklist",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    request_body = AddChecklistItemRequest,
+    responses(
+        (status = 201, description = "Item added", body = ChecklistItemResponse),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Admins cannot edit tasks"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn add_checklist_item(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<AddChecklistItemRequest>,
+) -> Result<(StatusCode, Json<ChecklistItemResponse>), AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    payload.validate()?;
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let item: ChecklistItem = sqlx::query_as(
+        "INSERT INTO task_checklist_items (task_id, text, position)
+         SELECT $1, $2, COALESCE(MAX(position), 0) + 1
+         FROM task_checklist_items WHERE task_id = $1
+         RETURNING id, task_id, text, done, position",
+    )
+    .bind(task_id)
+    .bind(&payload.text)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(checklist_item_to_response(item))))
+}
+
+/// Check or uncheck a checklist item, or change its text
+#[utoipa::path(
+    patch,
+    path = "/api/tasks/{id}/checklist/{item_id}",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        ("item_id" = Uuid, Path, description = "Checklist item ID")
+    ),
+    request_body = UpdateChecklistItemRequest,
+    responses(
+        (status = 200, description = "Updated item", body = ChecklistItemResponse),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Admins cannot edit tasks"),
+        (status = 404, description = "Task or checklist item not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn update_checklist_item(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((task_id, item_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateChecklistItemRequest>,
+) -> Result<Json<ChecklistItemResponse>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    payload.validate()?;
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let item: ChecklistItem = sqlx::query_as(
+        "UPDATE task_checklist_items
+         SET text = COALESCE($3, text), done = COALESCE($4, done)
+         WHERE id = $1 AND task_id = $2
+         RETURNING id, task_id, text, done, position",
+    )
+    .bind(item_id)
+    .bind(task_id)
+    .bind(&payload.text)
+    .bind(payload.done)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(codes::CHECKLIST_ITEM_NOT_FOUND, "Checklist item not found".to_string())
+    })?;
+
+    Ok(Json(checklist_item_to_response(item)))
+}
+
+/// Put a task's checklist in a new order; `ids` must list each of its items once
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{id}/checklist/order",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    request_body = ReorderChecklistRequest,
+    responses(
+        (status = 200, description = "Checklist in the new order", body = Vec<ChecklistItemResponse>),
+        (status = 400, description = "ids do not match the task's checklist"),
+        (status = 403, description = "Admins cannot edit tasks"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn reorder_checklist(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<ReorderChecklistRequest>,
+) -> Result<Json<Vec<ChecklistItemResponse>>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    // Locked, so concurrent reorders apply one after the other
+    let mut current: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM task_checklist_items WHERE task_id = $1 FOR UPDATE",
+    )
+    .bind(task_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    current.sort();
+    let mut requested = payload.ids.clone();
+    requested.sort();
+    if requested != current {
+        return Err(AppError::BadRequest(
+            codes::INVALID_CHECKLIST_ORDER,
+            "The order must list every checklist item of the task exactly once".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        "UPDATE task_checklist_items c SET position = o.position::int
+         FROM unnest($1::uuid[]) WITH ORDINALITY AS o(id, position)
+         WHERE c.id = o.id",
+    )
+    .bind(&payload.ids)
+    .execute(&mut *tx)
+    .await?;
+
+    let items = task_checklist(&mut tx, task_id).await?;
+    tx.commit().await?;
+
+    Ok(Json(items.into_iter().map(checklist_item_to_response).collect()))
+}
+
+/// Remove an item from a task's checklist
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{id}/checklist/{item_id}",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        ("item_id" = Uuid, Path, description = "Checklist item ID")
+    ),
+    responses(
+        (status = 204, description = "Item removed"),
+        (status = 403, description = "Admins cannot edit tasks"),
+        (status = 404, description = "Task or checklist item not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn delete_checklist_item(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((task_id, item_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let result = sqlx::query("DELETE FROM task_checklist_items WHERE id = $1 AND task_id = $2")
+        .bind(item_id)
+        .bind(task_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            codes::CHECKLIST_ITEM_NOT_FOUND,
+            "Checklist item not found".to_string(),
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
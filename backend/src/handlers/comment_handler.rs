@@ -0,0 +1,212 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::AuthUser;
+use crate::dto::{CommentResponse, CreateCommentRequest};
+use crate::errors::{codes, AppError};
+use crate::handlers::task_handler;
+use crate::handlers::watcher_handler::ensure_task_visible;
+use crate::models::{Task, TaskComment};
+use crate::notifications;
+use crate::AppState;
+
+pub fn comment_to_response(c: TaskComment) -> CommentResponse {
+    CommentResponse {
+        id: c.id,
+        task_id: c.task_id,
+        author_id: c.author_id,
+        author_name: c.author_name,
+        body: c.body,
+        created_at: c.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+/// Get comments of a task (oldest first)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/comments",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task comments", body = Vec<CommentResponse>),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Comments"
+)]
+pub async fn get_comments(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<CommentResponse>>, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let comments: Vec<TaskComment> = sqlx::query_as(
+        "SELECT c.id, c.task_id, c.author_id, u.full_name AS author_name, c.body, c.created_at
+         FROM task_comments c
+         LEFT JOIN users u ON u.id = c.author_id
+         WHERE c.task_id = $1
+         ORDER BY c.created_at, c.id",
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(comments.into_iter().map(comment_to_response).collect()))
+}
+
+/// Add a comment to a task
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/comments",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    request_body = CreateCommentRequest,
+    responses(
+        (status = 201, description = "Comment added", body = CommentResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Comments"
+)]
+pub async fn create_comment(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<(axum::http::StatusCode, Json<CommentResponse>), AppError> {
+    payload.validate()?;
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let comment: TaskComment = sqlx::query_as(
+        "WITH c AS (
+             INSERT INTO task_comments (task_id, author_id, body)
+             VALUES ($1, $2, $3)
+             RETURNING *
+         )
+         SELECT c.id, c.task_id, c.author_id, u.full_name AS author_name, c.body, c.created_at
+         FROM c LEFT JOIN users u ON u.id = c.author_id",
+    )
+    .bind(task_id)
+    .bind(auth.user_id)
+    .bind(&payload.body)
+    .fetch_one(&state.db)
+    .await?;
+
+    let task: Task = sqlx::query_as(&format!(
+        "SELECT {} FROM tasks t WHERE t.id = $1",
+        task_handler::TASK_COLUMNS
+    ))
+    .bind(task_id)
+    .fetch_one(&state.db)
+    .await?;
+    notifications::task_commented(&state, auth.user_id, &task).await;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(comment_to_response(comment)),
+    ))
+}
+
+/// Delete a comment (its author or a manager)
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{id}/comments/{comment_id}",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        ("comment_id" = Uuid, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 204, description = "Comment deleted"),
+        (status = 404, description = "Comment not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Comments"
+)]
+pub async fn delete_comment(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<axum::http::StatusCode, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let author_id: Uuid =
+        sqlx::query_scalar("SELECT author_id FROM task_comments WHERE id = $1 AND task_id = $2")
+            .bind(comment_id)
+            .bind(task_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(codes::COMMENT_NOT_FOUND, "Comment not found".to_string())
+            })?;
+
+    if author_id != auth.user_id && !auth.is_manager() {
+        return Err(AppError::Forbidden(
+            codes::NOT_COMMENT_AUTHOR,
+            "Only the comment author or a manager can delete comments".to_string(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM task_comments WHERE id = $1")
+        .bind(comment_id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserRole;
+    use crate::test_support::{self, create_task, create_user};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn tester_only_reaches_comments_of_visible_tasks(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        let other = create_user(&pool, "other", UserRole::Tester).await;
+        let own = create_task(&pool, manager, Some(tester)).await;
+        let foreign = create_task(&pool, manager, Some(other)).await;
+        let auth = test_support::auth(tester, UserRole::Tester);
+        let comment = || Json(CreateCommentRequest { body: "Looks good".to_string() });
+
+        let created = create_comment(State(state.clone()), auth.clone(), Path(own), comment()).await;
+        assert!(created.is_ok());
+        let Json(comments) =
+            get_comments(State(state.clone()), auth.clone(), Path(own)).await.unwrap();
+        assert_eq!(comments.len(), 1);
+
+        let err = get_comments(State(state.clone()), auth.clone(), Path(foreign))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+        let err = create_comment(State(state.clone()), auth.clone(), Path(foreign), comment())
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM task_comments WHERE task_id = $1")
+            .bind(foreign)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+
+        // A deleted task hides its comments from everyone
+        sqlx::query("UPDATE tasks SET deleted_at = NOW() WHERE id = $1")
+            .bind(own)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let err = get_comments(State(state), auth, Path(own)).await.err().unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+    }
+}
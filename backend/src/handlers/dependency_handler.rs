@@ -0,0 +1,212 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::dto::AddDependencyRequest;
+use crate::errors::{codes, AppError};
+use crate::handlers::watcher_handler::ensure_task_visible;
+use crate::AppState;
+
+async fn blocked_by(db: &sqlx::PgPool, task_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+    let ids: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT depends_on_id FROM task_dependencies WHERE task_id = $1 ORDER BY created_at",
+    )
+    .bind(task_id)
+    .fetch_all(db)
+    .await?;
+    Ok(ids)
+}
+
+/// Task numbers of live blockers of `task_id` that are not done or closed yet
+pub async fn open_blockers(
+    conn: &mut sqlx::PgConnection,
+    task_id: Uuid,
+) -> Result<Vec<i32>, AppError> {
+    let numbers: Vec<i32> = sqlx::query_scalar(
+        "SELECT b.task_number
+         FROM task_dependencies d
+         JOIN tasks b ON b.id = d.depends_on_id
+         WHERE d.task_id = $1
+           AND b.deleted_at IS NULL
+           AND b.status NOT IN ('done', 'closed')
+         ORDER BY b.task_number",
+    )
+    .bind(task_id)
+    .fetch_all(conn)
+    .await?;
+    Ok(numbers)
+}
+
+/// Declare that a task is blocked by another one (re-adding is a no-op)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/dependencies",
+    params(("id" = Uuid, Path, description = "Blocked task ID")),
+    request_body = AddDependencyRequest,
+    responses(
+        (status = 200, description = "IDs of all tasks blocking this one", body = Vec<Uuid>),
+        (status = 400, description = "Self-dependency or the dependency would form a cycle"),
+        (status = 403, description = "Admins cannot edit tasks"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn add_dependency(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<AddDependencyRequest>,
+) -> Result<Json<Vec<Uuid>>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    let depends_on_id = payload.depends_on_id;
+    if depends_on_id == task_id {
+        return Err(AppError::BadRequest(
+            codes::SELF_REFERENCE,
+            "A task cannot depend on itself".to_string(),
+        ));
+    }
+    ensure_task_visible(&state, &auth, task_id).await?;
+    ensure_task_visible(&state, &auth, depends_on_id)
+        .await
+        .map_err(|_| {
+            AppError::NotFound(codes::TASK_NOT_FOUND, "Blocking task not found".to_string())
+        })?;
+
+    // The new edge closes a cycle if the blocker already (transitively) depends on this task
+    let cycle: bool = sqlx::query_scalar(
+        "WITH RECURSIVE chain(id) AS (
+             SELECT $1::uuid
+             UNION
+             SELECT d.depends_on_id FROM task_dependencies d JOIN chain c ON d.task_id = c.id
+         )
+         SELECT EXISTS(SELECT 1 FROM chain WHERE id = $2)",
+    )
+    .bind(depends_on_id)
+    .bind(task_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if cycle {
+        return Err(AppError::BadRequest(
+            codes::CYCLE,
+            "The dependency would form a cycle".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        "INSERT INTO task_dependencies (task_id, depends_on_id) VALUES ($1, $2)
+         ON CONFLICT (task_id, depends_on_id) DO NOTHING",
+    )
+    .bind(task_id)
+    .bind(depends_on_id)
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(blocked_by(&state.db, task_id).await?))
+}
+
+/// Remove a blocking relationship
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{id}/dependencies/{depends_on_id}",
+    params(
+        ("id" = Uuid, Path, description = "Blocked task ID"),
+        ("depends_on_id" = Uuid, Path, description = "Blocking task ID")
+    ),
+    responses(
+        (status = 204, description = "Dependency removed"),
+        (status = 403, description = "Admins cannot edit tasks"),
+        (status = 404, description = "Task, blocking task or dependency not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn remove_dependency(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((task_id, depends_on_id)): Path<(Uuid, Uuid)>,
+) -> Result<axum::http::StatusCode, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    ensure_task_visible(&state, &auth, task_id).await?;
+    ensure_task_visible(&state, &auth, depends_on_id)
+        .await
+        .map_err(|_| {
+            AppError::NotFound(codes::TASK_NOT_FOUND, "Blocking task not found".to_string())
+        })?;
+
+    let result =
+        sqlx::query("DELETE FROM task_dependencies WHERE task_id = $1 AND depends_on_id = $2")
+            .bind(task_id)
+            .bind(depends_on_id)
+            .execute(&state.db)
+            .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            codes::DEPENDENCY_NOT_FOUND,
+            "Dependency not found".to_string(),
+        ));
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserRole;
+    use crate::test_support::{self, create_task, create_user};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn both_ends_must_be_visible(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        let other = create_user(&pool, "other", UserRole::Tester).await;
+        let own = create_task(&pool, manager, Some(tester)).await;
+        let also_own = create_task(&pool, manager, Some(tester)).await;
+        let foreign = create_task(&pool, manager, Some(other)).await;
+        let auth = test_support::auth(tester, UserRole::Tester);
+        let add = |task_id: Uuid, depends_on_id: Uuid| {
+            let payload = AddDependencyRequest { depends_on_id };
+            add_dependency(State(state.clone()), auth.clone(), Path(task_id), Json(payload))
+        };
+
+        for (task_id, depends_on_id) in [(own, foreign), (foreign, own)] {
+            let err = add(task_id, depends_on_id).await.err().unwrap();
+            assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+        }
+        assert!(add(own, also_own).await.is_ok());
+
+        // An edge made by someone who sees both tasks stays out of the tester's reach
+        sqlx::query("INSERT INTO task_dependencies (task_id, depends_on_id) VALUES ($1, $2)")
+            .bind(own)
+            .bind(foreign)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let path = Path((own, foreign));
+        let err = remove_dependency(State(state.clone()), auth.clone(), path).await.err().unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+
+        let path = Path((own, also_own));
+        assert!(remove_dependency(State(state), auth, path).await.is_ok());
+    }
+}
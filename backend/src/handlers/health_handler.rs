@@ -0,0 +1,47 @@
+use axum::{extract::State, http::StatusCode, Json};
+
+use crate::dto::HealthResponse;
+use crate::AppState;
+
+/// Liveness probe: the process is up and serving requests
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses((status = 200, description = "Service is alive", body = HealthResponse)),
+    tag = "Health"
+)]
+pub async fn health() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+    })
+}
+
+/// Readiness probe: the database is reachable
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = HealthResponse),
+        (status = 503, description = "Database unreachable", body = HealthResponse)
+    ),
+    tag = "Health"
+)]
+pub async fn ready(State(state): State<AppState>) -> (StatusCode, Json<HealthResponse>) {
+    match sqlx::query("SELECT 1").execute(&state.db).await {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(HealthResponse {
+                status: "ok".to_string(),
+            }),
+        ),
+        Err(e) => {
+            tracing::warn!("Readiness check failed: {:?}", e);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(HealthResponse {
+                    status: "unavailable".to_string(),
+                }),
+            )
+        }
+    }
+}
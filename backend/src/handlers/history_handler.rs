@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::dto::TaskEventResponse;
+use crate::errors::AppError;
+use crate::handlers::watcher_handler::ensure_task_visible;
+use crate::models::TaskEvent;
+use crate::user_names::fetch_user_names;
+use crate::AppState;
+
+/// Appends a history entry; call inside the same transaction as the change itself.
+pub async fn record_task_event(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    actor_id: Uuid,
+    field: &str,
+    old_value: Option<String>,
+    new_value: Option<String>,
+) -> Result<(), AppError> {
+    record_task_event_with_reason(conn, task_id, actor_id, field, old_value, new_value, None).await
+}
+
+/// Same as [`record_task_event`], keeping the reason the actor gave for the change.
+pub async fn record_task_event_with_reason(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    actor_id: Uuid,
+    field: &str,
+    old_value: Option<String>,
+    new_value: Option<String>,
+    reason: Option<String>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO task_events (task_id, actor_id, field, old_value, new_value, reason)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(task_id)
+    .bind(actor_id)
+    .bind(field)
+    .bind(old_value)
+    .bind(new_value)
+    .bind(reason)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// History fields whose values are user ids
+const USER_ID_FIELDS: &[&str] = &["tester_id"];
+
+/// The user id held by one of the event's values, if the field stores user ids
+fn user_id_value(e: &TaskEvent, value: &Option<String>) -> Option<Uuid> {
+    if !USER_ID_FIELDS.contains(&e.field.as_str()) {
+        return None;
+    }
+    value.as_deref()?.parse().ok()
+}
+
+pub fn event_to_response(e: TaskEvent, names: &HashMap<Uuid, String>) -> TaskEventResponse {
+    let name_of = |value| user_id_value(&e, value).and_then(|id| names.get(&id).cloned());
+    let old_value_name = name_of(&e.old_value);
+    let new_value_name = name_of(&e.new_value);
+
+    TaskEventResponse {
+        id: e.id,
+        task_id: e.task_id,
+        actor_id: e.actor_id,
+        actor_name: e.actor_name,
+        field: e.field,
+        old_value: e.old_value,
+        new_value: e.new_value,
+        old_value_name,
+        new_value_name,
+        reason: e.reason,
+        created_at: e.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+/// Get the change history of a task (oldest first)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/history",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task history", body = Vec<TaskEventResponse>),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn get_task_history(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<TaskEventResponse>>, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let events: Vec<TaskEvent> = sqlx::query_as(
+        "SELECT e.id, e.task_id, e.actor_id, u.full_name AS actor_name,
+                e.field, e.old_value, e.new_value, e.reason, e.created_at
+         FROM task_events e
+         LEFT JOIN users u ON u.id = e.actor_id
+         WHERE e.task_id = $1
+         ORDER BY e.created_at, e.id",
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let user_ids: Vec<Uuid> = events
+        .iter()
+        .flat_map(|e| [user_id_value(e, &e.old_value), user_id_value(e, &e.new_value)])
+        .flatten()
+        .collect();
+    let names = fetch_user_names(&state.db, &user_ids).await?;
+
+    Ok(Json(
+        events
+            .into_iter()
+            .map(|e| event_to_response(e, &names))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::codes;
+    use crate::models::UserRole;
+    use crate::test_support::{self, create_task, create_user};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn history_follows_task_visibility(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        let other = create_user(&pool, "other", UserRole::Tester).await;
+        let own = create_task(&pool, manager, Some(tester)).await;
+        let foreign = create_task(&pool, manager, Some(other)).await;
+        let auth = test_support::auth(tester, UserRole::Tester);
+
+        assert!(get_task_history(State(state.clone()), auth.clone(), Path(own)).await.is_ok());
+        let err = get_task_history(State(state), auth, Path(foreign)).await.err().unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+    }
+}
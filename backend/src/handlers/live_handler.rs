@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::HeaderMap,
+    response::Response,
+};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::auth::{self, AuthUser};
+use crate::errors::AppError;
+use crate::handlers::task_handler;
+use crate::live::LiveEvent;
+use crate::AppState;
+
+/// How often the connection is pinged and the token re-checked
+const HEARTBEAT: Duration = Duration::from_secs(30);
+
+/// Close code for a token that expired or was revoked while connected
+const POLICY_VIOLATION: u16 = 1008;
+
+#[derive(Debug, Deserialize)]
+pub struct LiveParams {
+    /// Access token, for clients that cannot set the Authorization header (browsers)
+    pub token: Option<String>,
+    /// Id of the last event received before a reconnect; newer buffered events are replayed
+    pub last_event_id: Option<u64>,
+}
+
+/// The event as `auth` may see it: `None` outside their task scope, restricted fields hidden
+fn visible_to(state: &AppState, auth: &AuthUser, event: &LiveEvent) -> Option<String> {
+    if let Some(me) = task_handler::visibility_scope(auth)
+        && event.task.tester_id != Some(me)
+        && event.task.assigned_by != me
+    {
+        return None;
+    }
+
+    let task = task_handler::redact_task_fields(event.task.clone(), auth, &state.config);
+    let message = serde_json::json!({
+        "id": event.id,
+        "event": event.event,
+        "occurred_at": event.occurred_at,
+        "task": task,
+    });
+    Some(message.to_string())
+}
+
+fn resync() -> Message {
+    Message::Text(r#"{"event":"resync"}"#.into())
+}
+
+/// Live task events over a WebSocket: `task.created`, `task.updated` and `task.closed`
+/// for the tasks the caller can see, as JSON text messages with increasing `id`s.
+/// A `{"event":"resync"}` message means events were missed and the client should reload.
+#[utoipa::path(
+    get,
+    path = "/ws/tasks",
+    params(
+        ("token" = Option<String>, Query, description = "Access token, when the Authorization header cannot be set"),
+        ("last_event_id" = Option<u64>, Query, description = "Last event id seen before reconnecting; newer events are replayed")
+    ),
+    responses(
+        (status = 101, description = "Switched to the WebSocket protocol"),
+        (status = 401, description = "Missing, invalid or revoked token"),
+        (status = 403, description = "Sync accounts cannot open the stream")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn tasks_socket(
+    State(state): State<AppState>,
+    Query(params): Query<LiveParams>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    let token = auth::token_from(&headers, params.token)?;
+    let auth = auth::authenticate(&state, &token, "/ws/tasks").await?;
+
+    Ok(ws.on_upgrade(move |socket| serve(socket, state, auth, token, params.last_event_id)))
+}
+
+async fn serve(
+    mut socket: WebSocket,
+    state: AppState,
+    auth: AuthUser,
+    token: String,
+    last_event_id: Option<u64>,
+) {
+    let subscription = state.live.subscribe(last_event_id);
+    let mut receiver = subscription.receiver;
+
+    if !subscription.complete && socket.send(resync()).await.is_err() {
+        return;
+    }
+    for event in &subscription.replay {
+        if let Some(message) = visible_to(&state, &auth, event)
+            && socket.send(Message::Text(message.into())).await.is_err()
+        {
+            return;
+        }
+    }
+
+    let mut heartbeat = tokio::time::interval(HEARTBEAT);
+    heartbeat.tick().await;
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => {
+                let message = match event {
+                    Ok(event) => match visible_to(&state, &auth, &event) {
+                        Some(message) => Message::Text(message.into()),
+                        None => continue,
+                    },
+                    // This client fell behind the channel and lost events
+                    Err(RecvError::Lagged(_)) => resync(),
+                    Err(RecvError::Closed) => break,
+                };
+                if socket.send(message).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                None | Some(Err(_)) | Some(Ok(Message::Close(_))) => break,
+                // Nothing is expected from the client; its pings are answered by the library
+                Some(Ok(_)) => {}
+            },
+            _ = heartbeat.tick() => {
+                // Expiry, logout and deactivation end the stream like they end requests
+                if let Err(e) = auth::authenticate(&state, &token, "/ws/tasks").await {
+                    let reason = match e {
+                        AppError::Unauthorized(_, reason)
+                        | AppError::Forbidden(_, reason) => reason,
+                        _ => "Authentication check failed".to_string(),
+                    };
+                    let _ = socket
+                        .send(Message::Close(Some(CloseFrame {
+                            code: POLICY_VIOLATION,
+                            reason: reason.into(),
+                        })))
+                        .await;
+                    break;
+                }
+                if socket.send(Message::Ping(Vec::new().into())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,70 @@
+pub mod attachment_handler;
 pub mod auth_handler;
+pub mod checklist_handler;
+pub mod comment_handler;
+pub mod dependency_handler;
+pub mod health_handler;
+pub mod history_handler;
+pub mod live_handler;
+pub mod notification_handler;
+pub mod tag_handler;
 pub mod task_handler;
+pub mod sync_handler;
+pub mod team_handler;
+pub mod template_handler;
+pub mod time_entry_handler;
+pub mod two_factor_handler;
+pub mod user_export_handler;
 pub mod user_handler;
+pub mod user_history_handler;
+pub mod view_handler;
+pub mod watcher_handler;
+pub mod webhook_handler;
+
+use axum::{
+    http::{header, HeaderName, StatusCode},
+    Json,
+};
+
+use crate::config::AppConfig;
+use crate::errors::{codes, AppError};
+
+pub const DEFAULT_PER_PAGE: i64 = 20;
+pub const MAX_PER_PAGE: i64 = 100;
+
+/// 201 response with a `Location` header pointing at the new resource
+pub type Created<T> = (StatusCode, [(HeaderName, String); 1], Json<T>);
+
+pub fn created<T>(location: String, body: T) -> Created<T> {
+    (StatusCode::CREATED, [(header::LOCATION, location)], Json(body))
+}
+
+/// `(page, per_page)` of a paginated listing. Out-of-range values are clamped into
+/// `1..` and `1..=MAX_PER_PAGE`, or rejected with 400 under `STRICT_PAGINATION`.
+pub fn page_bounds(
+    config: &AppConfig,
+    page: Option<i64>,
+    per_page: Option<i64>,
+) -> Result<(i64, i64), AppError> {
+    let page = page.unwrap_or(1);
+    let per_page = per_page.unwrap_or(DEFAULT_PER_PAGE);
+
+    if config.strict_pagination {
+        if page < 1 {
+            return Err(AppError::BadRequest(
+                codes::INVALID_PAGINATION,
+                "'page' must be 1 or greater".to_string(),
+            ));
+        }
+        if !(1..=MAX_PER_PAGE).contains(&per_page) {
+            return Err(AppError::BadRequest(
+                codes::INVALID_PAGINATION,
+                format!(
+                    "'per_page' must be between 1 and {}",
+                    MAX_PER_PAGE
+                ),
+            ));
+        }
+    }
+    Ok((page.max(1), per_page.clamp(1, MAX_PER_PAGE)))
+}
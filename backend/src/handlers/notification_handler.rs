@@ -0,0 +1,246 @@
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use futures_util::Stream;
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::auth::{self, AuthUser};
+use crate::config::AppConfig;
+use crate::dto::{
+    NotificationFilterParams, NotificationListResponse, NotificationResponse, UnreadCountResponse,
+};
+use crate::errors::{codes, AppError};
+use crate::handlers::page_bounds;
+use crate::models::Notification;
+use crate::AppState;
+
+/// Comment lines sent while idle, so proxies do not drop the connection
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// How often the token behind an open stream is re-checked
+const TOKEN_RECHECK: Duration = Duration::from_secs(30);
+
+pub fn notification_to_response(n: Notification, config: &AppConfig) -> NotificationResponse {
+    let payload = n.payload.0;
+    NotificationResponse {
+        id: n.id,
+        kind: n.kind,
+        task_id: payload.task_id,
+        task_number: payload.task_number,
+        display_number: config.display_number(payload.task_number),
+        title: payload.title,
+        actor_id: payload.actor_id,
+        reason: payload.reason,
+        status: payload.status,
+        read_at: n.read_at.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()),
+        created_at: n.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+async fn unread_count(state: &AppState, user_id: Uuid) -> Result<i64, AppError> {
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND read_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_one(&state.db)
+    .await?;
+    Ok(count)
+}
+
+/// Get the caller's notification inbox, newest first, with the number of unread ones
+#[utoipa::path(
+    get,
+    path = "/api/notifications",
+    params(
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 20, max 100)"),
+        ("unread_only" = Option<bool>, Query, description = "Only notifications not yet read")
+    ),
+    responses(
+        (status = 200, description = "Page of notifications", body = NotificationListResponse),
+        (status = 400, description = "Invalid query parameters")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Notifications"
+)]
+pub async fn get_notifications(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<NotificationFilterParams>,
+) -> Result<Json<NotificationListResponse>, AppError> {
+    let (page, per_page) = page_bounds(&state.config, params.page, params.per_page)?;
+    let offset = (page - 1) * per_page;
+    let unread_only = params.unread_only.unwrap_or(false);
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications
+         WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)",
+    )
+    .bind(auth.user_id)
+    .bind(unread_only)
+    .fetch_one(&state.db)
+    .await?;
+
+    let rows: Vec<Notification> = sqlx::query_as(
+        "SELECT id, user_id, kind, payload, read_at, created_at
+         FROM notifications
+         WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+         ORDER BY created_at DESC, id DESC
+         LIMIT $3 OFFSET $4",
+    )
+    .bind(auth.user_id)
+    .bind(unread_only)
+    .bind(per_page)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(NotificationListResponse {
+        items: rows
+            .into_iter()
+            .map(|n| notification_to_response(n, &state.config))
+            .collect(),
+        total,
+        page,
+        per_page,
+        unread_count: unread_count(&state, auth.user_id).await?,
+    }))
+}
+
+/// Mark one of the caller's notifications as read; marking it again changes nothing
+#[utoipa::path(
+    post,
+    path = "/api/notifications/{id}/read",
+    params(("id" = Uuid, Path, description = "Notification ID")),
+    responses(
+        (status = 200, description = "Unread notifications left", body = UnreadCountResponse),
+        (status = 404, description = "Notification not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Notifications"
+)]
+pub async fn mark_notification_read(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UnreadCountResponse>, AppError> {
+    // Other users' notifications are reported as missing, like tasks outside the caller's scope
+    let updated = sqlx::query(
+        "UPDATE notifications SET read_at = COALESCE(read_at, NOW())
+         WHERE id = $1 AND user_id = $2",
+    )
+    .bind(id)
+    .bind(auth.user_id)
+    .execute(&state.db)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            codes::NOTIFICATION_NOT_FOUND,
+            "Notification not found".to_string(),
+        ));
+    }
+
+    Ok(Json(UnreadCountResponse {
+        unread_count: unread_count(&state, auth.user_id).await?,
+    }))
+}
+
+/// Mark all of the caller's notifications as read
+#[utoipa::path(
+    post,
+    path = "/api/notifications/read-all",
+    responses(
+        (status = 200, description = "Unread notifications left (0)", body = UnreadCountResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Notifications"
+)]
+pub async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<UnreadCountResponse>, AppError> {
+    sqlx::query("UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL")
+        .bind(auth.user_id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(Json(UnreadCountResponse {
+        unread_count: unread_count(&state, auth.user_id).await?,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamParams {
+    /// Access token, for clients that cannot set the Authorization header (EventSource)
+    pub token: Option<String>,
+}
+
+/// Stream the caller's notifications as Server-Sent Events as they are added to the inbox:
+/// `task_assigned`, `task_commented`, `task_status_changed` and `task_reopened`
+#[utoipa::path(
+    get,
+    path = "/api/notifications/stream",
+    params(
+        ("token" = Option<String>, Query, description = "Access token, when the Authorization header cannot be set")
+    ),
+    responses(
+        (status = 200, description = "Event stream; each event's data is a NotificationResponse",
+            content_type = "text/event-stream", body = String),
+        (status = 401, description = "Missing, invalid or revoked token"),
+        (status = 403, description = "Sync accounts cannot open the stream")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Notifications"
+)]
+pub async fn notification_stream(
+    State(state): State<AppState>,
+    Query(params): Query<StreamParams>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    let token = auth::token_from(&headers, params.token)?;
+    let user = auth::authenticate(&state, &token, "/api/notifications/stream").await?;
+
+    let receiver = state.notifications.subscribe(user.user_id);
+    let mut recheck = tokio::time::interval(TOKEN_RECHECK);
+    recheck.reset();
+
+    let stream = futures_util::stream::unfold(
+        (receiver, recheck, state, token),
+        |(mut receiver, mut recheck, state, token)| async move {
+            loop {
+                tokio::select! {
+                    notification = receiver.recv() => match notification {
+                        Ok(notification) => {
+                            let event = Event::default()
+                                .event(notification.kind.to_string())
+                                .json_data(&*notification)
+                                .expect("notification serializes to JSON");
+                            return Some((Ok(event), (receiver, recheck, state, token)));
+                        }
+                        // A bell only needs the recent ones; skip what the stream fell behind on
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => return None,
+                    },
+                    // Expiry, logout and deactivation end the stream like they end requests
+                    _ = recheck.tick() => {
+                        let path = "/api/notifications/stream";
+                        if auth::authenticate(&state, &token, path).await.is_err() {
+                            return None;
+                        }
+                    }
+                }
+            }
+        },
+    );
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(KEEP_ALIVE)))
+}
@@ -0,0 +1,225 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::NaiveDateTime;
+
+use crate::auth::AuthUser;
+use crate::dto::{SyncChange, SyncTask, SyncTaskChange, SyncTasksPage, SyncTasksParams};
+use crate::errors::{codes, AppError};
+use crate::handlers::task_handler::{decode_cursor, encode_cursor, is_field_hidden, TASK_COLUMNS};
+use crate::models::{Task, UserRole};
+use crate::AppState;
+
+const DEFAULT_SYNC_LIMIT: i64 = 500;
+const MAX_SYNC_LIMIT: i64 = 1000;
+
+fn format_timestamp(at: NaiveDateTime) -> String {
+    at.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// The task row as sent to the mirror, with `TASK_FIELD_VISIBILITY` applied
+fn task_to_sync(t: Task, auth: &AuthUser, state: &AppState) -> SyncTask {
+    let visible = |field, value: Option<String>| {
+        value.filter(|_| !is_field_hidden(field, auth, &state.config))
+    };
+
+    SyncTask {
+        id: t.id,
+        task_number: t.task_number,
+        title: t.title,
+        description: visible("description", t.description),
+        assigned_by: t.assigned_by,
+        tester_id: t.tester_id,
+        status: t.status,
+        urgency: t.urgency,
+        created_at: format_timestamp(t.created_at),
+        closed_at: t.closed_at.map(format_timestamp),
+        acceptance_criteria: visible("acceptance_criteria", t.acceptance_criteria),
+        evaluation_criteria: visible("evaluation_criteria", t.evaluation_criteria),
+        comment: visible("comment", t.comment),
+        due_date: t.due_date.map(format_timestamp),
+        duplicate_of: t.duplicate_of,
+        updated_at: format_timestamp(t.updated_at),
+        estimate_hours: t.estimate_hours,
+    }
+}
+
+/// Changed tasks in `(updated_at, id)` order, for mirroring the task table incrementally.
+///
+/// `updated_at` is stamped with the writer's transaction start time, so a transaction still in
+/// flight can commit a row that sorts before rows already visible. Rows at or after the start
+/// of the oldest open transaction on this database are therefore held back until it ends, which
+/// keeps a cursor from ever passing a row that has yet to appear. Only the row's own columns
+/// are sent: tags, dependencies, the checklist and time entries do not move `updated_at`.
+#[utoipa::path(
+    get,
+    path = "/api/sync/tasks",
+    params(
+        ("since" = Option<String>, Query, description = "next_cursor of the previous page; omit for a full sync"),
+        ("limit" = Option<i64>, Query, description = "Changes per page (default 500, max 1000)")
+    ),
+    responses(
+        (status = 200, description = "Next page of task changes", body = SyncTasksPage),
+        (status = 400, description = "Invalid cursor"),
+        (status = 403, description = "Caller is not a sync account")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Sync"
+)]
+pub async fn sync_tasks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<SyncTasksParams>,
+) -> Result<Json<SyncTasksPage>, AppError> {
+    if auth.role != UserRole::Sync {
+        return Err(AppError::Forbidden(
+            codes::SYNC_ONLY,
+            "Only sync accounts can read the change feed".to_string(),
+        ));
+    }
+
+    let since = params.since.as_deref().map(decode_cursor).transpose()?;
+    let limit = params.limit.unwrap_or(DEFAULT_SYNC_LIMIT).clamp(1, MAX_SYNC_LIMIT);
+
+    let mut rows: Vec<Task> = sqlx::query_as(&format!(
+        "SELECT {} FROM tasks t
+         WHERE ($1::timestamp IS NULL OR (t.updated_at, t.id) > ($1, $2))
+           AND t.updated_at < (
+               SELECT LEAST(NOW(), MIN(xact_start))::timestamp
+               FROM pg_stat_activity
+               WHERE datname = current_database() AND pid <> pg_backend_pid()
+           )
+         ORDER BY t.updated_at, t.id
+         LIMIT $3",
+        TASK_COLUMNS
+    ))
+    .bind(since.map(|(at, _)| at))
+    .bind(since.map(|(_, id)| id))
+    .bind(limit + 1)
+    .fetch_all(&state.db)
+    .await?;
+
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+
+    let next_cursor = match rows.last() {
+        Some(last) => Some(encode_cursor(last.updated_at, last.id)),
+        None => params.since,
+    };
+
+    let changes = rows
+        .into_iter()
+        .map(|t| {
+            let (id, updated_at) = (t.id, t.updated_at);
+            let change = if t.deleted_at.is_some() {
+                SyncChange::Deleted
+            } else if since.is_none_or(|(at, _)| t.created_at > at) {
+                SyncChange::Created
+            } else {
+                SyncChange::Updated
+            };
+            let task = (change != SyncChange::Deleted).then(|| task_to_sync(t, &auth, &state));
+            SyncTaskChange {
+                change,
+                id,
+                updated_at: format_timestamp(updated_at),
+                task,
+            }
+        })
+        .collect();
+
+    Ok(Json(SyncTasksPage {
+        changes,
+        next_cursor,
+        has_more,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, create_task, create_user};
+    use axum::extract::FromRequestParts;
+    use sqlx::PgPool;
+
+    async fn sync(state: &AppState, auth: &AuthUser, since: Option<String>) -> SyncTasksPage {
+        let params = SyncTasksParams { since, limit: Some(2) };
+        sync_tasks(State(state.clone()), auth.clone(), Query(params)).await.unwrap().0
+    }
+
+    #[sqlx::test]
+    async fn pages_through_changes_and_reports_deletes(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let sync_user = create_user(&pool, "warehouse", UserRole::Sync).await;
+        let auth = test_support::auth(sync_user, UserRole::Sync);
+        let first = create_task(&pool, manager, None).await;
+        let second = create_task(&pool, manager, None).await;
+        let third = create_task(&pool, manager, None).await;
+
+        let page = sync(&state, &auth, None).await;
+        let ids: Vec<_> = page.changes.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [first, second]);
+        assert!(page.changes.iter().all(|c| c.change == SyncChange::Created));
+        assert!(page.has_more);
+
+        let page = sync(&state, &auth, page.next_cursor).await;
+        assert_eq!(page.changes.len(), 1);
+        assert_eq!(page.changes[0].id, third);
+        assert!(!page.has_more);
+        let caught_up = page.next_cursor;
+
+        let page = sync(&state, &auth, caught_up.clone()).await;
+        assert!(page.changes.is_empty());
+        assert_eq!(page.next_cursor, caught_up);
+
+        sqlx::query("UPDATE tasks SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1")
+            .bind(second)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE tasks SET title = 'Renamed', updated_at = NOW() WHERE id = $1")
+            .bind(first)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let page = sync(&state, &auth, caught_up).await;
+        let changes: Vec<_> = page.changes.iter().map(|c| (c.id, c.change)).collect();
+        assert_eq!(changes, [(second, SyncChange::Deleted), (first, SyncChange::Updated)]);
+        assert!(page.changes[0].task.is_none());
+        assert_eq!(page.changes[1].task.as_ref().unwrap().title, "Renamed");
+    }
+
+    #[sqlx::test]
+    async fn feed_and_sync_accounts_are_kept_apart(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let sync_user = create_user(&pool, "warehouse", UserRole::Sync).await;
+
+        let params = SyncTasksParams { since: None, limit: None };
+        let auth = test_support::auth(manager, UserRole::Manager);
+        let err = sync_tasks(State(state.clone()), auth, Query(params)).await.err().unwrap();
+        assert!(matches!(err, AppError::Forbidden(codes::SYNC_ONLY, _)));
+
+        let token =
+            crate::auth::create_token(sync_user, "warehouse", &UserRole::Sync, 0, &state.config)
+                .unwrap();
+        let request = |path: &str| {
+            axum::http::Request::builder()
+                .uri(path)
+                .header("Authorization", format!("Bearer {}", token))
+                .body(())
+                .unwrap()
+                .into_parts()
+                .0
+        };
+
+        let mut parts = request("/api/sync/tasks");
+        assert!(AuthUser::from_request_parts(&mut parts, &state).await.is_ok());
+        let mut parts = request("/api/tasks");
+        let err = AuthUser::from_request_parts(&mut parts, &state).await.err().unwrap();
+        assert!(matches!(err, AppError::Forbidden(codes::SYNC_ACCOUNT_RESTRICTED, _)));
+    }
+}
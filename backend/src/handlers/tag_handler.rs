@@ -0,0 +1,159 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::AuthUser;
+use crate::dto::AddTagRequest;
+use crate::errors::{codes, AppError};
+use crate::handlers::watcher_handler::ensure_task_visible;
+use crate::AppState;
+
+/// Tags are matched case-insensitively, so they are stored trimmed and lowercased
+pub fn normalize_tag(raw: &str) -> String {
+    raw.trim().to_lowercase()
+}
+
+async fn task_tags(db: &sqlx::PgPool, task_id: Uuid) -> Result<Vec<String>, AppError> {
+    let tags: Vec<String> =
+        sqlx::query_scalar("SELECT tag FROM task_tags WHERE task_id = $1 ORDER BY tag")
+            .bind(task_id)
+            .fetch_all(db)
+            .await?;
+    Ok(tags)
+}
+
+/// Add a tag to a task (adding an existing tag is a no-op)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/tags",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    request_body = AddTagRequest,
+    responses(
+        (status = 200, description = "All tags of the task", body = Vec<String>),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Admins cannot edit tasks"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn add_tag(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<AddTagRequest>,
+) -> Result<Json<Vec<String>>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    let payload = AddTagRequest {
+        tag: normalize_tag(&payload.tag),
+    };
+    payload.validate()?;
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    sqlx::query(
+        "INSERT INTO task_tags (task_id, tag) VALUES ($1, $2)
+         ON CONFLICT (task_id, tag) DO NOTHING",
+    )
+    .bind(task_id)
+    .bind(&payload.tag)
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(task_tags(&state.db, task_id).await?))
+}
+
+/// Remove a tag from a task
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{id}/tags/{tag}",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        ("tag" = String, Path, description = "Tag to remove")
+    ),
+    responses(
+        (status = 204, description = "Tag removed"),
+        (status = 403, description = "Admins cannot edit tasks"),
+        (status = 404, description = "Task not found or has no such tag")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn remove_tag(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((task_id, tag)): Path<(Uuid, String)>,
+) -> Result<axum::http::StatusCode, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let result = sqlx::query("DELETE FROM task_tags WHERE task_id = $1 AND tag = $2")
+        .bind(task_id)
+        .bind(normalize_tag(&tag))
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            codes::TAG_NOT_FOUND,
+            "Tag not found on this task".to_string(),
+        ));
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserRole;
+    use crate::test_support::{self, create_task, create_user};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn tags_of_hidden_or_deleted_tasks_cannot_be_removed(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        let other = create_user(&pool, "other", UserRole::Tester).await;
+        let foreign = create_task(&pool, manager, Some(other)).await;
+        let deleted = create_task(&pool, manager, Some(tester)).await;
+        sqlx::query("UPDATE tasks SET deleted_at = NOW() WHERE id = $1")
+            .bind(deleted)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO task_tags (task_id, tag) VALUES ($1, 'ui'), ($2, 'ui')")
+            .bind(foreign)
+            .bind(deleted)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let tester = test_support::auth(tester, UserRole::Tester);
+        for task_id in [foreign, deleted] {
+            let path = Path((task_id, "ui".to_string()));
+            let err = remove_tag(State(state.clone()), tester.clone(), path).await.err().unwrap();
+            assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+        }
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM task_tags")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+}
@@ -1,28 +1,399 @@
 use axum::{
     extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Response,
     Json,
 };
+use futures_util::StreamExt;
 use chrono::NaiveDateTime;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::auth::AuthUser;
+use crate::config::AppConfig;
 use crate::dto::{
-    CreateTaskRequest, EmployeeStats, TaskFilterParams, TaskListItem, TaskResponse,
-    UpdateTaskRequest,
+    AssignTaskRequest, BulkStatusUpdateRequest, BulkStatusUpdateResult, CreateTaskRequest, EmployeeStats,
+    EmployeeStatsParams, MarkDuplicateParams, MyTaskRole, MyTasksParams, OverdueTaskItem, PaginatedResponse, ReopenTaskRequest, SortOrder, TagMatch, TaskFilterParams, TaskListItem,
+    TaskResponse, TaskSortField, TaskSummaryResponse, TimeBucket, TimeseriesParams, TimeseriesPoint,
+    UpdateTaskRequest, UrgencyBreakdown,
 };
-use crate::errors::AppError;
-use crate::models::{Task, TaskStatus, TaskUrgency, UserRole};
+use crate::errors::{codes, AppError};
+use crate::etag::{self, Tagged};
+use crate::export::{self, csv_record, csv_response, ExportChunk};
+use crate::handlers::{
+    checklist_handler, created, dependency_handler, history_handler, page_bounds, tag_handler, view_handler,
+    watcher_handler, Created,
+};
+use crate::idempotency::{self, IdempotencyKey};
+use crate::models::{Task, TaskStatus, TaskUrgency, TaskWithNames, UserRole};
+use crate::models::WebhookEvent;
+use crate::notifications;
+use crate::notify::{self, TaskSummary};
+use crate::webhooks;
 use crate::AppState;
 
-fn task_to_response(
-    t: Task,
-    assigned_by_name: Option<String>,
-    tester_name: Option<String>,
-) -> TaskResponse {
+/// Filter predicates over `tasks t` shared by the task list, its total count and the export ($1..$15).
+/// Tags are unique per task, so counting matches against the number of wanted tags checks "all".
+/// $10 is the caller's visibility scope (see `visibility_scope`).
+/// $11 keeps tasks marked as duplicates, which are hidden by default.
+/// $12 keeps tasks whose tester or assigner belongs to the team.
+const TASK_FILTER_WHERE: &str = "($1::text[] IS NULL OR t.status::text = ANY($1))
+           AND ($2::text[] IS NULL OR t.urgency::text = ANY($2))
+           AND ($3::uuid IS NULL OR t.tester_id = $3)
+           AND ($4::uuid IS NULL OR t.assigned_by = $4)
+           AND ($5::timestamp IS NULL OR t.due_date < $5)
+           AND ($6::timestamp IS NULL OR t.due_date >= $6)
+           AND ($7::text[] IS NULL
+                OR (SELECT COUNT(*) FROM task_tags tt WHERE tt.task_id = t.id AND tt.tag = ANY($7))
+                   >= CASE WHEN $8 THEN cardinality($7) ELSE 1 END)
+           AND ($9 OR t.deleted_at IS NULL)
+           AND ($10::uuid IS NULL OR t.tester_id = $10 OR t.assigned_by = $10)
+           AND ($11 OR t.duplicate_of IS NULL)
+           AND ($12::uuid IS NULL OR EXISTS (
+                SELECT 1 FROM team_members tm
+                WHERE tm.team_id = $12 AND tm.user_id IN (t.tester_id, t.assigned_by)))
+           AND ($13::timestamp IS NULL OR t.created_at >= $13)
+           AND ($14::timestamp IS NULL OR t.created_at < $14)
+           AND ($15::int IS NULL OR t.task_number = $15)";
+
+/// Builds the ORDER BY clause from an allowlisted column, never from raw input.
+/// `urgency` and `status` are Postgres enums, so they sort by declaration order
+/// (low < medium < high < critical, new < ... < closed) rather than alphabetically.
+fn task_order_by(sort_by: Option<TaskSortField>, order: Option<SortOrder>) -> String {
+    let column = match sort_by.unwrap_or(TaskSortField::CreatedAt) {
+        TaskSortField::CreatedAt => "t.created_at",
+        TaskSortField::UpdatedAt => "t.updated_at",
+        TaskSortField::TaskNumber => "t.task_number",
+        TaskSortField::Urgency => "t.urgency",
+        TaskSortField::Status => "t.status",
+        TaskSortField::Title => "t.title",
+    };
+    let direction = match order.unwrap_or(SortOrder::Desc) {
+        SortOrder::Asc => "ASC",
+        SortOrder::Desc => "DESC",
+    };
+    format!("{} {}, t.id {}", column, direction, direction)
+}
+
+/// Opaque keyset cursor: base64url of `<timestamp as unix micros>:<id>` of the last row seen,
+/// `created_at` for task lists and `updated_at` for the sync feed
+pub fn encode_cursor(at: NaiveDateTime, id: Uuid) -> String {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+
+    URL_SAFE_NO_PAD.encode(format!("{}:{}", at.and_utc().timestamp_micros(), id))
+}
+
+pub fn decode_cursor(cursor: &str) -> Result<(NaiveDateTime, Uuid), AppError> {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+
+    let invalid = || AppError::BadRequest(codes::INVALID_CURSOR, "Invalid cursor".to_string());
+    let raw = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+    let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+    let (micros, id) = raw.split_once(':').ok_or_else(invalid)?;
+    let at = micros
+        .parse()
+        .ok()
+        .and_then(chrono::DateTime::from_timestamp_micros)
+        .ok_or_else(invalid)?
+        .naive_utc();
+    let id = id.parse().map_err(|_| invalid())?;
+    Ok((at, id))
+}
+
+type EmployeeStatsRow = (
+    Uuid,
+    String,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+    Option<f64>,
+);
+
+/// Per-employee task counts; `$1`/`$2` bound the period, `$3` a team and `$4` a role, each
+/// optional; `$5` drops deactivated users and `$6` users without counted tasks.
+/// Without a range every predicate is true, so all-time numbers are unchanged.
+const EMPLOYEE_STATS_QUERY: &str = "SELECT * FROM (
+         SELECT u.id, u.full_name,
+                COUNT(t.id) FILTER (
+                    WHERE ($1::timestamp IS NULL OR t.created_at >= $1)
+                      AND ($2::timestamp IS NULL OR t.created_at <= $2)
+                ) as total_tasks,
+                COUNT(t.id) FILTER (
+                    WHERE t.status::text IN ('done', 'closed')
+                      AND ($1::timestamp IS NULL OR t.closed_at >= $1)
+                      AND ($2::timestamp IS NULL OR t.closed_at <= $2)
+                ) as completed_tasks,
+                COUNT(t.id) FILTER (
+                    WHERE t.status::text = 'in_progress'
+                      AND ($1::timestamp IS NULL OR t.created_at >= $1)
+                      AND ($2::timestamp IS NULL OR t.created_at <= $2)
+                ) as in_progress_tasks,
+                (AVG(EXTRACT(EPOCH FROM (t.closed_at - t.created_at))) FILTER (
+                    WHERE t.status::text IN ('done', 'closed')
+                      AND t.closed_at IS NOT NULL
+                      AND ($1::timestamp IS NULL OR t.closed_at >= $1)
+                      AND ($2::timestamp IS NULL OR t.closed_at <= $2)
+                ) / 3600)::float8 as avg_completion_hours
+         FROM users u
+         LEFT JOIN tasks t ON t.tester_id = u.id AND t.deleted_at IS NULL
+         WHERE u.role::text != 'admin'
+           AND ($3::uuid IS NULL OR EXISTS (
+                SELECT 1 FROM team_members tm WHERE tm.team_id = $3 AND tm.user_id = u.id))
+           AND ($4::user_role IS NULL OR u.role = $4)
+           AND (NOT $5 OR u.is_active)
+         GROUP BY u.id, u.full_name
+     ) s
+     WHERE NOT $6 OR total_tasks > 0 OR completed_tasks > 0
+     ORDER BY full_name";
+
+fn employee_stats_from_row(row: EmployeeStatsRow) -> EmployeeStats {
+    let (user_id, full_name, total, completed, in_progress, avg_hours) = row;
+    EmployeeStats {
+        user_id,
+        full_name,
+        total_tasks: total.unwrap_or(0),
+        completed_tasks: completed.unwrap_or(0),
+        in_progress_tasks: in_progress.unwrap_or(0),
+        avg_completion_hours: avg_hours,
+    }
+}
+
+fn ensure_employee_stats_params(
+    auth: &AuthUser,
+    params: &EmployeeStatsParams,
+) -> Result<(), AppError> {
+    if auth.role != UserRole::Manager && auth.role != UserRole::Admin {
+        return Err(AppError::Forbidden(
+            codes::MANAGER_ONLY,
+            "Only managers and admins can view statistics".to_string(),
+        ));
+    }
+
+    if let (Some(from), Some(to)) = (params.from, params.to)
+        && from > to
+    {
+        return Err(AppError::BadRequest(
+            codes::INVALID_DATE_RANGE,
+            "'from' must not be after 'to'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// All `Task` columns over a `t` relation
+pub const TASK_COLUMNS: &str = "t.id, t.task_number, t.title, t.description, t.assigned_by,
+                t.tester_id, t.status, t.urgency, t.created_at, t.closed_at,
+                t.acceptance_criteria, t.evaluation_criteria, t.comment, t.due_date,
+                t.deleted_at, t.duplicate_of, t.updated_at, t.estimate_hours";
+
+/// Task columns plus joined user names, for use with `TASK_NAME_JOINS` over a `t` relation
+pub const TASK_WITH_NAMES_COLUMNS: &str = "t.id, t.task_number, t.title, t.description, t.assigned_by,
+                t.tester_id, t.status, t.urgency, t.created_at, t.closed_at,
+                t.acceptance_criteria, t.evaluation_criteria, t.comment, t.due_date,
+                t.deleted_at, t.duplicate_of, t.updated_at, t.estimate_hours,
+                a.full_name AS assigned_by_name, te.full_name AS tester_name,
+                ARRAY(SELECT tg.tag FROM task_tags tg WHERE tg.task_id = t.id ORDER BY tg.tag) AS tags,
+                ARRAY(SELECT d.depends_on_id FROM task_dependencies d
+                      WHERE d.task_id = t.id ORDER BY d.created_at) AS blocked_by,
+                ARRAY(SELECT d.task_id FROM task_dependencies d
+                      WHERE d.depends_on_id = t.id ORDER BY d.created_at) AS blocks,
+                (SELECT COALESCE(SUM(h.hours), 0) FROM time_entries h WHERE h.task_id = t.id)
+                    AS logged_hours,
+                (SELECT COALESCE(json_agg(json_build_object(
+                            'id', c.id, 'task_id', c.task_id, 'text', c.text,
+                            'done', c.done, 'position', c.position)
+                        ORDER BY c.position, c.created_at), '[]')
+                 FROM task_checklist_items c WHERE c.task_id = t.id) AS checklist";
+
+pub const TASK_NAME_JOINS: &str = "LEFT JOIN users a ON a.id = t.assigned_by
+         LEFT JOIN users te ON te.id = t.tester_id";
+
+fn is_finished(status: &TaskStatus) -> bool {
+    *status == TaskStatus::Done || *status == TaskStatus::Closed
+}
+
+/// Completion timestamp after moving from `old` (finished at `closed_at`) to `new`.
+/// It is stamped when the task enters done/closed, kept while it stays there (done -> closed
+/// included) and cleared when it is reopened.
+fn closed_at_after(
+    old: &TaskStatus,
+    closed_at: Option<NaiveDateTime>,
+    new: &TaskStatus,
+) -> Option<NaiveDateTime> {
+    if !is_finished(new) {
+        None
+    } else if is_finished(old) && closed_at.is_some() {
+        closed_at
+    } else {
+        Some(chrono::Utc::now().naive_utc())
+    }
+}
+
+/// Testers only see tasks they test or created; `None` means no restriction.
+///
+/// Policy: a task outside the caller's scope is reported as 404 by every endpoint that
+/// addresses it by id, reads and writes alike, so its existence is not disclosed. 403 is
+/// kept for tasks the caller can see but may not change.
+pub fn visibility_scope(auth: &AuthUser) -> Option<Uuid> {
+    (auth.role == UserRole::Tester).then_some(auth.user_id)
+}
+
+/// A task's tester must be an existing user with the tester role; otherwise
+/// the insert would surface a raw FK error (or silently accept any role).
+pub async fn ensure_tester(conn: &mut sqlx::PgConnection, tester_id: Uuid) -> Result<(), AppError> {
+    let role: Option<UserRole> = sqlx::query_scalar("SELECT role FROM users WHERE id = $1")
+        .bind(tester_id)
+        .fetch_optional(conn)
+        .await?;
+
+    match role {
+        None => Err(AppError::BadRequest(codes::USER_NOT_FOUND, "Tester not found".to_string())),
+        Some(UserRole::Tester) => Ok(()),
+        Some(role) => Err(AppError::BadRequest(
+            codes::BAD_REQUEST,
+            format!(
+                "User {} has role '{}', only testers can be assigned as tester",
+                tester_id, role
+            ),
+        )),
+    }
+}
+
+/// With `DEPENDENCIES_BLOCK_COMPLETION`, a task cannot be finished before its blockers
+async fn ensure_unblocked(
+    conn: &mut sqlx::PgConnection,
+    config: &AppConfig,
+    task_id: Uuid,
+) -> Result<(), AppError> {
+    if !config.dependencies_block_completion {
+        return Ok(());
+    }
+
+    let open = dependency_handler::open_blockers(conn, task_id).await?;
+    if open.is_empty() {
+        return Ok(());
+    }
+
+    let numbers: Vec<String> = open.iter().map(|n| config.display_number(*n)).collect();
+    Err(AppError::BadRequest(
+        codes::TASK_BLOCKED,
+        format!(
+            "Task is blocked by unfinished tasks: {}",
+            numbers.join(", ")
+        ),
+    ))
+}
+
+/// With `CHECKLIST_BLOCKS_COMPLETION`, a task cannot be done while a checklist item is unchecked
+async fn ensure_checklist_complete(
+    conn: &mut sqlx::PgConnection,
+    config: &AppConfig,
+    task_id: Uuid,
+) -> Result<(), AppError> {
+    if !config.checklist_blocks_completion {
+        return Ok(());
+    }
+
+    let unchecked = checklist_handler::unchecked_items(conn, task_id).await?;
+    if unchecked == 0 {
+        return Ok(());
+    }
+
+    Err(AppError::BadRequest(
+        codes::CHECKLIST_INCOMPLETE,
+        format!(
+            "Task has unchecked checklist items: {}",
+            unchecked
+        ),
+    ))
+}
+
+/// The `task_number` filter as a number; `None` when no number was asked for
+fn task_number_filter(config: &AppConfig, params: &TaskFilterParams) -> Result<Option<i32>, AppError> {
+    params
+        .task_number
+        .as_deref()
+        .map(|raw| {
+            config.parse_task_number(raw).ok_or_else(|| {
+                AppError::BadRequest(
+                    codes::INVALID_TASK_NUMBER,
+                    format!("Invalid task number: '{}'", raw),
+                )
+            })
+        })
+        .transpose()
+}
+
+fn ensure_created_range(params: &TaskFilterParams) -> Result<(), AppError> {
+    if let (Some(after), Some(before)) = (params.created_after, params.created_before)
+        && after > before
+    {
+        return Err(AppError::BadRequest(
+            codes::INVALID_DATE_RANGE,
+            "'created_after' must not be after 'created_before'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects filters the task list would reject, so a saved view is checked when it is stored
+pub fn validate_filters(
+    config: &AppConfig,
+    auth: &AuthUser,
+    params: &TaskFilterParams,
+) -> Result<(), AppError> {
+    page_bounds(config, params.page, params.per_page)?;
+    ensure_created_range(params)?;
+    task_number_filter(config, params)?;
+    include_deleted(params.include_deleted, auth)?;
+    Ok(())
+}
+
+/// Soft-deleted tasks are only listed on request, and only for managers
+fn include_deleted(requested: Option<bool>, auth: &AuthUser) -> Result<bool, AppError> {
+    let requested = requested.unwrap_or(false);
+    if requested && !auth.is_manager() {
+        return Err(AppError::Forbidden(
+            codes::MANAGER_ONLY,
+            "Only managers can list deleted tasks".to_string(),
+        ));
+    }
+    Ok(requested)
+}
+
+/// Parses the comma-separated `tags` filter; `None` when no usable tag was given
+fn parse_tag_filter(raw: Option<&str>) -> Option<Vec<String>> {
+    let tags: Vec<String> = raw?
+        .split(',')
+        .map(tag_handler::normalize_tag)
+        .filter(|t| !t.is_empty())
+        .collect();
+    (!tags.is_empty()).then_some(tags)
+}
+
+pub fn task_to_response(row: TaskWithNames, config: &AppConfig) -> TaskResponse {
+    let TaskWithNames {
+        task: t,
+        assigned_by_name,
+        tester_name,
+        tags,
+        blocked_by,
+        blocks,
+        logged_hours,
+        checklist,
+    } = row;
+    let checklist: Vec<_> = checklist
+        .0
+        .into_iter()
+        .map(checklist_handler::checklist_item_to_response)
+        .collect();
+
     TaskResponse {
         id: t.id,
         task_number: t.task_number,
+        display_number: config.display_number(t.task_number),
         title: t.title,
         description: t.description,
         assigned_by: t.assigned_by,
@@ -38,328 +409,1764 @@ fn task_to_response(
         acceptance_criteria: t.acceptance_criteria,
         evaluation_criteria: t.evaluation_criteria,
         comment: t.comment,
+        due_date: t
+            .due_date
+            .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+        tags,
+        deleted_at: t
+            .deleted_at
+            .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+        duplicate_of: t.duplicate_of,
+        updated_at: t.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        blocked_by,
+        blocks,
+        estimate_hours: t.estimate_hours,
+        logged_hours,
+        checklist_done: checklist.iter().filter(|item| item.done).count(),
+        checklist,
+        is_watching: None,
     }
 }
 
-async fn fetch_user_name(db: &sqlx::PgPool, user_id: Uuid) -> Option<String> {
-    sqlx::query_scalar::<_, String>("SELECT full_name FROM users WHERE id = $1")
-        .bind(user_id)
-        .fetch_optional(db)
-        .await
-        .ok()
-        .flatten()
+/// Creator and tester, who follow a task from the start
+pub fn participants(task: &Task) -> Vec<Uuid> {
+    [task.assigned_by].into_iter().chain(task.tester_id).collect()
+}
+
+/// Announces a task change to webhook subscribers and live `/ws/tasks` clients
+fn publish_task_event(state: &AppState, event: WebhookEvent, task: &TaskResponse) {
+    webhooks::dispatch(state, event, task);
+    state.live.publish(event, task.clone());
+}
+
+/// Tells the tester of a newly committed task about it and publishes its creation
+pub async fn announce_created(state: &AppState, actor_id: Uuid, task: TaskWithNames) -> TaskResponse {
+    if let Some(tester_id) = task.task.tester_id {
+        notifications::task_assigned(state, tester_id, actor_id, &task.task).await;
+        notify::tester_assigned(
+            state.mailer.as_ref(),
+            &state.db,
+            tester_id,
+            TaskSummary {
+                task_number: task.task.task_number,
+                title: task.task.title.clone(),
+                urgency: task.task.urgency.clone(),
+            },
+        );
+    }
+
+    let response = task_to_response(task, &state.config);
+    publish_task_event(state, WebhookEvent::Created, &response);
+    response
+}
+
+/// Hides task fields the caller's role is not allowed to see, per `TASK_FIELD_VISIBILITY`.
+/// Every handler that returns task details goes through here so the rule stays consistent.
+pub fn redact_task_fields(
+    mut resp: TaskResponse,
+    auth: &AuthUser,
+    config: &AppConfig,
+) -> TaskResponse {
+    let hidden = |field| is_field_hidden(field, auth, config);
+    if hidden("description") {
+        resp.description = None;
+    }
+    if hidden("acceptance_criteria") {
+        resp.acceptance_criteria = None;
+    }
+    if hidden("evaluation_criteria") {
+        resp.evaluation_criteria = None;
+    }
+    if hidden("comment") {
+        resp.comment = None;
+    }
+    resp
+}
+
+/// Whether `TASK_FIELD_VISIBILITY` hides `field` from the caller's role
+pub fn is_field_hidden(field: &str, auth: &AuthUser, config: &AppConfig) -> bool {
+    config
+        .task_field_visibility
+        .get(field)
+        .is_some_and(|roles| !roles.contains(&auth.role))
+}
+
+/// A task as returned to the caller: `redact_task_fields` plus whether they watch it
+pub async fn task_for_caller(
+    state: &AppState,
+    auth: &AuthUser,
+    resp: TaskResponse,
+) -> Result<TaskResponse, AppError> {
+    let mut resp = redact_task_fields(resp, auth, &state.config);
+    resp.is_watching = Some(watcher_handler::is_watching(&state.db, resp.id, auth.user_id).await?);
+    Ok(resp)
 }
 
-/// Get all tasks (with filtering)
+/// Get all tasks (with filtering; testers only see tasks they test or created)
 #[utoipa::path(
     get,
     path = "/api/tasks",
     params(
         ("page" = Option<i64>, Query, description = "Page number"),
-        ("per_page" = Option<i64>, Query, description = "Items per page"),
-        ("status" = Option<TaskStatus>, Query, description = "Filter by status"),
-        ("urgency" = Option<TaskUrgency>, Query, description = "Filter by urgency"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 20, max 100)"),
+        ("status" = Option<String>, Query, description = "Comma-separated statuses, e.g. new,in_progress,testing"),
+        ("urgency" = Option<String>, Query, description = "Comma-separated urgencies, e.g. high,critical"),
         ("tester_id" = Option<Uuid>, Query, description = "Filter by tester"),
-        ("assigned_by" = Option<Uuid>, Query, description = "Filter by assigner")
+        ("assigned_by" = Option<Uuid>, Query, description = "Filter by assigner"),
+        ("task_number" = Option<String>, Query, description = "A single task by number, e.g. 142 or TF-142"),
+        ("due_before" = Option<NaiveDateTime>, Query, description = "Due strictly before (e.g. 2026-10-20T00:00:00)"),
+        ("due_after" = Option<NaiveDateTime>, Query, description = "Due at or after"),
+        ("created_after" = Option<String>, Query, description = "Created at or after, RFC 3339 (e.g. 2026-10-12T00:00:00Z)"),
+        ("created_before" = Option<String>, Query, description = "Created strictly before, RFC 3339"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags"),
+        ("tags_match" = Option<TagMatch>, Query, description = "Match any (default) or all of the tags"),
+        ("include_deleted" = Option<bool>, Query, description = "Include soft-deleted tasks (managers only)"),
+        ("include_duplicates" = Option<bool>, Query, description = "Include tasks marked as duplicates"),
+        ("team_id" = Option<Uuid>, Query, description = "Only tasks whose tester or assigner is in the team"),
+        ("sort_by" = Option<TaskSortField>, Query, description = "Sort column (default created_at)"),
+        ("order" = Option<SortOrder>, Query, description = "Sort direction (default desc)"),
+        ("after" = Option<String>, Query, description = "Cursor from next_cursor; switches to keyset pagination (created_at order only)"),
+        ("view" = Option<Uuid>, Query, description = "Apply a saved view's filters")
     ),
     responses(
-        (status = 200, description = "List of tasks", body = Vec<TaskListItem>)
+        (status = 200, description = "Page of tasks", body = PaginatedResponse<TaskListItem>),
+        (status = 400, description = "Invalid query parameters"),
+        (status = 404, description = "Saved view not found")
     ),
     security(("bearer_auth" = [])),
     tag = "Tasks"
 )]
 pub async fn get_tasks(
     State(state): State<AppState>,
-    _auth: AuthUser,
+    auth: AuthUser,
+    Query(params): Query<TaskFilterParams>,
+) -> Result<Json<PaginatedResponse<TaskListItem>>, AppError> {
+    let params = view_handler::resolve_filters(&state.db, auth.user_id, params).await?;
+    let scope = visibility_scope(&auth);
+
+    Ok(Json(list_tasks(&state, &auth, params, scope).await?))
+}
+
+/// Get tasks the caller tests or created (filterable like the task list)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/mine",
+    params(
+        ("role" = Option<MyTaskRole>, Query, description = "Only tasks I test (tester) or created (assigner); both by default"),
+        ("page" = Option<i64>, Query, description = "Page number"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 20, max 100)"),
+        ("status" = Option<String>, Query, description = "Comma-separated statuses, e.g. new,in_progress,testing"),
+        ("urgency" = Option<String>, Query, description = "Comma-separated urgencies, e.g. high,critical"),
+        ("task_number" = Option<String>, Query, description = "A single task by number, e.g. 142 or TF-142"),
+        ("due_before" = Option<NaiveDateTime>, Query, description = "Due strictly before (e.g. 2026-10-20T00:00:00)"),
+        ("due_after" = Option<NaiveDateTime>, Query, description = "Due at or after"),
+        ("created_after" = Option<String>, Query, description = "Created at or after, RFC 3339 (e.g. 2026-10-12T00:00:00Z)"),
+        ("created_before" = Option<String>, Query, description = "Created strictly before, RFC 3339"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags"),
+        ("tags_match" = Option<TagMatch>, Query, description = "Match any (default) or all of the tags"),
+        ("include_deleted" = Option<bool>, Query, description = "Include soft-deleted tasks (managers only)"),
+        ("include_duplicates" = Option<bool>, Query, description = "Include tasks marked as duplicates"),
+        ("team_id" = Option<Uuid>, Query, description = "Only tasks whose tester or assigner is in the team"),
+        ("sort_by" = Option<TaskSortField>, Query, description = "Sort column (default created_at)"),
+        ("order" = Option<SortOrder>, Query, description = "Sort direction (default desc)"),
+        ("after" = Option<String>, Query, description = "Cursor from next_cursor; switches to keyset pagination (created_at order only)"),
+        ("view" = Option<Uuid>, Query, description = "Apply a saved view's filters")
+    ),
+    responses(
+        (status = 200, description = "Page of tasks", body = PaginatedResponse<TaskListItem>),
+        (status = 400, description = "Invalid query parameters"),
+        (status = 404, description = "Saved view not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn get_my_tasks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(mine): Query<MyTasksParams>,
+    Query(params): Query<TaskFilterParams>,
+) -> Result<Json<PaginatedResponse<TaskListItem>>, AppError> {
+    let mut params = view_handler::resolve_filters(&state.db, auth.user_id, params).await?;
+
+    // The assignment filters always come from the caller, never from the query or view
+    params.tester_id = None;
+    params.assigned_by = None;
+    match mine.role {
+        Some(MyTaskRole::Tester) => params.tester_id = Some(auth.user_id),
+        Some(MyTaskRole::Assigner) => params.assigned_by = Some(auth.user_id),
+        None => {}
+    }
+
+    Ok(Json(list_tasks(&state, &auth, params, Some(auth.user_id)).await?))
+}
+
+/// Get the tasks matching one of the caller's saved views
+#[utoipa::path(
+    get,
+    path = "/api/views/{id}/tasks",
+    params(
+        ("id" = Uuid, Path, description = "View ID"),
+        ("page" = Option<i64>, Query, description = "Page number"),
+        ("per_page" = Option<i64>, Query, description = "Items per page (default: the view's, or 20)"),
+        ("after" = Option<String>, Query, description = "Cursor from next_cursor")
+    ),
+    responses(
+        (status = 200, description = "Page of tasks", body = PaginatedResponse<TaskListItem>),
+        (status = 400, description = "Invalid query parameters"),
+        (status = 404, description = "Saved view not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Views"
+)]
+pub async fn get_view_tasks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
     Query(params): Query<TaskFilterParams>,
-) -> Result<Json<Vec<TaskListItem>>, AppError> {
-    let page = params.page.unwrap_or(1).max(1);
-    let per_page = params.per_page.unwrap_or(20).clamp(1, 100);
+) -> Result<Json<PaginatedResponse<TaskListItem>>, AppError> {
+    // Like `GET /api/tasks?view={id}`: other task list parameters override the stored ones
+    let params = TaskFilterParams {
+        view: Some(id),
+        ..params
+    };
+    let params = view_handler::resolve_filters(&state.db, auth.user_id, params).await?;
+    let scope = visibility_scope(&auth);
+
+    Ok(Json(list_tasks(&state, &auth, params, scope).await?))
+}
+
+/// One page of tasks matching `params`, restricted to tasks `scope` tests or created
+async fn list_tasks(
+    state: &AppState,
+    auth: &AuthUser,
+    params: TaskFilterParams,
+    scope: Option<Uuid>,
+) -> Result<PaginatedResponse<TaskListItem>, AppError> {
+    let (page, per_page) = page_bounds(&state.config, params.page, params.per_page)?;
+    ensure_created_range(&params)?;
+    let task_number = task_number_filter(&state.config, &params)?;
+
+    // Cursors are keyed on (created_at, id), so they only make sense in that order
+    let keyset = matches!(params.sort_by, None | Some(TaskSortField::CreatedAt));
+    let cursor = params.after.as_deref().map(decode_cursor).transpose()?;
+    if cursor.is_some() && !keyset {
+        return Err(AppError::BadRequest(
+            codes::INVALID_CURSOR,
+            "'after' can only be used with sort_by=created_at".to_string(),
+        ));
+    }
+    // In cursor mode `page` is ignored: the cursor already marks the position
+    let page = if cursor.is_some() { 1 } else { page };
     let offset = (page - 1) * per_page;
+    let after_op = match params.order.unwrap_or(SortOrder::Desc) {
+        SortOrder::Asc => ">",
+        SortOrder::Desc => "<",
+    };
 
-    let status_str = params.status.map(|s| s.to_string());
-    let urgency_str = params.urgency.map(|u| u.to_string());
+    let statuses = params
+        .status
+        .map(|v| v.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+    let urgencies = params
+        .urgency
+        .map(|v| v.iter().map(|u| u.to_string()).collect::<Vec<_>>());
+    let tags = parse_tag_filter(params.tags.as_deref());
+    let match_all = params.tags_match == Some(TagMatch::All);
+    let include_deleted = include_deleted(params.include_deleted, auth)?;
+    let include_duplicates = params.include_duplicates.unwrap_or(false);
 
-    let tasks: Vec<Task> = sqlx::query_as(
-        "SELECT id, task_number, title, description, assigned_by, tester_id,
-                status, urgency, created_at, closed_at, acceptance_criteria,
-                evaluation_criteria, comment
-         FROM tasks
-         WHERE ($1::text IS NULL OR status::text = $1)
-           AND ($2::text IS NULL OR urgency::text = $2)
-           AND ($3::uuid IS NULL OR tester_id = $3)
-           AND ($4::uuid IS NULL OR assigned_by = $4)
-         ORDER BY created_at DESC
-         LIMIT $5 OFFSET $6",
-    )
-    .bind(&status_str)
-    .bind(&urgency_str)
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM tasks t WHERE {}",
+        TASK_FILTER_WHERE
+    ))
+    .bind(&statuses)
+    .bind(&urgencies)
+    .bind(params.tester_id)
+    .bind(params.assigned_by)
+    .bind(params.due_before)
+    .bind(params.due_after)
+    .bind(&tags)
+    .bind(match_all)
+    .bind(include_deleted)
+    .bind(scope)
+    .bind(include_duplicates)
+    .bind(params.team_id)
+    .bind(params.created_after.map(|d| d.naive_utc()))
+    .bind(params.created_before.map(|d| d.naive_utc()))
+    .bind(task_number)
+    .fetch_one(&state.db)
+    .await?;
+
+    // One extra row tells whether there is a next page
+    let mut tasks: Vec<Task> = sqlx::query_as(&format!(
+        "SELECT {}
+         FROM tasks t
+         WHERE {}
+           AND ($16::timestamp IS NULL OR (t.created_at, t.id) {} ($16, $17))
+         ORDER BY {}
+         LIMIT $18 OFFSET $19",
+        TASK_COLUMNS,
+        TASK_FILTER_WHERE,
+        after_op,
+        task_order_by(params.sort_by, params.order)
+    ))
+    .bind(&statuses)
+    .bind(&urgencies)
     .bind(params.tester_id)
     .bind(params.assigned_by)
-    .bind(per_page)
+    .bind(params.due_before)
+    .bind(params.due_after)
+    .bind(&tags)
+    .bind(match_all)
+    .bind(include_deleted)
+    .bind(scope)
+    .bind(include_duplicates)
+    .bind(params.team_id)
+    .bind(params.created_after.map(|d| d.naive_utc()))
+    .bind(params.created_before.map(|d| d.naive_utc()))
+    .bind(task_number)
+    .bind(cursor.map(|(created_at, _)| created_at))
+    .bind(cursor.map(|(_, id)| id))
+    .bind(per_page + 1)
     .bind(offset)
     .fetch_all(&state.db)
     .await?;
 
-    let response: Vec<TaskListItem> = tasks
+    let has_more = tasks.len() as i64 > per_page;
+    tasks.truncate(per_page as usize);
+    let next_cursor = match tasks.last() {
+        Some(last) if has_more && keyset => Some(encode_cursor(last.created_at, last.id)),
+        _ => None,
+    };
+
+    let items: Vec<TaskListItem> = tasks
         .into_iter()
         .map(|t| TaskListItem {
             id: t.id,
             task_number: t.task_number,
+            display_number: state.config.display_number(t.task_number),
             title: t.title,
             status: t.status,
             urgency: t.urgency,
+            due_date: t
+                .due_date
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+            deleted_at: t
+                .deleted_at
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+        })
+        .collect();
+
+    Ok(PaginatedResponse {
+        items,
+        total,
+        page,
+        per_page,
+        next_cursor,
+    })
+}
+
+/// Get task counts by status and by urgency (scoped like the task list)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/summary",
+    responses(
+        (status = 200, description = "Counts of non-deleted tasks visible to the caller", body = TaskSummaryResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn get_task_summary(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<TaskSummaryResponse>, AppError> {
+    // One pass: a row per status, a row per urgency and the grand total (both NULL)
+    let rows: Vec<(Option<TaskStatus>, Option<TaskUrgency>, i64)> = sqlx::query_as(
+        "SELECT t.status, t.urgency, COUNT(*)
+         FROM tasks t
+         WHERE t.deleted_at IS NULL
+           AND ($1::uuid IS NULL OR t.tester_id = $1 OR t.assigned_by = $1)
+         GROUP BY GROUPING SETS ((t.status), (t.urgency), ())",
+    )
+    .bind(visibility_scope(&auth))
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut summary = TaskSummaryResponse::default();
+    for (status, urgency, count) in rows {
+        match (status, urgency) {
+            (Some(status), _) => match status {
+                TaskStatus::New => summary.by_status.new = count,
+                TaskStatus::InProgress => summary.by_status.in_progress = count,
+                TaskStatus::Testing => summary.by_status.testing = count,
+                TaskStatus::Done => summary.by_status.done = count,
+                TaskStatus::Closed => summary.by_status.closed = count,
+            },
+            (None, Some(urgency)) => match urgency {
+                TaskUrgency::Low => summary.by_urgency.low = count,
+                TaskUrgency::Medium => summary.by_urgency.medium = count,
+                TaskUrgency::High => summary.by_urgency.high = count,
+                TaskUrgency::Critical => summary.by_urgency.critical = count,
+            },
+            (None, None) => summary.total = count,
+        }
+    }
+
+    Ok(Json(summary))
+}
+
+/// Get tasks past their due date that are not closed, most overdue first (scoped like the task list)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/overdue",
+    responses(
+        (status = 200, description = "Overdue tasks visible to the caller", body = Vec<OverdueTaskItem>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn get_overdue_tasks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<OverdueTaskItem>>, AppError> {
+    // Due dates are naive UTC (see `create_task`), so compare against UTC rather than NOW()
+    let now = chrono::Utc::now().naive_utc();
+
+    let tasks: Vec<TaskWithNames> = sqlx::query_as(&format!(
+        "SELECT {} FROM tasks t {}
+         WHERE t.deleted_at IS NULL
+           AND t.status <> 'closed'
+           AND t.due_date < $1
+           AND ($2::uuid IS NULL OR t.tester_id = $2 OR t.assigned_by = $2)
+         ORDER BY t.due_date, t.task_number",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(now)
+    .bind(visibility_scope(&auth))
+    .fetch_all(&state.db)
+    .await?;
+
+    let items = tasks
+        .into_iter()
+        .filter_map(|t| {
+            let due_date = t.task.due_date?;
+            Some(OverdueTaskItem {
+                id: t.task.id,
+                task_number: t.task.task_number,
+                display_number: state.config.display_number(t.task.task_number),
+                title: t.task.title,
+                status: t.task.status,
+                urgency: t.task.urgency,
+                tester_id: t.task.tester_id,
+                tester_name: t.tester_name,
+                due_date: due_date.format("%Y-%m-%d %H:%M:%S").to_string(),
+                days_overdue: (now - due_date).num_days(),
+            })
         })
         .collect();
 
-    Ok(Json(response))
+    Ok(Json(items))
+}
+
+/// Export tasks as CSV (same filters as the task list, without pagination)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/export",
+    params(
+        ("status" = Option<String>, Query, description = "Comma-separated statuses, e.g. new,in_progress,testing"),
+        ("urgency" = Option<String>, Query, description = "Comma-separated urgencies, e.g. high,critical"),
+        ("tester_id" = Option<Uuid>, Query, description = "Filter by tester"),
+        ("assigned_by" = Option<Uuid>, Query, description = "Filter by assigner"),
+        ("task_number" = Option<String>, Query, description = "A single task by number, e.g. 142 or TF-142"),
+        ("due_before" = Option<NaiveDateTime>, Query, description = "Due strictly before (e.g. 2026-10-20T00:00:00)"),
+        ("due_after" = Option<NaiveDateTime>, Query, description = "Due at or after"),
+        ("created_after" = Option<String>, Query, description = "Created at or after, RFC 3339 (e.g. 2026-10-12T00:00:00Z)"),
+        ("created_before" = Option<String>, Query, description = "Created strictly before, RFC 3339"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags"),
+        ("tags_match" = Option<TagMatch>, Query, description = "Match any (default) or all of the tags"),
+        ("include_deleted" = Option<bool>, Query, description = "Include soft-deleted tasks (managers only)"),
+        ("include_duplicates" = Option<bool>, Query, description = "Include tasks marked as duplicates"),
+        ("team_id" = Option<Uuid>, Query, description = "Only tasks whose tester or assigner is in the team"),
+        ("sort_by" = Option<TaskSortField>, Query, description = "Sort column (default created_at)"),
+        ("order" = Option<SortOrder>, Query, description = "Sort direction (default desc)"),
+        ("view" = Option<Uuid>, Query, description = "Apply a saved view's filters")
+    ),
+    responses(
+        (status = 200, description = "CSV file", content_type = "text/csv", body = String),
+        (status = 400, description = "Invalid query parameters"),
+        (status = 404, description = "Saved view not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn export_tasks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<TaskFilterParams>,
+) -> Result<Response, AppError> {
+    let params = view_handler::resolve_filters(&state.db, auth.user_id, params).await?;
+    ensure_created_range(&params)?;
+    let task_number = task_number_filter(&state.config, &params)?;
+
+    let statuses = params
+        .status
+        .map(|v| v.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+    let urgencies = params
+        .urgency
+        .map(|v| v.iter().map(|u| u.to_string()).collect::<Vec<_>>());
+    let tags = parse_tag_filter(params.tags.as_deref());
+    let match_all = params.tags_match == Some(TagMatch::All);
+    let include_deleted = include_deleted(params.include_deleted, &auth)?;
+    let include_duplicates = params.include_duplicates.unwrap_or(false);
+    let scope = visibility_scope(&auth);
+    let sql = format!(
+        "SELECT {} FROM tasks t {} WHERE {} ORDER BY {}",
+        TASK_WITH_NAMES_COLUMNS,
+        TASK_NAME_JOINS,
+        TASK_FILTER_WHERE,
+        task_order_by(params.sort_by, params.order)
+    );
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<ExportChunk>(32);
+    let mut db_tx = export::unbounded_transaction(&state.db).await?;
+
+    tokio::spawn(async move {
+        let header = csv_record([
+            "task_number",
+            "title",
+            "status",
+            "urgency",
+            "assigned_by_name",
+            "tester_name",
+            "created_at",
+            "closed_at",
+            "due_date",
+            "tags",
+        ]);
+        if tx.send(Ok(header)).await.is_err() {
+            return;
+        }
+
+        let mut rows = sqlx::query_as::<_, TaskWithNames>(&sql)
+            .bind(&statuses)
+            .bind(&urgencies)
+            .bind(params.tester_id)
+            .bind(params.assigned_by)
+            .bind(params.due_before)
+            .bind(params.due_after)
+            .bind(&tags)
+            .bind(match_all)
+            .bind(include_deleted)
+            .bind(scope)
+            .bind(include_duplicates)
+            .bind(params.team_id)
+            .bind(params.created_after.map(|d| d.naive_utc()))
+            .bind(params.created_before.map(|d| d.naive_utc()))
+            .bind(task_number)
+            .fetch(&mut *db_tx);
+
+        while let Some(row) = rows.next().await {
+            let chunk = match row {
+                Ok(row) => {
+                    let t = row.task;
+                    Ok(csv_record([
+                        t.task_number.to_string(),
+                        t.title,
+                        t.status.to_string(),
+                        t.urgency.to_string(),
+                        row.assigned_by_name.unwrap_or_default(),
+                        row.tester_name.unwrap_or_default(),
+                        t.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        t.closed_at
+                            .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_default(),
+                        t.due_date
+                            .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_default(),
+                        row.tags.join(";"),
+                    ]))
+                }
+                Err(e) => {
+                    tracing::error!("Task export failed: {:?}", e);
+                    Err(std::io::Error::other("task export failed"))
+                }
+            };
+            let failed = chunk.is_err();
+            // Receiver gone means the client disconnected
+            if tx.send(chunk).await.is_err() || failed {
+                return;
+            }
+        }
+    });
+
+    Ok(csv_response("tasks", rx))
+}
+
+/// Get task by ID
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous read")
+    ),
+    responses(
+        (status = 200, description = "Task details", body = TaskResponse,
+            headers(("ETag" = String, description = "Version of the task"))),
+        (status = 304, description = "Task unchanged since the If-None-Match version"),
+        (status = 404, description = "Task not found or not visible to the caller")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn get_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "SELECT {} FROM tasks t {}
+         WHERE t.id = $1 AND t.deleted_at IS NULL
+           AND ($2::uuid IS NULL OR t.tester_id = $2 OR t.assigned_by = $2)",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(id)
+    .bind(visibility_scope(&auth))
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?;
+
+    etag::conditional_get(
+        &headers,
+        task_for_caller(&state, &auth, task_to_response(task, &state.config)).await?,
+    )
+}
+
+/// Get task by its number, bare (`142`) or with the prefix (`TF-142`)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/by-number/{task_number}",
+    params(
+        ("task_number" = String, Path, description = "Task number, e.g. 142 or TF-142"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous read")
+    ),
+    responses(
+        (status = 200, description = "Task details", body = TaskResponse,
+            headers(("ETag" = String, description = "Version of the task"))),
+        (status = 304, description = "Task unchanged since the If-None-Match version"),
+        (status = 404, description = "Task not found or not visible to the caller")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn get_task_by_number(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_number): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let not_found = || AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string());
+    let task_number = state
+        .config
+        .parse_task_number(&task_number)
+        .ok_or_else(not_found)?;
+
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "SELECT {} FROM tasks t {}
+         WHERE t.task_number = $1 AND t.deleted_at IS NULL
+           AND ($2::uuid IS NULL OR t.tester_id = $2 OR t.assigned_by = $2)",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(task_number)
+    .bind(visibility_scope(&auth))
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(not_found)?;
+
+    etag::conditional_get(
+        &headers,
+        task_for_caller(&state, &auth, task_to_response(task, &state.config)).await?,
+    )
+}
+
+/// Create a new task (all roles except admin)
+#[utoipa::path(
+    post,
+    path = "/api/tasks",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key return the original task for 24h")
+    ),
+    request_body = CreateTaskRequest,
+    responses(
+        (status = 201, description = "Task created (or the task created earlier with this Idempotency-Key)", body = TaskResponse,
+            headers(("Location" = String, description = "URL of the task"))),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Admins cannot create tasks"),
+        (status = 409, description = "Idempotency-Key reused with a different body")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn create_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
+    Json(payload): Json<CreateTaskRequest>,
+) -> Result<Created<TaskResponse>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot create tasks".to_string(),
+        ));
+    }
+
+    payload.validate()?;
+
+    let request_hash = idempotency::request_hash(&payload)?;
+    if let Some(key) = &idempotency_key {
+        let mut conn = state.db.acquire().await?;
+        if let Some(task_id) =
+            idempotency::find(&mut conn, auth.user_id, "task", key, &request_hash).await?
+        {
+            let task: TaskWithNames = sqlx::query_as(&format!(
+                "SELECT {} FROM tasks t {} WHERE t.id = $1",
+                TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+            ))
+            .bind(task_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            return Ok(created(
+                format!("/api/tasks/{}", task.task.id),
+                task_for_caller(&state, &auth, task_to_response(task, &state.config)).await?,
+            ));
+        }
+    }
+
+    if payload
+        .due_date
+        .is_some_and(|d| d < chrono::Utc::now().naive_utc())
+    {
+        return Err(AppError::BadRequest(
+            codes::DUE_DATE_IN_PAST,
+            "Due date cannot be in the past".to_string(),
+        ));
+    }
+
+    if let Some(tester_id) = payload.tester_id {
+        let mut conn = state.db.acquire().await?;
+        ensure_tester(&mut conn, tester_id).await?;
+    }
+
+    let urgency_str = payload
+        .urgency
+        .as_ref()
+        .map(|u| u.to_string())
+        .unwrap_or_else(|| "medium".to_string());
+
+    let mut tx = state.db.begin().await?;
+
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             INSERT INTO tasks (title, description, assigned_by, tester_id, urgency,
+                                acceptance_criteria, evaluation_criteria, comment, due_date,
+                                estimate_hours)
+             VALUES ($1, $2, $3, $4, $5::task_urgency, $6, $7, $8, $9, $10)
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(&payload.title)
+    .bind(&payload.description)
+    .bind(auth.user_id)
+    .bind(payload.tester_id)
+    .bind(&urgency_str)
+    .bind(&payload.acceptance_criteria)
+    .bind(&payload.evaluation_criteria)
+    .bind(&payload.comment)
+    .bind(payload.due_date)
+    .bind(payload.estimate_hours)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    watcher_handler::add_watchers(&mut tx, task.task.id, &participants(&task.task)).await?;
+
+    if let Some(key) = &idempotency_key {
+        idempotency::save(&mut tx, auth.user_id, "task", key, &request_hash, task.task.id)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    let response = announce_created(&state, auth.user_id, task).await;
+
+    Ok(created(
+        format!("/api/tasks/{}", response.id),
+        task_for_caller(&state, &auth, response).await?,
+    ))
+}
+
+/// Create a copy of a task as a new task by the caller (all roles except admin)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/clone",
+    params(("id" = Uuid, Path, description = "Task to copy")),
+    responses(
+        (status = 201, description = "Copy created", body = TaskResponse,
+            headers(("Location" = String, description = "URL of the copy"))),
+        (status = 404, description = "Task not found"),
+        (status = 403, description = "Admins cannot create tasks")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn clone_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Created<TaskResponse>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot create tasks".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Work fields and the checklist (unchecked) are copied; status, comment, due date, closed_at
+    // and logged time start fresh
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             INSERT INTO tasks (title, description, assigned_by, tester_id, urgency,
+                                acceptance_criteria, evaluation_criteria, estimate_hours)
+             SELECT LEFT('Copy of ' || s.title, 255), s.description, $2, s.tester_id, s.urgency,
+                    s.acceptance_criteria, s.evaluation_criteria, s.estimate_hours
+             FROM tasks s
+             WHERE s.id = $1 AND s.deleted_at IS NULL
+               AND ($3::uuid IS NULL OR s.tester_id = $3 OR s.assigned_by = $3)
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(id)
+    .bind(auth.user_id)
+    .bind(visibility_scope(&auth))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?;
+
+    let mut task = task;
+    task.checklist.0 = checklist_handler::copy_checklist(&mut tx, id, task.task.id).await?;
+    watcher_handler::add_watchers(&mut tx, task.task.id, &participants(&task.task)).await?;
+
+    tx.commit().await?;
+
+    let response = announce_created(&state, auth.user_id, task).await;
+
+    Ok(created(
+        format!("/api/tasks/{}", response.id),
+        task_for_caller(&state, &auth, response).await?,
+    ))
+}
+
+/// Update a task (all roles except admin)
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag from a previous read; the update is refused if the task changed since")
+    ),
+    request_body = UpdateTaskRequest,
+    responses(
+        (status = 200, description = "Task updated", body = TaskResponse,
+            headers(("ETag" = String, description = "Version of the updated task"))),
+        (status = 400, description = "Validation error or invalid status transition"),
+        (status = 404, description = "Task not found or not visible to the caller"),
+        (status = 412, description = "Task changed since the If-Match version"),
+        (status = 403, description = "Admins cannot edit tasks")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn update_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateTaskRequest>,
+) -> Result<Tagged<TaskResponse>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let existing: Task = sqlx::query_as(&format!(
+        "SELECT {} FROM tasks t
+         WHERE t.id = $1 AND t.deleted_at IS NULL
+           AND ($2::uuid IS NULL OR t.tester_id = $2 OR t.assigned_by = $2)
+         FOR UPDATE",
+        TASK_COLUMNS
+    ))
+    .bind(id)
+    .bind(visibility_scope(&auth))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?;
+
+    // Compared against the version the caller read, under the row lock taken above
+    if etag::has_if_match(&headers) {
+        let current: TaskWithNames = sqlx::query_as(&format!(
+            "SELECT {} FROM tasks t {} WHERE t.id = $1",
+            TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+        ))
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let current = task_for_caller(&state, &auth, task_to_response(current, &state.config)).await?;
+        etag::ensure_match(&headers, &etag::of(&current)?)?;
+    }
+
+    let old_status = existing.status.clone();
+    let old_urgency = existing.urgency.clone();
+    let old_tester_id = existing.tester_id;
+
+    let new_title = payload.title.unwrap_or(existing.title);
+    let new_description = payload.description.unwrap_or(existing.description);
+    let new_tester_id = payload.tester_id.unwrap_or(existing.tester_id);
+    if let Some(tester_id) = new_tester_id.filter(|_| new_tester_id != old_tester_id) {
+        ensure_tester(&mut tx, tester_id).await?;
+    }
+    let new_status = payload.status.unwrap_or(existing.status.clone());
+    if !existing.status.can_transition_to(&new_status) {
+        return Err(AppError::BadRequest(
+            codes::INVALID_STATUS_TRANSITION,
+            format!(
+                "Invalid status transition: {} -> {}",
+                existing.status, new_status
+            ),
+        ));
+    }
+    if new_status != old_status && is_finished(&new_status) {
+        ensure_unblocked(&mut tx, &state.config, id).await?;
+    }
+    if new_status != old_status && new_status == TaskStatus::Done {
+        ensure_checklist_complete(&mut tx, &state.config, id).await?;
+    }
+    let new_urgency = payload.urgency.unwrap_or(existing.urgency);
+    let new_acceptance = payload.acceptance_criteria.unwrap_or(existing.acceptance_criteria);
+    let new_evaluation = payload.evaluation_criteria.unwrap_or(existing.evaluation_criteria);
+    let new_comment = payload.comment.unwrap_or(existing.comment);
+    let new_due_date = payload.due_date.unwrap_or(existing.due_date);
+    let new_estimate = payload.estimate_hours.unwrap_or(existing.estimate_hours);
+
+    let closed_at = closed_at_after(&old_status, existing.closed_at, &new_status);
+
+    let status_str = new_status.to_string();
+    let urgency_str = new_urgency.to_string();
+
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             UPDATE tasks SET title = $1, description = $2, tester_id = $3,
+                              status = $4::task_status, urgency = $5::task_urgency,
+                              acceptance_criteria = $6, evaluation_criteria = $7,
+                              comment = $8, closed_at = $9, due_date = $10,
+                              estimate_hours = $11, updated_at = NOW()
+             WHERE id = $12
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(&new_title)
+    .bind(&new_description)
+    .bind(new_tester_id)
+    .bind(&status_str)
+    .bind(&urgency_str)
+    .bind(&new_acceptance)
+    .bind(&new_evaluation)
+    .bind(&new_comment)
+    .bind(closed_at)
+    .bind(new_due_date)
+    .bind(new_estimate)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if old_status != new_status {
+        history_handler::record_task_event(
+            &mut tx,
+            id,
+            auth.user_id,
+            "status",
+            Some(old_status.to_string()),
+            Some(status_str),
+        )
+        .await?;
+    }
+    if old_urgency != new_urgency {
+        history_handler::record_task_event(
+            &mut tx,
+            id,
+            auth.user_id,
+            "urgency",
+            Some(old_urgency.to_string()),
+            Some(urgency_str),
+        )
+        .await?;
+    }
+    if old_tester_id != new_tester_id {
+        history_handler::record_task_event(
+            &mut tx,
+            id,
+            auth.user_id,
+            "tester_id",
+            old_tester_id.map(|u| u.to_string()),
+            new_tester_id.map(|u| u.to_string()),
+        )
+        .await?;
+        if let Some(tester_id) = new_tester_id {
+            watcher_handler::add_watchers(&mut tx, id, &[tester_id]).await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    if let Some(tester_id) = new_tester_id.filter(|_| old_tester_id != new_tester_id) {
+        notifications::task_assigned(&state, tester_id, auth.user_id, &task.task).await;
+        notify::tester_assigned(
+            state.mailer.as_ref(),
+            &state.db,
+            tester_id,
+            TaskSummary {
+                task_number: task.task.task_number,
+                title: task.task.title.clone(),
+                urgency: task.task.urgency.clone(),
+            },
+        );
+    }
+
+    if new_status != old_status {
+        notifications::task_status_changed(&state, auth.user_id, &task.task).await;
+    }
+
+    let response = task_to_response(task, &state.config);
+    publish_task_event(&state, WebhookEvent::Updated, &response);
+    if new_status == TaskStatus::Closed && old_status != TaskStatus::Closed {
+        publish_task_event(&state, WebhookEvent::Closed, &response);
+    }
+
+    etag::tagged(task_for_caller(&state, &auth, response).await?)
+}
+
+/// Reassign (or unassign) a task's tester without touching other fields (all roles except admin)
+#[utoipa::path(
+    patch,
+    path = "/api/tasks/{id}/assign",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    request_body = AssignTaskRequest,
+    responses(
+        (status = 200, description = "Task reassigned", body = TaskResponse),
+        (status = 400, description = "Tester not found or not a tester"),
+        (status = 404, description = "Task not found or not visible to the caller"),
+        (status = 403, description = "Admins cannot edit tasks")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn assign_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AssignTaskRequest>,
+) -> Result<Json<TaskResponse>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let old_tester_id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT tester_id FROM tasks
+         WHERE id = $1 AND deleted_at IS NULL
+           AND ($2::uuid IS NULL OR tester_id = $2 OR assigned_by = $2)
+         FOR UPDATE",
+    )
+    .bind(id)
+    .bind(visibility_scope(&auth))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?;
+
+    let new_tester_id = payload.tester_id;
+    let changed = old_tester_id != new_tester_id;
+    if let Some(tester_id) = new_tester_id.filter(|_| changed) {
+        ensure_tester(&mut tx, tester_id).await?;
+    }
+
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             UPDATE tasks SET tester_id = $1, updated_at = NOW() WHERE id = $2
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(new_tester_id)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if changed {
+        history_handler::record_task_event(
+            &mut tx,
+            id,
+            auth.user_id,
+            "tester_id",
+            old_tester_id.map(|u| u.to_string()),
+            new_tester_id.map(|u| u.to_string()),
+        )
+        .await?;
+        if let Some(tester_id) = new_tester_id {
+            watcher_handler::add_watchers(&mut tx, id, &[tester_id]).await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    if let Some(tester_id) = new_tester_id.filter(|_| changed) {
+        notifications::task_assigned(&state, tester_id, auth.user_id, &task.task).await;
+        notify::tester_assigned(
+            state.mailer.as_ref(),
+            &state.db,
+            tester_id,
+            TaskSummary {
+                task_number: task.task.task_number,
+                title: task.task.title.clone(),
+                urgency: task.task.urgency.clone(),
+            },
+        );
+    }
+
+    let response = task_to_response(task, &state.config);
+    if changed {
+        publish_task_event(&state, WebhookEvent::Updated, &response);
+    }
+
+    Ok(Json(task_for_caller(&state, &auth, response).await?))
+}
+
+/// Reopen a done or closed task, moving it back to in progress (all roles except admin)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/reopen",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    request_body = ReopenTaskRequest,
+    responses(
+        (status = 200, description = "Task reopened", body = TaskResponse),
+        (status = 400, description = "Validation error or the task is not done or closed"),
+        (status = 404, description = "Task not found or not visible to the caller"),
+        (status = 403, description = "Admins cannot edit tasks")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn reopen_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReopenTaskRequest>,
+) -> Result<Json<TaskResponse>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let old_status: TaskStatus = sqlx::query_scalar(
+        "SELECT status FROM tasks
+         WHERE id = $1 AND deleted_at IS NULL
+           AND ($2::uuid IS NULL OR tester_id = $2 OR assigned_by = $2)
+         FOR UPDATE",
+    )
+    .bind(id)
+    .bind(visibility_scope(&auth))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?;
+
+    if old_status != TaskStatus::Done && old_status != TaskStatus::Closed {
+        return Err(AppError::BadRequest(
+            codes::TASK_NOT_CLOSED,
+            format!(
+                "Only done or closed tasks can be reopened, this one is '{}'",
+                old_status
+            ),
+        ));
+    }
+
+    let new_status = TaskStatus::InProgress;
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             UPDATE tasks SET status = $1::task_status, closed_at = NULL, updated_at = NOW() WHERE id = $2
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(new_status.to_string())
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    history_handler::record_task_event_with_reason(
+        &mut tx,
+        id,
+        auth.user_id,
+        "status",
+        Some(old_status.to_string()),
+        Some(new_status.to_string()),
+        Some(payload.reason.clone()),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    notifications::task_reopened(&state, auth.user_id, &task.task, &payload.reason).await;
+
+    let response = task_to_response(task, &state.config);
+    publish_task_event(&state, WebhookEvent::Updated, &response);
+
+    Ok(Json(task_for_caller(&state, &auth, response).await?))
+}
+
+/// Mark a task as a duplicate of another (all roles except admin)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/duplicate-of/{other_id}",
+    params(
+        ("id" = Uuid, Path, description = "Duplicate task ID"),
+        ("other_id" = Uuid, Path, description = "Canonical task ID"),
+        ("move_comments" = Option<bool>, Query, description = "Move the duplicate's comments to the canonical task")
+    ),
+    responses(
+        (status = 200, description = "Task marked as duplicate", body = TaskResponse),
+        (status = 400, description = "The link would create a cycle"),
+        (status = 404, description = "Task not found or not visible to the caller"),
+        (status = 403, description = "Admins cannot edit tasks")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn mark_duplicate(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, other_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<MarkDuplicateParams>,
+) -> Result<Json<TaskResponse>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+    if id == other_id {
+        return Err(AppError::BadRequest(
+            codes::SELF_REFERENCE,
+            "A task cannot be a duplicate of itself".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let locked: Vec<(Uuid, Option<Uuid>)> = sqlx::query_as(
+        "SELECT id, duplicate_of FROM tasks
+         WHERE id = ANY($1) AND deleted_at IS NULL
+           AND ($2::uuid IS NULL OR tester_id = $2 OR assigned_by = $2)
+         ORDER BY id
+         FOR UPDATE",
+    )
+    .bind(vec![id, other_id])
+    .bind(visibility_scope(&auth))
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let old_duplicate_of = locked
+        .iter()
+        .find(|(task_id, _)| *task_id == id)
+        .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?
+        .1;
+    if !locked.iter().any(|(task_id, _)| *task_id == other_id) {
+        return Err(AppError::NotFound(
+            codes::TASK_NOT_FOUND,
+            "Canonical task not found".to_string(),
+        ));
+    }
+
+    // Following the canonical task's own duplicate chain must not lead back here
+    let cycle: bool = sqlx::query_scalar(
+        "WITH RECURSIVE chain(id) AS (
+             SELECT $1::uuid
+             UNION
+             SELECT t.duplicate_of FROM tasks t JOIN chain c ON t.id = c.id
+             WHERE t.duplicate_of IS NOT NULL
+         )
+         SELECT EXISTS(SELECT 1 FROM chain WHERE id = $2)",
+    )
+    .bind(other_id)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if cycle {
+        return Err(AppError::BadRequest(
+            codes::CYCLE,
+            "Marking this duplicate would create a cycle".to_string(),
+        ));
+    }
+
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             UPDATE tasks SET duplicate_of = $1, updated_at = NOW() WHERE id = $2
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(other_id)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if params.move_comments.unwrap_or(false) {
+        sqlx::query("UPDATE task_comments SET task_id = $1 WHERE task_id = $2")
+            .bind(other_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    if old_duplicate_of != Some(other_id) {
+        history_handler::record_task_event(
+            &mut tx,
+            id,
+            auth.user_id,
+            "duplicate_of",
+            old_duplicate_of.map(|u| u.to_string()),
+            Some(other_id.to_string()),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let response = task_to_response(task, &state.config);
+    publish_task_event(&state, WebhookEvent::Updated, &response);
+
+    Ok(Json(task_for_caller(&state, &auth, response).await?))
 }
 
-/// Get task by ID
+/// Clear a task's duplicate mark (all roles except admin)
 #[utoipa::path(
-    get,
-    path = "/api/tasks/{id}",
+    delete,
+    path = "/api/tasks/{id}/duplicate-of",
     params(("id" = Uuid, Path, description = "Task ID")),
     responses(
-        (status = 200, description = "Task details", body = TaskResponse),
-        (status = 404, description = "Task not found")
+        (status = 200, description = "Task is no longer a duplicate", body = TaskResponse),
+        (status = 404, description = "Task not found or not visible to the caller"),
+        (status = 403, description = "Admins cannot edit tasks")
     ),
     security(("bearer_auth" = [])),
     tag = "Tasks"
 )]
-pub async fn get_task(
+pub async fn unmark_duplicate(
     State(state): State<AppState>,
-    _auth: AuthUser,
+    auth: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<Json<TaskResponse>, AppError> {
-    let task: Task = sqlx::query_as(
-        "SELECT id, task_number, title, description, assigned_by, tester_id,
-                status, urgency, created_at, closed_at, acceptance_criteria,
-                evaluation_criteria, comment
-         FROM tasks WHERE id = $1",
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let old_duplicate_of: Option<Uuid> = sqlx::query_scalar(
+        "SELECT duplicate_of FROM tasks
+         WHERE id = $1 AND deleted_at IS NULL
+           AND ($2::uuid IS NULL OR tester_id = $2 OR assigned_by = $2)
+         FOR UPDATE",
     )
     .bind(id)
-    .fetch_optional(&state.db)
+    .bind(visibility_scope(&auth))
+    .fetch_optional(&mut *tx)
     .await?
-    .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?;
 
-    let assigned_by_name = fetch_user_name(&state.db, task.assigned_by).await;
-    let tester_name = match task.tester_id {
-        Some(tid) => fetch_user_name(&state.db, tid).await,
-        None => None,
-    };
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             UPDATE tasks SET duplicate_of = NULL, updated_at = NOW() WHERE id = $1
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if let Some(old) = old_duplicate_of {
+        history_handler::record_task_event(
+            &mut tx,
+            id,
+            auth.user_id,
+            "duplicate_of",
+            Some(old.to_string()),
+            None,
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let response = task_to_response(task, &state.config);
+    if old_duplicate_of.is_some() {
+        publish_task_event(&state, WebhookEvent::Updated, &response);
+    }
 
-    Ok(Json(task_to_response(task, assigned_by_name, tester_name)))
+    Ok(Json(task_for_caller(&state, &auth, response).await?))
 }
 
-/// Create a new task (all roles except admin)
+/// Move several tasks to one status in a single transaction (all roles except admin)
 #[utoipa::path(
-    post,
-    path = "/api/tasks",
-    request_body = CreateTaskRequest,
+    patch,
+    path = "/api/tasks/bulk",
+    request_body = BulkStatusUpdateRequest,
     responses(
-        (status = 201, description = "Task created", body = TaskResponse),
+        (status = 200, description = "Per-task outcome, in request order", body = Vec<BulkStatusUpdateResult>),
         (status = 400, description = "Validation error"),
-        (status = 403, description = "Admins cannot create tasks")
+        (status = 403, description = "Admins cannot edit tasks")
     ),
     security(("bearer_auth" = [])),
     tag = "Tasks"
 )]
-pub async fn create_task(
+pub async fn bulk_update_status(
     State(state): State<AppState>,
     auth: AuthUser,
-    Json(payload): Json<CreateTaskRequest>,
-) -> Result<(axum::http::StatusCode, Json<TaskResponse>), AppError> {
-    if auth.is_admin() {
+    Json(payload): Json<BulkStatusUpdateRequest>,
+) -> Result<Json<Vec<BulkStatusUpdateResult>>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
         return Err(AppError::Forbidden(
-            "Administrators cannot create tasks".to_string(),
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
         ));
     }
 
     payload.validate()?;
 
-    let urgency_str = payload
-        .urgency
-        .as_ref()
-        .map(|u| u.to_string())
-        .unwrap_or_else(|| "medium".to_string());
+    let mut ids = payload.ids;
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(*id));
 
-    let task: Task = sqlx::query_as(
-        "INSERT INTO tasks (title, description, assigned_by, tester_id, urgency,
-                            acceptance_criteria, evaluation_criteria, comment)
-         VALUES ($1, $2, $3, $4, $5::task_urgency, $6, $7, $8)
-         RETURNING id, task_number, title, description, assigned_by, tester_id,
-                   status, urgency, created_at, closed_at, acceptance_criteria,
-                   evaluation_criteria, comment",
+    let mut tx = state.db.begin().await?;
+
+    // Lock in id order so concurrent bulk updates cannot deadlock each other. Tasks outside the
+    // caller's scope are left out and reported like missing ones.
+    let rows: Vec<(Uuid, TaskStatus, Option<NaiveDateTime>)> = sqlx::query_as(
+        "SELECT id, status, closed_at FROM tasks
+         WHERE id = ANY($1) AND deleted_at IS NULL
+           AND ($2::uuid IS NULL OR tester_id = $2 OR assigned_by = $2)
+         ORDER BY id
+         FOR UPDATE",
     )
-    .bind(&payload.title)
-    .bind(&payload.description)
-    .bind(auth.user_id)
-    .bind(payload.tester_id)
-    .bind(&urgency_str)
-    .bind(&payload.acceptance_criteria)
-    .bind(&payload.evaluation_criteria)
-    .bind(&payload.comment)
-    .fetch_one(&state.db)
+    .bind(&ids)
+    .bind(visibility_scope(&auth))
+    .fetch_all(&mut *tx)
     .await?;
+    let current: std::collections::HashMap<Uuid, (TaskStatus, Option<NaiveDateTime>)> =
+        rows.into_iter().map(|(id, status, closed_at)| (id, (status, closed_at))).collect();
 
-    let assigned_by_name = fetch_user_name(&state.db, task.assigned_by).await;
-    let tester_name = match task.tester_id {
-        Some(tid) => fetch_user_name(&state.db, tid).await,
-        None => None,
-    };
+    let new_status = payload.status;
+    let status_str = new_status.to_string();
+    let mut results = Vec::with_capacity(ids.len());
+    let mut changed = Vec::new();
 
-    Ok((
-        axum::http::StatusCode::CREATED,
-        Json(task_to_response(task, assigned_by_name, tester_name)),
-    ))
+    for id in ids {
+        let skipped = |reason: String| BulkStatusUpdateResult {
+            id,
+            success: false,
+            reason: Some(reason),
+        };
+
+        let Some((old_status, old_closed_at)) = current.get(&id) else {
+            results.push(skipped("Task not found".to_string()));
+            continue;
+        };
+        if !old_status.can_transition_to(&new_status) {
+            results.push(skipped(format!(
+                "Invalid status transition: {} -> {}",
+                old_status, new_status
+            )));
+            continue;
+        }
+        if *old_status != new_status
+            && is_finished(&new_status)
+            && let Err(e) = ensure_unblocked(&mut tx, &state.config, id).await
+        {
+            results.push(skipped(match e {
+                AppError::BadRequest(_, reason) => reason,
+                other => return Err(other),
+            }));
+            continue;
+        }
+        if *old_status != new_status
+            && new_status == TaskStatus::Done
+            && let Err(e) = ensure_checklist_complete(&mut tx, &state.config, id).await
+        {
+            results.push(skipped(match e {
+                AppError::BadRequest(_, reason) => reason,
+                other => return Err(other),
+            }));
+            continue;
+        }
+
+        if *old_status != new_status {
+            sqlx::query(
+                "UPDATE tasks SET status = $1::task_status, closed_at = $2, updated_at = NOW()
+                 WHERE id = $3",
+            )
+            .bind(&status_str)
+            .bind(closed_at_after(old_status, *old_closed_at, &new_status))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+            history_handler::record_task_event(
+                &mut tx,
+                id,
+                auth.user_id,
+                "status",
+                Some(old_status.to_string()),
+                Some(status_str.clone()),
+            )
+            .await?;
+
+            changed.push((id, *old_status == TaskStatus::Closed));
+        }
+
+        results.push(BulkStatusUpdateResult {
+            id,
+            success: true,
+            reason: None,
+        });
+    }
+
+    tx.commit().await?;
+
+    if !changed.is_empty() {
+        let changed_ids: Vec<Uuid> = changed.iter().map(|(id, _)| *id).collect();
+        let tasks: Vec<TaskWithNames> = sqlx::query_as(&format!(
+            "SELECT {} FROM tasks t {} WHERE t.id = ANY($1)",
+            TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+        ))
+        .bind(&changed_ids)
+        .fetch_all(&state.db)
+        .await?;
+
+        for task in tasks {
+            let was_closed = changed.iter().any(|(id, closed)| *id == task.task.id && *closed);
+            notifications::task_status_changed(&state, auth.user_id, &task.task).await;
+            let response = task_to_response(task, &state.config);
+            publish_task_event(&state, WebhookEvent::Updated, &response);
+            if new_status == TaskStatus::Closed && !was_closed {
+                publish_task_event(&state, WebhookEvent::Closed, &response);
+            }
+        }
+    }
+
+    Ok(Json(results))
 }
 
-/// Update a task (all roles except admin)
+/// Soft-delete a task (manager or the person who created it)
 #[utoipa::path(
-    put,
+    delete,
     path = "/api/tasks/{id}",
     params(("id" = Uuid, Path, description = "Task ID")),
-    request_body = UpdateTaskRequest,
     responses(
-        (status = 200, description = "Task updated", body = TaskResponse),
-        (status = 400, description = "Validation error"),
-        (status = 404, description = "Task not found"),
-        (status = 403, description = "Admins cannot edit tasks")
+        (status = 204, description = "Task deleted"),
+        (status = 404, description = "Task not found or not visible to the caller"),
+        (status = 403, description = "Forbidden")
     ),
     security(("bearer_auth" = [])),
     tag = "Tasks"
 )]
-pub async fn update_task(
+pub async fn delete_task(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(id): Path<Uuid>,
-    Json(payload): Json<UpdateTaskRequest>,
-) -> Result<Json<TaskResponse>, AppError> {
-    if auth.is_admin() {
+) -> Result<axum::http::StatusCode, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
         return Err(AppError::Forbidden(
-            "Administrators cannot edit tasks".to_string(),
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot manage tasks".to_string(),
         ));
     }
 
-    payload.validate()?;
-
-    let existing: Task = sqlx::query_as(
-        "SELECT id, task_number, title, description, assigned_by, tester_id,
-                status, urgency, created_at, closed_at, acceptance_criteria,
-                evaluation_criteria, comment
-         FROM tasks WHERE id = $1",
-    )
+    let task: Task = sqlx::query_as(&format!(
+        "SELECT {} FROM tasks t
+         WHERE t.id = $1 AND t.deleted_at IS NULL
+           AND ($2::uuid IS NULL OR t.tester_id = $2 OR t.assigned_by = $2)",
+        TASK_COLUMNS
+    ))
     .bind(id)
+    .bind(visibility_scope(&auth))
     .fetch_optional(&state.db)
     .await?
-    .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?;
 
-    let new_title = payload.title.unwrap_or(existing.title);
-    let new_description = payload.description.or(existing.description);
-    let new_tester_id = payload.tester_id.or(existing.tester_id);
-    let new_status = payload.status.unwrap_or(existing.status);
-    let new_urgency = payload.urgency.unwrap_or(existing.urgency);
-    let new_acceptance = payload.acceptance_criteria.or(existing.acceptance_criteria);
-    let new_evaluation = payload.evaluation_criteria.or(existing.evaluation_criteria);
-    let new_comment = payload.comment.or(existing.comment);
+    // Admins only get this far with ADMINS_CAN_MANAGE_TASKS, and then act as managers
+    if task.assigned_by != auth.user_id && !auth.is_manager() && !auth.is_admin() {
+        return Err(AppError::Forbidden(
+            codes::NOT_TASK_CREATOR,
+            "Only the task creator or a manager can delete tasks".to_string(),
+        ));
+    }
 
-    let closed_at = if new_status == TaskStatus::Closed || new_status == TaskStatus::Done {
-        Some(chrono::Utc::now().naive_utc())
-    } else {
-        existing.closed_at
-    };
+    let mut tx = state.db.begin().await?;
 
-    let status_str = new_status.to_string();
-    let urgency_str = new_urgency.to_string();
+    sqlx::query("UPDATE tasks SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
 
-    let task: Task = sqlx::query_as(
-        "UPDATE tasks SET title = $1, description = $2, tester_id = $3,
-                          status = $4::task_status, urgency = $5::task_urgency,
-                          acceptance_criteria = $6, evaluation_criteria = $7,
-                          comment = $8, closed_at = $9
-         WHERE id = $10
-         RETURNING id, task_number, title, description, assigned_by, tester_id,
-                   status, urgency, created_at, closed_at, acceptance_criteria,
-                   evaluation_criteria, comment",
+    history_handler::record_task_event(
+        &mut tx,
+        id,
+        auth.user_id,
+        "deleted",
+        Some("false".to_string()),
+        Some("true".to_string()),
     )
-    .bind(&new_title)
-    .bind(&new_description)
-    .bind(new_tester_id)
-    .bind(&status_str)
-    .bind(&urgency_str)
-    .bind(&new_acceptance)
-    .bind(&new_evaluation)
-    .bind(&new_comment)
-    .bind(closed_at)
-    .bind(id)
-    .fetch_one(&state.db)
     .await?;
 
-    let assigned_by_name = fetch_user_name(&state.db, task.assigned_by).await;
-    let tester_name = match task.tester_id {
-        Some(tid) => fetch_user_name(&state.db, tid).await,
-        None => None,
-    };
+    tx.commit().await?;
 
-    Ok(Json(task_to_response(task, assigned_by_name, tester_name)))
+    Ok(axum::http::StatusCode::NO_CONTENT)
 }
 
-/// Delete a task (manager or the person who created it)
+/// Restore a soft-deleted task (manager or the person who created it)
 #[utoipa::path(
-    delete,
-    path = "/api/tasks/{id}",
+    post,
+    path = "/api/tasks/{id}/restore",
     params(("id" = Uuid, Path, description = "Task ID")),
     responses(
-        (status = 204, description = "Task deleted"),
-        (status = 404, description = "Task not found"),
-        (status = 403, description = "Forbidden")
+        (status = 200, description = "Task restored", body = TaskResponse),
+        (status = 404, description = "Task not found or not visible to the caller"),
+        (status = 403, description = "Forbidden"),
+        (status = 409, description = "Task is not deleted")
     ),
     security(("bearer_auth" = [])),
     tag = "Tasks"
 )]
-pub async fn delete_task(
+pub async fn restore_task(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<axum::http::StatusCode, AppError> {
-    if auth.is_admin() {
+) -> Result<Json<TaskResponse>, AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
         return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
             "Administrators cannot manage tasks".to_string(),
         ));
     }
 
-    let task: Task = sqlx::query_as(
-        "SELECT id, task_number, title, description, assigned_by, tester_id,
-                status, urgency, created_at, closed_at, acceptance_criteria,
-                evaluation_criteria, comment
-         FROM tasks WHERE id = $1",
-    )
+    let mut tx = state.db.begin().await?;
+
+    let task: Task = sqlx::query_as(&format!(
+        "SELECT {} FROM tasks t
+         WHERE t.id = $1 AND ($2::uuid IS NULL OR t.tester_id = $2 OR t.assigned_by = $2)
+         FOR UPDATE",
+        TASK_COLUMNS
+    ))
     .bind(id)
-    .fetch_optional(&state.db)
+    .bind(visibility_scope(&auth))
+    .fetch_optional(&mut *tx)
     .await?
-    .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()))?;
 
-    if task.assigned_by != auth.user_id && !auth.is_manager() {
+    // Admins only get this far with ADMINS_CAN_MANAGE_TASKS, and then act as managers
+    if task.assigned_by != auth.user_id && !auth.is_manager() && !auth.is_admin() {
         return Err(AppError::Forbidden(
-            "Only the task creator or a manager can delete tasks".to_string(),
+            codes::NOT_TASK_CREATOR,
+            "Only the task creator or a manager can restore tasks".to_string(),
         ));
     }
 
-    sqlx::query("DELETE FROM tasks WHERE id = $1")
-        .bind(id)
-        .execute(&state.db)
-        .await?;
+    if task.deleted_at.is_none() {
+        return Err(AppError::Conflict(codes::TASK_NOT_DELETED, "Task is not deleted".to_string()));
+    }
 
-    Ok(axum::http::StatusCode::NO_CONTENT)
+    let restored: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             UPDATE tasks SET deleted_at = NULL, updated_at = NOW() WHERE id = $1
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        TASK_WITH_NAMES_COLUMNS, TASK_NAME_JOINS
+    ))
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    history_handler::record_task_event(
+        &mut tx,
+        id,
+        auth.user_id,
+        "deleted",
+        Some("true".to_string()),
+        Some("false".to_string()),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(task_for_caller(&state, &auth, task_to_response(restored, &state.config)).await?))
 }
 
 /// Get employee statistics (manager/admin only)
 #[utoipa::path(
     get,
     path = "/api/statistics/employees",
+    params(
+        ("from" = Option<NaiveDateTime>, Query, description = "Range start (e.g. 2026-07-01T00:00:00)"),
+        ("to" = Option<NaiveDateTime>, Query, description = "Range end, inclusive"),
+        ("team_id" = Option<Uuid>, Query, description = "Only members of this team"),
+        ("role" = Option<UserRole>, Query, description = "Only users with this role, e.g. tester"),
+        ("active_only" = Option<bool>, Query, description = "Leave out deactivated users"),
+        ("exclude_empty" = Option<bool>, Query, description = "Leave out users without tasks in the range")
+    ),
     responses(
         (status = 200, description = "Employee statistics", body = Vec<EmployeeStats>),
+        (status = 400, description = "from is after to"),
         (status = 403, description = "Forbidden - managers only")
     ),
     security(("bearer_auth" = [])),
@@ -368,37 +2175,497 @@ pub async fn delete_task(
 pub async fn get_employee_stats(
     State(state): State<AppState>,
     auth: AuthUser,
+    Query(params): Query<EmployeeStatsParams>,
 ) -> Result<Json<Vec<EmployeeStats>>, AppError> {
+    ensure_employee_stats_params(&auth, &params)?;
+
+    let rows: Vec<EmployeeStatsRow> = sqlx::query_as(EMPLOYEE_STATS_QUERY)
+        .bind(params.from)
+        .bind(params.to)
+        .bind(params.team_id)
+        .bind(&params.role)
+        .bind(params.active_only.unwrap_or(false))
+        .bind(params.exclude_empty.unwrap_or(false))
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(rows.into_iter().map(employee_stats_from_row).collect()))
+}
+
+/// Export employee statistics as CSV, one row per employee (manager/admin only)
+#[utoipa::path(
+    get,
+    path = "/api/statistics/employees/export",
+    params(
+        ("from" = Option<NaiveDateTime>, Query, description = "Range start (e.g. 2026-07-01T00:00:00)"),
+        ("to" = Option<NaiveDateTime>, Query, description = "Range end, inclusive"),
+        ("team_id" = Option<Uuid>, Query, description = "Only members of this team"),
+        ("role" = Option<UserRole>, Query, description = "Only users with this role, e.g. tester"),
+        ("active_only" = Option<bool>, Query, description = "Leave out deactivated users"),
+        ("exclude_empty" = Option<bool>, Query, description = "Leave out users without tasks in the range")
+    ),
+    responses(
+        (status = 200, description = "CSV file", content_type = "text/csv", body = String),
+        (status = 400, description = "from is after to"),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Statistics"
+)]
+pub async fn export_employee_stats(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<EmployeeStatsParams>,
+) -> Result<Response, AppError> {
+    ensure_employee_stats_params(&auth, &params)?;
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<ExportChunk>(32);
+    let mut db_tx = export::unbounded_transaction(&state.db).await?;
+
+    tokio::spawn(async move {
+        let header = csv_record([
+            "user_id",
+            "full_name",
+            "total_tasks",
+            "completed_tasks",
+            "in_progress_tasks",
+            "avg_completion_hours",
+        ]);
+        if tx.send(Ok(header)).await.is_err() {
+            return;
+        }
+
+        let mut rows = sqlx::query_as::<_, EmployeeStatsRow>(EMPLOYEE_STATS_QUERY)
+            .bind(params.from)
+            .bind(params.to)
+            .bind(params.team_id)
+            .bind(&params.role)
+            .bind(params.active_only.unwrap_or(false))
+            .bind(params.exclude_empty.unwrap_or(false))
+            .fetch(&mut *db_tx);
+
+        while let Some(row) = rows.next().await {
+            let chunk = match row {
+                Ok(row) => {
+                    let e = employee_stats_from_row(row);
+                    Ok(csv_record([
+                        e.user_id.to_string(),
+                        e.full_name,
+                        e.total_tasks.to_string(),
+                        e.completed_tasks.to_string(),
+                        e.in_progress_tasks.to_string(),
+                        e.avg_completion_hours
+                            .map(|h| format!("{:.2}", h))
+                            .unwrap_or_default(),
+                    ]))
+                }
+                Err(e) => {
+                    tracing::error!("Employee statistics export failed: {:?}", e);
+                    Err(std::io::Error::other("employee statistics export failed"))
+                }
+            };
+            let failed = chunk.is_err();
+            // Receiver gone means the client disconnected
+            if tx.send(chunk).await.is_err() || failed {
+                return;
+            }
+        }
+    });
+
+    Ok(csv_response("employee_stats", rx))
+}
+
+/// Get open and finished task counts per urgency (managers/admins only)
+#[utoipa::path(
+    get,
+    path = "/api/statistics/urgency",
+    responses(
+        (status = 200, description = "Task counts by urgency and status, low to critical", body = Vec<UrgencyBreakdown>),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Statistics"
+)]
+pub async fn get_urgency_stats(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<UrgencyBreakdown>>, AppError> {
     if auth.role != UserRole::Manager && auth.role != UserRole::Admin {
         return Err(AppError::Forbidden(
+            codes::MANAGER_ONLY,
             "Only managers and admins can view statistics".to_string(),
         ));
     }
 
-    let rows: Vec<(Uuid, String, Option<i64>, Option<i64>, Option<i64>)> = sqlx::query_as(
-        "SELECT u.id, u.full_name,
-                COUNT(t.id) as total_tasks,
-                COUNT(t.id) FILTER (WHERE t.status::text IN ('done', 'closed')) as completed_tasks,
-                COUNT(t.id) FILTER (WHERE t.status::text = 'in_progress') as in_progress_tasks
-         FROM users u
-         LEFT JOIN tasks t ON t.tester_id = u.id
-         WHERE u.role::text != 'admin'
-         GROUP BY u.id, u.full_name
-         ORDER BY u.full_name",
+    let rows: Vec<(TaskUrgency, TaskStatus, i64)> = sqlx::query_as(
+        "SELECT urgency, status, COUNT(*)
+         FROM tasks
+         WHERE deleted_at IS NULL
+         GROUP BY urgency, status",
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    // Every urgency level is reported, even without tasks
+    let mut response: Vec<UrgencyBreakdown> = [
+        TaskUrgency::Low,
+        TaskUrgency::Medium,
+        TaskUrgency::High,
+        TaskUrgency::Critical,
+    ]
+    .into_iter()
+    .map(|urgency| UrgencyBreakdown {
+        urgency,
+        new: 0,
+        in_progress: 0,
+        testing: 0,
+        done: 0,
+        closed: 0,
+    })
+    .collect();
+
+    for (urgency, status, count) in rows {
+        let Some(entry) = response.iter_mut().find(|b| b.urgency == urgency) else {
+            continue;
+        };
+        match status {
+            TaskStatus::New => entry.new = count,
+            TaskStatus::InProgress => entry.in_progress = count,
+            TaskStatus::Testing => entry.testing = count,
+            TaskStatus::Done => entry.done = count,
+            TaskStatus::Closed => entry.closed = count,
+        }
+    }
+
+    Ok(Json(response))
+}
+
+/// Most buckets one time series may span
+const MAX_TIMESERIES_BUCKETS: i64 = 366;
+
+/// Get tasks created and finished per day or week, for burndown and throughput charts
+/// (managers/admins only)
+#[utoipa::path(
+    get,
+    path = "/api/statistics/timeseries",
+    params(
+        ("from" = Option<NaiveDateTime>, Query, description = "Range start (default: 30 days before to)"),
+        ("to" = Option<NaiveDateTime>, Query, description = "Range end, inclusive (default: now)"),
+        ("bucket" = Option<TimeBucket>, Query, description = "day (default) or week")
+    ),
+    responses(
+        (status = 200, description = "One point per bucket, oldest first; empty buckets are zero", body = Vec<TimeseriesPoint>),
+        (status = 400, description = "from is after to, or the range has too many buckets"),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Statistics"
+)]
+pub async fn get_timeseries_stats(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<TimeseriesParams>,
+) -> Result<Json<Vec<TimeseriesPoint>>, AppError> {
+    if auth.role != UserRole::Manager && auth.role != UserRole::Admin {
+        return Err(AppError::Forbidden(
+            codes::MANAGER_ONLY,
+            "Only managers and admins can view statistics".to_string(),
+        ));
+    }
+
+    let to = params.to.unwrap_or_else(|| chrono::Utc::now().naive_utc());
+    let from = params.from.unwrap_or(to - chrono::Duration::days(30));
+    if from > to {
+        return Err(AppError::BadRequest(
+            codes::INVALID_DATE_RANGE,
+            "'from' must not be after 'to'".to_string(),
+        ));
+    }
+    let bucket = params.bucket.unwrap_or(TimeBucket::Day);
+    let buckets = match bucket {
+        TimeBucket::Day => (to - from).num_days() + 1,
+        TimeBucket::Week => (to - from).num_weeks() + 1,
+    };
+    if buckets > MAX_TIMESERIES_BUCKETS {
+        return Err(AppError::BadRequest(
+            codes::INVALID_DATE_RANGE,
+            format!(
+                "The range spans more than {} buckets",
+                MAX_TIMESERIES_BUCKETS
+            ),
+        ));
+    }
+
+    // The series supplies every bucket, so ones without tasks come out as zero
+    let rows: Vec<(NaiveDateTime, i64, i64)> = sqlx::query_as(
+        "WITH buckets AS (
+             SELECT generate_series(date_trunc($3, $1), date_trunc($3, $2), ('1 ' || $3)::interval)
+                 AS bucket
+         ),
+         created AS (
+             SELECT date_trunc($3, created_at) AS bucket, COUNT(*) AS n
+             FROM tasks
+             WHERE deleted_at IS NULL AND created_at >= $1 AND created_at <= $2
+             GROUP BY 1
+         ),
+         closed AS (
+             SELECT date_trunc($3, closed_at) AS bucket, COUNT(*) AS n
+             FROM tasks
+             WHERE deleted_at IS NULL AND closed_at >= $1 AND closed_at <= $2
+             GROUP BY 1
+         )
+         SELECT b.bucket, COALESCE(c.n, 0), COALESCE(d.n, 0)
+         FROM buckets b
+         LEFT JOIN created c ON c.bucket = b.bucket
+         LEFT JOIN closed d ON d.bucket = b.bucket
+         ORDER BY b.bucket",
     )
+    .bind(from)
+    .bind(to)
+    .bind(bucket.as_str())
     .fetch_all(&state.db)
     .await?;
 
-    let response: Vec<EmployeeStats> = rows
+    let response = rows
         .into_iter()
-        .map(|(user_id, full_name, total, completed, in_progress)| EmployeeStats {
-            user_id,
-            full_name,
-            total_tasks: total.unwrap_or(0),
-            completed_tasks: completed.unwrap_or(0),
-            in_progress_tasks: in_progress.unwrap_or(0),
+        .map(|(bucket_start, created, closed)| TimeseriesPoint {
+            bucket_start: bucket_start.format("%Y-%m-%d").to_string(),
+            created,
+            closed,
         })
         .collect();
 
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, create_task, create_user};
+    use serde_json::json;
+    use sqlx::PgPool;
+
+    async fn update(state: &AppState, auth: &AuthUser, id: Uuid, body: serde_json::Value) {
+        let _ = update_task(
+            State(state.clone()),
+            auth.clone(),
+            Path(id),
+            HeaderMap::new(),
+            Json(serde_json::from_value(body).unwrap()),
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn closed_at(db: &PgPool, id: Uuid) -> Option<NaiveDateTime> {
+        sqlx::query_scalar("SELECT closed_at FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_one(db)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn closed_at_is_stamped_on_entry_kept_while_finished_and_cleared_on_reopen() {
+        use TaskStatus::*;
+        let earlier = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        assert!(closed_at_after(&Testing, None, &Done).is_some());
+        assert_eq!(closed_at_after(&Done, Some(earlier), &Done), Some(earlier));
+        assert_eq!(closed_at_after(&Done, Some(earlier), &Closed), Some(earlier));
+        assert_eq!(closed_at_after(&Done, Some(earlier), &InProgress), None);
+        assert_eq!(closed_at_after(&Closed, Some(earlier), &InProgress), None);
+        assert_eq!(closed_at_after(&Testing, None, &InProgress), None);
+        // Rows finished before closed_at existed get stamped on their next transition
+        assert!(closed_at_after(&Done, None, &Closed).is_some());
+    }
+
+    #[sqlx::test]
+    async fn close_then_reopen_clears_closed_at(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let auth = test_support::auth(manager, UserRole::Manager);
+        let id = create_task(&pool, manager, None).await;
+
+        for status in ["in_progress", "testing", "done"] {
+            update(&state, &auth, id, json!({ "status": status })).await;
+        }
+        let finished = closed_at(&pool, id).await;
+        assert!(finished.is_some());
+
+        update(&state, &auth, id, json!({ "title": "Renamed" })).await;
+        assert_eq!(closed_at(&pool, id).await, finished);
+        update(&state, &auth, id, json!({ "status": "closed" })).await;
+        assert_eq!(closed_at(&pool, id).await, finished);
+
+        update(&state, &auth, id, json!({ "status": "in_progress" })).await;
+        assert_eq!(closed_at(&pool, id).await, None);
+    }
+
+    #[sqlx::test]
+    async fn bulk_close_keeps_the_done_timestamp(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let auth = test_support::auth(manager, UserRole::Manager);
+        let id = create_task(&pool, manager, None).await;
+
+        for status in ["in_progress", "testing", "done"] {
+            update(&state, &auth, id, json!({ "status": status })).await;
+        }
+        let finished = closed_at(&pool, id).await;
+
+        let request = json!({ "ids": [id], "status": "closed" });
+        let Json(results) =
+            bulk_update_status(State(state), auth, Json(serde_json::from_value(request).unwrap()))
+                .await
+                .unwrap();
+        assert!(results[0].success);
+        assert_eq!(closed_at(&pool, id).await, finished);
+    }
+
+    #[sqlx::test]
+    async fn tester_cannot_touch_a_foreign_task(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        let other = create_user(&pool, "other", UserRole::Tester).await;
+        let own = create_task(&pool, manager, Some(tester)).await;
+        let foreign = create_task(&pool, manager, Some(other)).await;
+        let auth = test_support::auth(tester, UserRole::Tester);
+
+        let request = json!({ "ids": [own, foreign], "status": "in_progress" });
+        let Json(results) = bulk_update_status(
+            State(state.clone()),
+            auth.clone(),
+            Json(serde_json::from_value(request).unwrap()),
+        )
+        .await
+        .unwrap();
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert_eq!(results[1].reason.as_deref(), Some("Task not found"));
+
+        let status: TaskStatus = sqlx::query_scalar("SELECT status FROM tasks WHERE id = $1")
+            .bind(foreign)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(status, TaskStatus::New);
+
+        let request = json!({ "status": "in_progress" });
+        let err = update_task(
+            State(state.clone()),
+            auth.clone(),
+            Path(foreign),
+            HeaderMap::new(),
+            Json(serde_json::from_value(request).unwrap()),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+
+        let err = get_task(State(state), auth, Path(foreign), HeaderMap::new())
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, AppError::NotFound(codes::TASK_NOT_FOUND, _)));
+    }
+
+    #[sqlx::test]
+    async fn tester_must_exist_and_be_a_tester(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let developer = create_user(&pool, "dev", UserRole::Developer).await;
+        let auth = test_support::auth(manager, UserRole::Manager);
+
+        let create = |tester_id: Uuid| {
+            let request = json!({ "title": "Task", "tester_id": tester_id });
+            super::create_task(
+                State(state.clone()),
+                auth.clone(),
+                IdempotencyKey(None),
+                Json(serde_json::from_value(request).unwrap()),
+            )
+        };
+        let err = create(Uuid::new_v4()).await.err().unwrap();
+        assert!(matches!(err, AppError::BadRequest(codes::USER_NOT_FOUND, _)));
+        let err = create(developer).await.err().unwrap();
+        assert!(
+            matches!(err, AppError::BadRequest(_, ref msg) if msg.contains("only testers"))
+        );
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+
+        let id = create_task(&pool, manager, None).await;
+        let update = |tester_id: Uuid| {
+            let request = json!({ "tester_id": tester_id });
+            update_task(
+                State(state.clone()),
+                auth.clone(),
+                Path(id),
+                HeaderMap::new(),
+                Json(serde_json::from_value(request).unwrap()),
+            )
+        };
+        let err = update(Uuid::new_v4()).await.err().unwrap();
+        assert!(matches!(err, AppError::BadRequest(codes::USER_NOT_FOUND, _)));
+        let err = update(developer).await.err().unwrap();
+        assert!(matches!(err, AppError::BadRequest(codes::BAD_REQUEST, _)));
+
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        assert!(update(tester).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn null_clears_a_field_and_absence_keeps_it(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let manager = create_user(&pool, "mgr", UserRole::Manager).await;
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        let auth = test_support::auth(manager, UserRole::Manager);
+        let id = create_task(&pool, manager, Some(tester)).await;
+        sqlx::query(
+            "UPDATE tasks SET description = 'd', acceptance_criteria = 'a',
+                              evaluation_criteria = 'e', comment = 'c'
+             WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        type Nullable =
+            (Option<String>, Option<Uuid>, Option<String>, Option<String>, Option<String>);
+        let fields = async || -> Nullable {
+            sqlx::query_as(
+                "SELECT description, tester_id, acceptance_criteria, evaluation_criteria, comment
+                 FROM tasks WHERE id = $1",
+            )
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        };
+        let set = |s: &str| Some(s.to_string());
+
+        update(&state, &auth, id, json!({ "title": "Renamed" })).await;
+        assert_eq!(fields().await, (set("d"), Some(tester), set("a"), set("e"), set("c")));
+
+        update(&state, &auth, id, json!({ "description": null, "comment": null })).await;
+        assert_eq!(fields().await, (None, Some(tester), set("a"), set("e"), None));
+
+        let request = json!({
+            "tester_id": null,
+            "acceptance_criteria": null,
+            "evaluation_criteria": null,
+        });
+        update(&state, &auth, id, request).await;
+        assert_eq!(fields().await, (None, None, None, None, None));
+    }
+}
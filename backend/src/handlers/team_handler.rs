@@ -0,0 +1,320 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::AuthUser;
+use crate::dto::{AddTeamMemberRequest, CreateTeamRequest, TeamResponse, UpdateTeamRequest};
+use crate::errors::{codes, AppError};
+use crate::handlers::{created, Created};
+use crate::models::Team;
+use crate::AppState;
+
+/// Team columns with member ids, over a `tm` relation
+const TEAM_COLUMNS: &str = "tm.id, tm.name, tm.description,
+                ARRAY(SELECT m.user_id FROM team_members m WHERE m.team_id = tm.id
+                      ORDER BY m.created_at) AS member_ids,
+                tm.created_at, tm.updated_at";
+
+fn require_admin(auth: &AuthUser) -> Result<(), AppError> {
+    if !auth.is_admin() {
+        return Err(AppError::Forbidden(
+            codes::ADMIN_ONLY,
+            "Only administrators can manage teams".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn team_to_response(t: Team) -> TeamResponse {
+    TeamResponse {
+        id: t.id,
+        name: t.name,
+        description: t.description,
+        member_ids: t.member_ids,
+        created_at: t.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        updated_at: t.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+async fn fetch_team(db: &sqlx::PgPool, id: Uuid) -> Result<Team, AppError> {
+    sqlx::query_as(&format!("SELECT {} FROM teams tm WHERE tm.id = $1", TEAM_COLUMNS))
+        .bind(id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(codes::TEAM_NOT_FOUND, "Team not found".to_string()))
+}
+
+/// Team names are unique regardless of case
+async fn ensure_name_free(
+    db: &sqlx::PgPool,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<(), AppError> {
+    let taken: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM teams
+                       WHERE LOWER(name) = LOWER($1) AND ($2::uuid IS NULL OR id <> $2))",
+    )
+    .bind(name)
+    .bind(except)
+    .fetch_one(db)
+    .await?;
+
+    if taken {
+        return Err(AppError::Conflict(
+            codes::TEAM_NAME_TAKEN,
+            "Team name already exists".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Get all teams
+#[utoipa::path(
+    get,
+    path = "/api/teams",
+    responses(
+        (status = 200, description = "List of teams", body = Vec<TeamResponse>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Teams"
+)]
+pub async fn get_teams(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+) -> Result<Json<Vec<TeamResponse>>, AppError> {
+    let teams: Vec<Team> = sqlx::query_as(&format!(
+        "SELECT {} FROM teams tm ORDER BY tm.name",
+        TEAM_COLUMNS
+    ))
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(teams.into_iter().map(team_to_response).collect()))
+}
+
+/// Get a team with its members
+#[utoipa::path(
+    get,
+    path = "/api/teams/{id}",
+    params(("id" = Uuid, Path, description = "Team ID")),
+    responses(
+        (status = 200, description = "Team details", body = TeamResponse),
+        (status = 404, description = "Team not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Teams"
+)]
+pub async fn get_team(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TeamResponse>, AppError> {
+    Ok(Json(team_to_response(fetch_team(&state.db, id).await?)))
+}
+
+/// Create a team (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/teams",
+    request_body = CreateTeamRequest,
+    responses(
+        (status = 201, description = "Team created", body = TeamResponse,
+            headers(("Location" = String, description = "URL of the team"))),
+        (status = 400, description = "Validation error"),
+        (status = 409, description = "Team name already exists"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Teams"
+)]
+pub async fn create_team(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateTeamRequest>,
+) -> Result<Created<TeamResponse>, AppError> {
+    require_admin(&auth)?;
+    payload.validate()?;
+    ensure_name_free(&state.db, &payload.name, None).await?;
+
+    let team: Team = sqlx::query_as(&format!(
+        "WITH tm AS (
+             INSERT INTO teams (name, description) VALUES ($1, $2)
+             RETURNING *
+         )
+         SELECT {} FROM tm",
+        TEAM_COLUMNS
+    ))
+    .bind(payload.name.trim())
+    .bind(&payload.description)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(created(format!("/api/teams/{}", team.id), team_to_response(team)))
+}
+
+/// Update a team's name or description (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/teams/{id}",
+    params(("id" = Uuid, Path, description = "Team ID")),
+    request_body = UpdateTeamRequest,
+    responses(
+        (status = 200, description = "Team updated", body = TeamResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Team not found"),
+        (status = 409, description = "Team name already exists"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Teams"
+)]
+pub async fn update_team(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateTeamRequest>,
+) -> Result<Json<TeamResponse>, AppError> {
+    require_admin(&auth)?;
+    payload.validate()?;
+    if let Some(name) = &payload.name {
+        ensure_name_free(&state.db, name, Some(id)).await?;
+    }
+
+    let team: Team = sqlx::query_as(&format!(
+        "WITH tm AS (
+             UPDATE teams SET name = COALESCE($1, name),
+                              description = COALESCE($2, description),
+                              updated_at = NOW()
+             WHERE id = $3
+             RETURNING *
+         )
+         SELECT {} FROM tm",
+        TEAM_COLUMNS
+    ))
+    .bind(payload.name.as_deref().map(str::trim))
+    .bind(&payload.description)
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::TEAM_NOT_FOUND, "Team not found".to_string()))?;
+
+    Ok(Json(team_to_response(team)))
+}
+
+/// Delete a team; its members are kept (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/teams/{id}",
+    params(("id" = Uuid, Path, description = "Team ID")),
+    responses(
+        (status = 204, description = "Team deleted"),
+        (status = 404, description = "Team not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Teams"
+)]
+pub async fn delete_team(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<axum::http::StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM teams WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(codes::TEAM_NOT_FOUND, "Team not found".to_string()));
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Add a user to a team (adding an existing member is a no-op; admin only)
+#[utoipa::path(
+    post,
+    path = "/api/teams/{id}/members",
+    params(("id" = Uuid, Path, description = "Team ID")),
+    request_body = AddTeamMemberRequest,
+    responses(
+        (status = 200, description = "Team with the new member", body = TeamResponse),
+        (status = 400, description = "User not found"),
+        (status = 404, description = "Team not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Teams"
+)]
+pub async fn add_team_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AddTeamMemberRequest>,
+) -> Result<Json<TeamResponse>, AppError> {
+    require_admin(&auth)?;
+    fetch_team(&state.db, id).await?;
+
+    let user_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+        .bind(payload.user_id)
+        .fetch_one(&state.db)
+        .await?;
+    if !user_exists {
+        return Err(AppError::BadRequest(codes::USER_NOT_FOUND, "User not found".to_string()));
+    }
+
+    sqlx::query(
+        "INSERT INTO team_members (team_id, user_id) VALUES ($1, $2)
+         ON CONFLICT (team_id, user_id) DO NOTHING",
+    )
+    .bind(id)
+    .bind(payload.user_id)
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(team_to_response(fetch_team(&state.db, id).await?)))
+}
+
+/// Remove a user from a team (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/teams/{id}/members/{user_id}",
+    params(
+        ("id" = Uuid, Path, description = "Team ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Member removed"),
+        (status = 404, description = "User is not a member of the team"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Teams"
+)]
+pub async fn remove_team_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<axum::http::StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM team_members WHERE team_id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            codes::NOT_FOUND,
+            "User is not a member of this team".to_string(),
+        ));
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,346 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::AuthUser;
+use crate::dto::{CreateTemplateRequest, TaskResponse, TemplateResponse, UpdateTemplateRequest};
+use crate::errors::{codes, AppError};
+use crate::handlers::{created, task_handler, watcher_handler, Created};
+use crate::models::{TaskTemplate, TaskUrgency, TaskWithNames, TemplateRecurrence, UserRole};
+use crate::AppState;
+
+const TEMPLATE_COLUMNS: &str = "id, title, description, urgency, acceptance_criteria,
+                evaluation_criteria, tester_id, created_by, recurrence, next_run_at,
+                created_at, updated_at";
+
+fn require_manager(auth: &AuthUser) -> Result<(), AppError> {
+    if auth.role != UserRole::Manager && auth.role != UserRole::Admin {
+        return Err(AppError::Forbidden(
+            codes::MANAGER_ONLY,
+            "Only managers and admins can manage task templates".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn template_to_response(t: TaskTemplate) -> TemplateResponse {
+    TemplateResponse {
+        id: t.id,
+        title: t.title,
+        description: t.description,
+        urgency: t.urgency,
+        acceptance_criteria: t.acceptance_criteria,
+        evaluation_criteria: t.evaluation_criteria,
+        tester_id: t.tester_id,
+        created_by: t.created_by,
+        recurrence: t.recurrence,
+        next_run_at: t.next_run_at.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+        created_at: t.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        updated_at: t.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+async fn fetch_template(db: &sqlx::PgPool, id: Uuid) -> Result<TaskTemplate, AppError> {
+    sqlx::query_as(&format!("SELECT {} FROM task_templates WHERE id = $1", TEMPLATE_COLUMNS))
+        .bind(id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(codes::TEMPLATE_NOT_FOUND, "Task template not found".to_string())
+        })
+}
+
+/// A schedule needs a cadence; without an explicit first run it starts right away
+fn schedule(
+    recurrence: Option<TemplateRecurrence>,
+    next_run_at: Option<NaiveDateTime>,
+) -> Result<Option<NaiveDateTime>, AppError> {
+    match recurrence {
+        Some(_) => Ok(Some(next_run_at.unwrap_or_else(|| chrono::Utc::now().naive_utc()))),
+        None if next_run_at.is_some() => Err(AppError::BadRequest(
+            codes::RECURRENCE_REQUIRED,
+            "next_run_at requires a recurrence".to_string(),
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Creates a task from a template with status `new`, authored by `author_id`, with the same
+/// watchers, notifications and events as `POST /api/tasks`. A tester who is no longer one is
+/// left out rather than failing every scheduled run.
+pub async fn instantiate(
+    state: &AppState,
+    template_id: Uuid,
+    author_id: Uuid,
+) -> Result<TaskResponse, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let task: TaskWithNames = sqlx::query_as(&format!(
+        "WITH t AS (
+             INSERT INTO tasks (title, description, assigned_by, tester_id, urgency,
+                                acceptance_criteria, evaluation_criteria)
+             SELECT tt.title, tt.description, $2,
+                    (SELECT u.id FROM users u WHERE u.id = tt.tester_id AND u.role = 'tester'),
+                    tt.urgency, tt.acceptance_criteria, tt.evaluation_criteria
+             FROM task_templates tt
+             WHERE tt.id = $1
+             RETURNING *
+         )
+         SELECT {} FROM t {}",
+        task_handler::TASK_WITH_NAMES_COLUMNS,
+        task_handler::TASK_NAME_JOINS
+    ))
+    .bind(template_id)
+    .bind(author_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(codes::TEMPLATE_NOT_FOUND, "Task template not found".to_string())
+    })?;
+
+    watcher_handler::add_watchers(&mut tx, task.task.id, &task_handler::participants(&task.task))
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(task_handler::announce_created(state, author_id, task).await)
+}
+
+/// List task templates (managers/admins)
+#[utoipa::path(
+    get,
+    path = "/api/task-templates",
+    responses(
+        (status = 200, description = "All task templates by title", body = Vec<TemplateResponse>),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Task templates"
+)]
+pub async fn get_templates(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<TemplateResponse>>, AppError> {
+    require_manager(&auth)?;
+
+    let templates: Vec<TaskTemplate> = sqlx::query_as(&format!(
+        "SELECT {} FROM task_templates ORDER BY title, created_at",
+        TEMPLATE_COLUMNS
+    ))
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(templates.into_iter().map(template_to_response).collect()))
+}
+
+/// Get a task template (managers/admins)
+#[utoipa::path(
+    get,
+    path = "/api/task-templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 200, description = "Task template", body = TemplateResponse),
+        (status = 404, description = "Task template not found"),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Task templates"
+)]
+pub async fn get_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TemplateResponse>, AppError> {
+    require_manager(&auth)?;
+    Ok(Json(template_to_response(fetch_template(&state.db, id).await?)))
+}
+
+/// Create a task template, optionally instantiated on a schedule (managers/admins)
+#[utoipa::path(
+    post,
+    path = "/api/task-templates",
+    request_body = CreateTemplateRequest,
+    responses(
+        (status = 201, description = "Template created", body = TemplateResponse,
+            headers(("Location" = String, description = "URL of the template"))),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Task templates"
+)]
+pub async fn create_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateTemplateRequest>,
+) -> Result<Created<TemplateResponse>, AppError> {
+    require_manager(&auth)?;
+    payload.validate()?;
+    let next_run_at = schedule(payload.recurrence, payload.next_run_at)?;
+    if let Some(tester_id) = payload.tester_id {
+        task_handler::ensure_tester(&mut *state.db.acquire().await?, tester_id).await?;
+    }
+
+    let template: TaskTemplate = sqlx::query_as(&format!(
+        "INSERT INTO task_templates (title, description, urgency, acceptance_criteria,
+                                     evaluation_criteria, tester_id, created_by, recurrence,
+                                     next_run_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+         RETURNING {}",
+        TEMPLATE_COLUMNS
+    ))
+    .bind(&payload.title)
+    .bind(&payload.description)
+    .bind(payload.urgency.unwrap_or(TaskUrgency::Medium))
+    .bind(&payload.acceptance_criteria)
+    .bind(&payload.evaluation_criteria)
+    .bind(payload.tester_id)
+    .bind(auth.user_id)
+    .bind(payload.recurrence)
+    .bind(next_run_at)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(created(
+        format!("/api/task-templates/{}", template.id),
+        template_to_response(template),
+    ))
+}
+
+/// Update a task template; omitted fields are kept (managers/admins)
+#[utoipa::path(
+    put,
+    path = "/api/task-templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    request_body = UpdateTemplateRequest,
+    responses(
+        (status = 200, description = "Template updated", body = TemplateResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Task template not found"),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Task templates"
+)]
+pub async fn update_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateTemplateRequest>,
+) -> Result<Json<TemplateResponse>, AppError> {
+    require_manager(&auth)?;
+    payload.validate()?;
+
+    let existing = fetch_template(&state.db, id).await?;
+
+    let tester_id = payload.tester_id.unwrap_or(existing.tester_id);
+    if let Some(tester_id) = tester_id.filter(|t| Some(*t) != existing.tester_id) {
+        task_handler::ensure_tester(&mut *state.db.acquire().await?, tester_id).await?;
+    }
+    let recurrence = payload.recurrence.unwrap_or(existing.recurrence);
+    // A schedule that is kept keeps its next run unless a new one is given
+    let next_run_at = match payload.next_run_at {
+        Some(next_run_at) => schedule(recurrence, next_run_at)?,
+        None => schedule(recurrence, existing.next_run_at.filter(|_| recurrence.is_some()))?,
+    };
+
+    let template: TaskTemplate = sqlx::query_as(&format!(
+        "UPDATE task_templates
+         SET title = $1, description = $2, urgency = $3, acceptance_criteria = $4,
+             evaluation_criteria = $5, tester_id = $6, recurrence = $7, next_run_at = $8,
+             updated_at = NOW()
+         WHERE id = $9
+         RETURNING {}",
+        TEMPLATE_COLUMNS
+    ))
+    .bind(payload.title.unwrap_or(existing.title))
+    .bind(payload.description.unwrap_or(existing.description))
+    .bind(payload.urgency.unwrap_or(existing.urgency))
+    .bind(payload.acceptance_criteria.unwrap_or(existing.acceptance_criteria))
+    .bind(payload.evaluation_criteria.unwrap_or(existing.evaluation_criteria))
+    .bind(tester_id)
+    .bind(recurrence)
+    .bind(next_run_at)
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(codes::TEMPLATE_NOT_FOUND, "Task template not found".to_string())
+    })?;
+
+    Ok(Json(template_to_response(template)))
+}
+
+/// Delete a task template; tasks created from it are kept (managers/admins)
+#[utoipa::path(
+    delete,
+    path = "/api/task-templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 204, description = "Template deleted"),
+        (status = 404, description = "Task template not found"),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Task templates"
+)]
+pub async fn delete_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<axum::http::StatusCode, AppError> {
+    require_manager(&auth)?;
+
+    let result = sqlx::query("DELETE FROM task_templates WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            codes::TEMPLATE_NOT_FOUND,
+            "Task template not found".to_string(),
+        ));
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Create a task from a template now, authored by the caller (managers/admins)
+#[utoipa::path(
+    post,
+    path = "/api/task-templates/{id}/instantiate",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 201, description = "Task created with status new", body = TaskResponse,
+            headers(("Location" = String, description = "URL of the task"))),
+        (status = 404, description = "Task template not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Task templates"
+)]
+pub async fn instantiate_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Created<TaskResponse>, AppError> {
+    require_manager(&auth)?;
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot create tasks".to_string(),
+        ));
+    }
+
+    let response = instantiate(&state, id, auth.user_id).await?;
+
+    Ok(created(
+        format!("/api/tasks/{}", response.id),
+        task_handler::task_for_caller(&state, &auth, response).await?,
+    ))
+}
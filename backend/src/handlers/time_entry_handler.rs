@@ -0,0 +1,196 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::AuthUser;
+use crate::dto::{EmployeeStatsParams, EmployeeTimeStats, LogTimeRequest, TimeEntryResponse};
+use crate::errors::{codes, AppError};
+use crate::handlers::watcher_handler::ensure_task_visible;
+use crate::models::{TimeEntry, UserRole};
+use crate::AppState;
+
+pub fn time_entry_to_response(e: TimeEntry) -> TimeEntryResponse {
+    TimeEntryResponse {
+        id: e.id,
+        task_id: e.task_id,
+        user_id: e.user_id,
+        user_name: e.user_name,
+        hours: e.hours,
+        note: e.note,
+        logged_at: e.logged_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        created_at: e.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+/// Get the time logged on a task, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/time",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Time entries of the task", body = Vec<TimeEntryResponse>),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn get_time_entries(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<TimeEntryResponse>>, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let entries: Vec<TimeEntry> = sqlx::query_as(
+        "SELECT e.id, e.task_id, e.user_id, u.full_name AS user_name, e.hours, e.note,
+                e.logged_at, e.created_at
+         FROM time_entries e
+         LEFT JOIN users u ON u.id = e.user_id
+         WHERE e.task_id = $1
+         ORDER BY e.logged_at, e.id",
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(entries.into_iter().map(time_entry_to_response).collect()))
+}
+
+/// Log time the caller spent on a task
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/time",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    request_body = LogTimeRequest,
+    responses(
+        (status = 201, description = "Time logged", body = TimeEntryResponse),
+        (status = 400, description = "Validation error or logged_at in the future"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn log_time(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<LogTimeRequest>,
+) -> Result<(StatusCode, Json<TimeEntryResponse>), AppError> {
+    if auth.is_admin() && !state.config.admins_can_manage_tasks {
+        return Err(AppError::Forbidden(
+            codes::FORBIDDEN_ADMIN_EDIT,
+            "Administrators cannot edit tasks".to_string(),
+        ));
+    }
+
+    payload.validate()?;
+
+    let now = chrono::Utc::now().naive_utc();
+    if payload.logged_at.is_some_and(|t| t > now) {
+        return Err(AppError::BadRequest(
+            codes::TIME_IN_FUTURE,
+            "Time cannot be logged in the future".to_string(),
+        ));
+    }
+
+    ensure_task_visible(&state, &auth, task_id).await?;
+
+    let entry: TimeEntry = sqlx::query_as(
+        "WITH e AS (
+             INSERT INTO time_entries (task_id, user_id, hours, note, logged_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING *
+         )
+         SELECT e.id, e.task_id, e.user_id, u.full_name AS user_name, e.hours, e.note,
+                e.logged_at, e.created_at
+         FROM e LEFT JOIN users u ON u.id = e.user_id",
+    )
+    .bind(task_id)
+    .bind(auth.user_id)
+    .bind(payload.hours)
+    .bind(&payload.note)
+    .bind(payload.logged_at.unwrap_or(now))
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(time_entry_to_response(entry))))
+}
+
+/// Get hours logged per employee, optionally within a range (manager/admin only)
+#[utoipa::path(
+    get,
+    path = "/api/statistics/time",
+    params(
+        ("from" = Option<NaiveDateTime>, Query, description = "Count time logged at or after this moment"),
+        ("to" = Option<NaiveDateTime>, Query, description = "Range end, inclusive"),
+        ("team_id" = Option<Uuid>, Query, description = "Only members of this team")
+    ),
+    responses(
+        (status = 200, description = "Logged hours per employee", body = Vec<EmployeeTimeStats>),
+        (status = 400, description = "from is after to"),
+        (status = 403, description = "Forbidden - managers only")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Statistics"
+)]
+pub async fn get_time_stats(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<EmployeeStatsParams>,
+) -> Result<Json<Vec<EmployeeTimeStats>>, AppError> {
+    if auth.role != UserRole::Manager && auth.role != UserRole::Admin {
+        return Err(AppError::Forbidden(
+            codes::MANAGER_ONLY,
+            "Only managers and admins can view statistics".to_string(),
+        ));
+    }
+
+    if let (Some(from), Some(to)) = (params.from, params.to)
+        && from > to
+    {
+        return Err(AppError::BadRequest(
+            codes::INVALID_DATE_RANGE,
+            "'from' must not be after 'to'".to_string(),
+        ));
+    }
+
+    // Time on deleted tasks was still spent, so it is counted
+    let rows: Vec<(Uuid, String, f64, i64, i64)> = sqlx::query_as(
+        "SELECT u.id, u.full_name,
+                COALESCE(SUM(e.hours), 0)::float8,
+                COUNT(e.id),
+                COUNT(DISTINCT e.task_id)
+         FROM users u
+         LEFT JOIN time_entries e ON e.user_id = u.id
+              AND ($1::timestamp IS NULL OR e.logged_at >= $1)
+              AND ($2::timestamp IS NULL OR e.logged_at <= $2)
+         WHERE u.role::text != 'admin'
+           AND ($3::uuid IS NULL OR EXISTS (
+                SELECT 1 FROM team_members tm WHERE tm.team_id = $3 AND tm.user_id = u.id))
+         GROUP BY u.id, u.full_name
+         ORDER BY u.full_name",
+    )
+    .bind(params.from)
+    .bind(params.to)
+    .bind(params.team_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let response = rows
+        .into_iter()
+        .map(|(user_id, full_name, hours_logged, entries, tasks)| EmployeeTimeStats {
+            user_id,
+            full_name,
+            hours_logged,
+            entries,
+            tasks,
+        })
+        .collect();
+
+    Ok(Json(response))
+}
@@ -0,0 +1,178 @@
+use axum::{extract::State, Json};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::{hash_opaque_token, AuthUser};
+use crate::dto::{RecoveryCodesResponse, TwoFactorSetupResponse, VerifyTwoFactorRequest};
+use crate::errors::{codes, AppError};
+use crate::totp;
+use crate::AppState;
+
+/// Start 2FA setup: generates a new secret for the caller (not enforced until verified)
+#[utoipa::path(
+    post,
+    path = "/api/users/me/2fa/enable",
+    responses(
+        (status = 200, description = "Secret to add to an authenticator app", body = TwoFactorSetupResponse),
+        (status = 409, description = "2FA is already enabled")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Users"
+)]
+pub async fn enable_two_factor(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<TwoFactorSetupResponse>, AppError> {
+    let (username, totp_enabled): (String, bool) =
+        sqlx::query_as("SELECT username, totp_enabled FROM users WHERE id = $1")
+            .bind(auth.user_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string())
+            })?;
+
+    if totp_enabled {
+        return Err(AppError::Conflict(
+            codes::TWO_FACTOR_ALREADY_ENABLED,
+            "Two-factor authentication is already enabled".to_string(),
+        ));
+    }
+
+    let secret = totp::generate_secret()?;
+    let encrypted = totp::encrypt_secret(&state.config.totp_encryption_key, &secret)?;
+    let totp = totp::build(secret, &state.config.totp_issuer, &username)?;
+
+    // Restarting setup simply replaces a pending, unverified secret
+    sqlx::query("UPDATE users SET totp_secret = $1, updated_at = NOW() WHERE id = $2")
+        .bind(&encrypted)
+        .bind(auth.user_id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(Json(TwoFactorSetupResponse {
+        secret: totp.get_secret_base32(),
+        otpauth_url: totp.get_url(),
+    }))
+}
+
+/// Confirm 2FA setup with a code from the app; enables 2FA and returns recovery codes
+#[utoipa::path(
+    post,
+    path = "/api/users/me/2fa/verify",
+    request_body = VerifyTwoFactorRequest,
+    responses(
+        (status = 200, description = "2FA enabled; recovery codes are shown only once", body = RecoveryCodesResponse),
+        (status = 400, description = "Invalid code or setup not started"),
+        (status = 409, description = "2FA is already enabled")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Users"
+)]
+pub async fn verify_two_factor(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<VerifyTwoFactorRequest>,
+) -> Result<Json<RecoveryCodesResponse>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let (username, totp_enabled, stored_secret): (String, bool, Option<String>) =
+        sqlx::query_as(
+            "SELECT username, totp_enabled, totp_secret FROM users WHERE id = $1 FOR UPDATE",
+        )
+        .bind(auth.user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()))?;
+
+    if totp_enabled {
+        return Err(AppError::Conflict(
+            codes::TWO_FACTOR_ALREADY_ENABLED,
+            "Two-factor authentication is already enabled".to_string(),
+        ));
+    }
+    let stored_secret = stored_secret.ok_or_else(|| {
+        AppError::BadRequest(
+            codes::TWO_FACTOR_NOT_STARTED,
+            "Start setup with /api/users/me/2fa/enable first".to_string(),
+        )
+    })?;
+
+    let secret = totp::decrypt_secret(&state.config.totp_encryption_key, &stored_secret)?;
+    let totp = totp::build(secret, &state.config.totp_issuer, &username)?;
+    if !totp::check_code(&totp, payload.code.trim())? {
+        return Err(AppError::BadRequest(
+            codes::INVALID_TWO_FACTOR_CODE,
+            "Invalid two-factor code".to_string(),
+        ));
+    }
+
+    sqlx::query("UPDATE users SET totp_enabled = TRUE, updated_at = NOW() WHERE id = $1")
+        .bind(auth.user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let recovery_codes = replace_recovery_codes(&mut tx, auth.user_id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(RecoveryCodesResponse { recovery_codes }))
+}
+
+async fn replace_recovery_codes(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+) -> Result<Vec<String>, AppError> {
+    sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let codes = totp::generate_recovery_codes();
+    let hashes: Vec<String> = codes
+        .iter()
+        .map(|c| hash_opaque_token(&totp::normalize_recovery_code(c)))
+        .collect();
+
+    sqlx::query(
+        "INSERT INTO user_recovery_codes (user_id, code_hash)
+         SELECT $1, UNNEST($2::text[])",
+    )
+    .bind(user_id)
+    .bind(&hashes)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(codes)
+}
+
+/// Checks a login code: a current TOTP code, or else an unused recovery code
+/// (which is consumed).
+pub async fn verify_login_code(
+    state: &AppState,
+    user_id: Uuid,
+    username: &str,
+    stored_secret: &str,
+    code: &str,
+) -> Result<bool, AppError> {
+    let code = code.trim();
+
+    if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+        let secret = totp::decrypt_secret(&state.config.totp_encryption_key, stored_secret)?;
+        let totp = totp::build(secret, &state.config.totp_issuer, username)?;
+        return totp::check_code(&totp, code);
+    }
+
+    let used = sqlx::query(
+        "UPDATE user_recovery_codes SET used_at = NOW()
+         WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL",
+    )
+    .bind(user_id)
+    .bind(hash_opaque_token(&totp::normalize_recovery_code(code)))
+    .execute(&state.db)
+    .await?;
+
+    Ok(used.rows_affected() > 0)
+}
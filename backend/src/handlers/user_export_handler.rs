@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    response::Response,
+};
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::errors::{codes, AppError};
+use crate::export::{self, json_response, ExportChunk};
+use crate::handlers::{
+    comment_handler, history_handler, notification_handler, task_handler, time_entry_handler,
+    user_handler, user_history_handler,
+};
+use crate::models::{
+    Notification, TaskComment, TaskEvent, TaskWithNames, TimeEntry, User, UserEvent,
+};
+use crate::AppState;
+
+/// Sends one piece of the document; fails once the client has gone away
+async fn send(out: &mpsc::Sender<ExportChunk>, bytes: Vec<u8>) -> Result<(), AppError> {
+    out.send(Ok(bytes))
+        .await
+        .map_err(|_| AppError::Internal("Export download aborted".to_string()))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, AppError> {
+    serde_json::to_vec(value)
+        .map_err(|e| AppError::Internal(format!("Export serialization error: {}", e)))
+}
+
+/// Writes `,"key":[...]`, one row at a time
+async fn write_array<R, T, S>(
+    out: &mpsc::Sender<ExportChunk>,
+    key: &str,
+    mut rows: S,
+    to_item: impl Fn(R) -> T,
+) -> Result<(), AppError>
+where
+    S: Stream<Item = Result<R, sqlx::Error>> + Unpin,
+    T: Serialize,
+{
+    send(out, format!(",\"{}\":[", key).into_bytes()).await?;
+    let mut first = true;
+    while let Some(row) = rows.next().await {
+        let mut chunk = if first { Vec::new() } else { b",".to_vec() };
+        chunk.extend(to_json(&to_item(row?))?);
+        send(out, chunk).await?;
+        first = false;
+    }
+    send(out, b"]".to_vec()).await
+}
+
+/// Everything stored about `user_id` after the profile: tasks they created or test (deleted
+/// ones included), comments they wrote, time they logged, task changes they made, their own
+/// user history and their notifications.
+async fn write_bundle(
+    state: &AppState,
+    auth: &AuthUser,
+    user_id: Uuid,
+    out: &mpsc::Sender<ExportChunk>,
+) -> Result<(), AppError> {
+    let mut db_tx = export::unbounded_transaction(&state.db).await?;
+
+    let tasks_sql = format!(
+        "SELECT {} FROM tasks t {}
+         WHERE t.assigned_by = $1 OR t.tester_id = $1
+         ORDER BY t.task_number",
+        task_handler::TASK_WITH_NAMES_COLUMNS,
+        task_handler::TASK_NAME_JOINS
+    );
+    let rows = sqlx::query_as::<_, TaskWithNames>(&tasks_sql)
+        .bind(user_id)
+        .fetch(&mut *db_tx);
+    write_array(out, "tasks", rows, |t| {
+        task_handler::redact_task_fields(
+            task_handler::task_to_response(t, &state.config),
+            auth,
+            &state.config,
+        )
+    })
+    .await?;
+
+    let rows = sqlx::query_as::<_, TaskComment>(
+        "SELECT c.id, c.task_id, c.author_id, u.full_name AS author_name, c.body, c.created_at
+         FROM task_comments c
+         LEFT JOIN users u ON u.id = c.author_id
+         WHERE c.author_id = $1
+         ORDER BY c.created_at, c.id",
+    )
+    .bind(user_id)
+    .fetch(&mut *db_tx);
+    write_array(out, "comments", rows, comment_handler::comment_to_response).await?;
+
+    let rows = sqlx::query_as::<_, TimeEntry>(
+        "SELECT e.id, e.task_id, e.user_id, u.full_name AS user_name, e.hours, e.note,
+                e.logged_at, e.created_at
+         FROM time_entries e
+         LEFT JOIN users u ON u.id = e.user_id
+         WHERE e.user_id = $1
+         ORDER BY e.logged_at, e.id",
+    )
+    .bind(user_id)
+    .fetch(&mut *db_tx);
+    write_array(out, "time_entries", rows, time_entry_handler::time_entry_to_response).await?;
+
+    // Other people's names are not part of this user's data, so user ids stay unresolved
+    let no_names = HashMap::new();
+    let rows = sqlx::query_as::<_, TaskEvent>(
+        "SELECT e.id, e.task_id, e.actor_id, u.full_name AS actor_name,
+                e.field, e.old_value, e.new_value, e.reason, e.created_at
+         FROM task_events e
+         LEFT JOIN users u ON u.id = e.actor_id
+         WHERE e.actor_id = $1
+         ORDER BY e.created_at, e.id",
+    )
+    .bind(user_id)
+    .fetch(&mut *db_tx);
+    write_array(out, "task_events", rows, |e| {
+        history_handler::event_to_response(e, &no_names)
+    })
+    .await?;
+
+    let rows = sqlx::query_as::<_, UserEvent>(
+        "SELECT e.id, e.user_id, e.actor_id, u.full_name AS actor_name,
+                e.field, e.old_value, e.new_value, e.created_at
+         FROM user_events e
+         LEFT JOIN users u ON u.id = e.actor_id
+         WHERE e.user_id = $1
+         ORDER BY e.created_at, e.id",
+    )
+    .bind(user_id)
+    .fetch(&mut *db_tx);
+    write_array(out, "user_events", rows, user_history_handler::event_to_response).await?;
+
+    let rows = sqlx::query_as::<_, Notification>(
+        "SELECT id, user_id, kind, payload, read_at, created_at
+         FROM notifications
+         WHERE user_id = $1
+         ORDER BY created_at, id",
+    )
+    .bind(user_id)
+    .fetch(&mut *db_tx);
+    write_array(out, "notifications", rows, |n| {
+        notification_handler::notification_to_response(n, &state.config)
+    })
+    .await?;
+
+    send(out, b"}".to_vec()).await
+}
+
+/// Download everything stored about a user as one JSON document, for subject access
+/// requests (admin, or the user themselves). Password hashes are never included.
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/export",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "JSON attachment with `exported_at`, `user`, `tasks`, `comments`, `time_entries`, `task_events`, `user_events` and `notifications`",
+            content_type = "application/json", body = String),
+        (status = 404, description = "User not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Users"
+)]
+pub async fn export_user_data(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Response, AppError> {
+    if id != auth.user_id && !auth.is_admin() {
+        return Err(AppError::Forbidden(
+            codes::ADMIN_ONLY,
+            "Only administrators can export another user's data".to_string(),
+        ));
+    }
+
+    let user: User = sqlx::query_as(
+        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at
+         FROM users WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()))?;
+
+    let mut head = format!(
+        "{{\"exported_at\":\"{}\",\"user\":",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")
+    )
+    .into_bytes();
+    head.extend(to_json(&user_handler::user_to_response(user))?);
+
+    let (tx, rx) = mpsc::channel::<ExportChunk>(32);
+    send(&tx, head).await?;
+
+    tokio::spawn(async move {
+        // A closed channel means the client went away, which is not worth an error
+        if let Err(e) = write_bundle(&state, &auth, id, &tx).await
+            && !tx.is_closed()
+        {
+            tracing::error!("User data export failed: {}", e);
+            let _ = tx.send(Err(std::io::Error::other(e.to_string()))).await;
+        }
+    });
+
+    Ok(json_response(&format!("user_{}", id), rx))
+}
@@ -1,26 +1,89 @@
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
+    http::{header::CONTENT_TYPE, HeaderMap},
     Json,
 };
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationErrors};
 
-use crate::auth::AuthUser;
-use crate::dto::{CreateUserRequest, PaginationParams, UpdateUserRequest, UserResponse};
-use crate::errors::AppError;
-use crate::models::User;
+use crate::auth::{revoke_all_sessions, AuthUser};
+use crate::dto::{
+    CreateUserRequest, ImportUserRow, PaginatedResponse, SortOrder, UpdateProfileRequest,
+    UpdateUserRequest, UserFilterParams, UserImportMode, UserImportParams, UserImportResult,
+    UserResponse, UserSortField,
+};
+use crate::errors::{self, codes, AppError};
+use crate::handlers::{created, page_bounds, user_history_handler, Created};
+use crate::idempotency::{self, IdempotencyKey};
+use crate::models::{User, UserRole};
+use crate::password;
 use crate::AppState;
 
 fn require_admin(auth: &AuthUser) -> Result<(), AppError> {
     if !auth.is_admin() {
         return Err(AppError::Forbidden(
+            codes::ADMIN_ONLY,
             "Only administrators can manage users".to_string(),
         ));
     }
     Ok(())
 }
 
-fn user_to_response(u: User) -> UserResponse {
+/// Rejects removing `user_id` from the set of active admins when it is the last one.
+/// Locks the active admin rows, so two concurrent demotions cannot both pass the check.
+async fn ensure_not_last_admin(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    let admins: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM users
+         WHERE role = 'admin' AND is_active = TRUE
+         ORDER BY id
+         FOR UPDATE",
+    )
+    .fetch_all(conn)
+    .await?;
+
+    if admins.len() == 1 && admins[0] == user_id {
+        return Err(AppError::BadRequest(
+            codes::LAST_ADMIN,
+            "Cannot remove the last active administrator".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Usernames are unique regardless of case, emails exactly. The error says which of the
+/// two is taken; every caller is authenticated, and admins can list users anyway.
+async fn ensure_identity_free(
+    conn: &mut sqlx::PgConnection,
+    username: &str,
+    email: &str,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    let (username_taken, email_taken): (bool, bool) = sqlx::query_as(
+        "SELECT COALESCE(BOOL_OR(LOWER(username) = LOWER($1)), FALSE),
+                COALESCE(BOOL_OR(email = $2), FALSE)
+         FROM users
+         WHERE (LOWER(username) = LOWER($1) OR email = $2) AND id <> $3",
+    )
+    .bind(username)
+    .bind(email)
+    .bind(user_id)
+    .fetch_one(conn)
+    .await?;
+
+    if username_taken || email_taken {
+        return Err(AppError::IdentityTaken {
+            username: username_taken,
+            email: email_taken,
+        });
+    }
+    Ok(())
+}
+
+pub fn user_to_response(u: User) -> UserResponse {
     UserResponse {
         id: u.id,
         username: u.username,
@@ -30,19 +93,57 @@ fn user_to_response(u: User) -> UserResponse {
         is_active: u.is_active,
         created_at: u.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
         updated_at: u.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        last_login_at: u
+            .last_login_at
+            .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
     }
 }
 
-/// Get all users (admin only)
+/// Filter predicates for the user list and its total count ($1..$3)
+const USER_FILTER_WHERE: &str = "($1::text IS NULL OR role::text = $1)
+           AND ($2::bool IS NULL OR is_active = $2)
+           AND ($3::text IS NULL OR username ILIKE $3 OR email ILIKE $3 OR full_name ILIKE $3)";
+
+/// Builds the ORDER BY clause from an allowlisted column, never from raw input.
+/// Users who never logged in sort last in either direction.
+fn user_order_by(sort_by: Option<UserSortField>, order: Option<SortOrder>) -> String {
+    let direction = match order.unwrap_or(SortOrder::Desc) {
+        SortOrder::Asc => "ASC",
+        SortOrder::Desc => "DESC",
+    };
+    let column = match sort_by.unwrap_or(UserSortField::CreatedAt) {
+        UserSortField::CreatedAt => "created_at",
+        UserSortField::LastLoginAt => "last_login_at",
+        UserSortField::Username => "username",
+    };
+    format!("{} {} NULLS LAST, id {}", column, direction, direction)
+}
+
+/// `%q%` for ILIKE, with the user's own `%`, `_` and `\` matched literally
+fn like_pattern(q: &str) -> String {
+    let escaped = q
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Get all users (admin only), optionally filtered
 #[utoipa::path(
     get,
     path = "/api/users",
     params(
         ("page" = Option<i64>, Query, description = "Page number (default 1)"),
-        ("per_page" = Option<i64>, Query, description = "Items per page (default 20)")
+        ("per_page" = Option<i64>, Query, description = "Items per page (default 20, max 100)"),
+        ("role" = Option<UserRole>, Query, description = "Filter by role"),
+        ("is_active" = Option<bool>, Query, description = "Filter by active flag"),
+        ("q" = Option<String>, Query, description = "Case-insensitive search in username, email and full name"),
+        ("sort_by" = Option<UserSortField>, Query, description = "Sort column (default created_at)"),
+        ("order" = Option<SortOrder>, Query, description = "Sort direction (default desc)")
     ),
     responses(
-        (status = 200, description = "List of users", body = Vec<UserResponse>),
+        (status = 200, description = "Page of users", body = PaginatedResponse<UserResponse>),
+        (status = 400, description = "Invalid query parameters"),
         (status = 403, description = "Forbidden")
     ),
     security(("bearer_auth" = [])),
@@ -51,25 +152,54 @@ fn user_to_response(u: User) -> UserResponse {
 pub async fn get_users(
     State(state): State<AppState>,
     auth: AuthUser,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<Vec<UserResponse>>, AppError> {
+    Query(params): Query<UserFilterParams>,
+) -> Result<Json<PaginatedResponse<UserResponse>>, AppError> {
     require_admin(&auth)?;
 
-    let page = params.page.unwrap_or(1).max(1);
-    let per_page = params.per_page.unwrap_or(20).clamp(1, 100);
+    let (page, per_page) = page_bounds(&state.config, params.page, params.per_page)?;
     let offset = (page - 1) * per_page;
 
-    let users: Vec<User> = sqlx::query_as(
-        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at
-         FROM users ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-    )
+    let role_str = params.role.map(|r| r.to_string());
+    let pattern = params
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(like_pattern);
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM users WHERE {}",
+        USER_FILTER_WHERE
+    ))
+    .bind(&role_str)
+    .bind(params.is_active)
+    .bind(&pattern)
+    .fetch_one(&state.db)
+    .await?;
+
+    let users: Vec<User> = sqlx::query_as(&format!(
+        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at
+         FROM users WHERE {} ORDER BY {} LIMIT $4 OFFSET $5",
+        USER_FILTER_WHERE,
+        user_order_by(params.sort_by, params.order)
+    ))
+    .bind(&role_str)
+    .bind(params.is_active)
+    .bind(&pattern)
     .bind(per_page)
     .bind(offset)
     .fetch_all(&state.db)
     .await?;
 
-    let response: Vec<UserResponse> = users.into_iter().map(user_to_response).collect();
-    Ok(Json(response))
+    let items: Vec<UserResponse> = users.into_iter().map(user_to_response).collect();
+    Ok(Json(PaginatedResponse {
+        items,
+        total,
+        page,
+        per_page,
+        next_cursor: None,
+    }))
 }
 
 /// Get user by ID (admin only)
@@ -78,7 +208,8 @@ pub async fn get_users(
     path = "/api/users/{id}",
     params(("id" = Uuid, Path, description = "User ID")),
     responses(
-        (status = 200, description = "User found", body = UserResponse),
+        (status = 200, description = "User found", body = UserResponse,
+            headers(("Location" = String, description = "URL of the user"))),
         (status = 404, description = "User not found"),
         (status = 403, description = "Forbidden")
     ),
@@ -93,13 +224,14 @@ pub async fn get_user(
     require_admin(&auth)?;
 
     let user: User = sqlx::query_as(
-        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at
+        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at
          FROM users WHERE id = $1",
     )
     .bind(id)
     .fetch_optional(&state.db)
     .await?
-    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()))?;
 
     Ok(Json(user_to_response(user)))
 }
@@ -108,11 +240,14 @@ pub async fn get_user(
 #[utoipa::path(
     post,
     path = "/api/users",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key return the original user for 24h")
+    ),
     request_body = CreateUserRequest,
     responses(
-        (status = 201, description = "User created", body = UserResponse),
+        (status = 201, description = "User created (or the user created earlier with this Idempotency-Key)", body = UserResponse),
         (status = 400, description = "Validation error"),
-        (status = 409, description = "Username or email already exists"),
+        (status = 409, description = "Username and/or email already exist (USERNAME_TAKEN, EMAIL_TAKEN or USERNAME_AND_EMAIL_TAKEN, with `fields`), or Idempotency-Key reused with a different body"),
         (status = 403, description = "Forbidden")
     ),
     security(("bearer_auth" = [])),
@@ -121,48 +256,280 @@ pub async fn get_user(
 pub async fn create_user(
     State(state): State<AppState>,
     auth: AuthUser,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     Json(payload): Json<CreateUserRequest>,
-) -> Result<(axum::http::StatusCode, Json<UserResponse>), AppError> {
+) -> Result<Created<UserResponse>, AppError> {
     require_admin(&auth)?;
     payload.validate()?;
 
-    let existing: Option<(Uuid,)> = sqlx::query_as(
-        "SELECT id FROM users WHERE username = $1 OR email = $2",
+    // Checked before the duplicate check, which a successful retry would otherwise trip
+    let request_hash = idempotency::request_hash(&payload)?;
+    if let Some(key) = &idempotency_key {
+        let mut conn = state.db.acquire().await?;
+        if let Some(user_id) =
+            idempotency::find(&mut conn, auth.user_id, "user", key, &request_hash).await?
+        {
+            let user: User = sqlx::query_as(
+                "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                        last_login_at
+                 FROM users WHERE id = $1",
+            )
+            .bind(user_id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string())
+            })?;
+
+            return Ok(created(format!("/api/users/{}", user.id), user_to_response(user)));
+        }
+    }
+
+    ensure_identity_free(
+        &mut *state.db.acquire().await?,
+        &payload.username,
+        &payload.email,
+        Uuid::nil(),
     )
-    .bind(&payload.username)
-    .bind(&payload.email)
-    .fetch_optional(&state.db)
     .await?;
 
-    if existing.is_some() {
-        return Err(AppError::Conflict(
-            "Username or email already exists".to_string(),
-        ));
-    }
+    password::validate_password_strength(
+        &state.config.password_policy,
+        "password",
+        &payload.password,
+    )?;
+    password::ensure_not_breached(&state, &payload.password).await?;
 
-    use argon2::PasswordHasher;
-    let salt =
-        argon2::password_hash::SaltString::generate(&mut argon2::password_hash::rand_core::OsRng);
-    let password_hash = argon2::Argon2::default()
-        .hash_password(payload.password.as_bytes(), &salt)
-        .map_err(|e| AppError::Internal(format!("Password hash error: {}", e)))?
-        .to_string();
+    let password_hash = password::hash_password(&state.config, &payload.password)?;
 
     let role_str = payload.role.to_string();
+    let mut tx = state.db.begin().await?;
+
     let user: User = sqlx::query_as(
         "INSERT INTO users (username, email, password_hash, full_name, role)
          VALUES ($1, $2, $3, $4, $5::user_role)
-         RETURNING id, username, email, password_hash, full_name, role, is_active, created_at, updated_at",
+         RETURNING id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at",
     )
     .bind(&payload.username)
     .bind(&payload.email)
     .bind(&password_hash)
     .bind(&payload.full_name)
     .bind(&role_str)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    user_history_handler::record_user_event(
+        &mut tx,
+        user.id,
+        auth.user_id,
+        "created",
+        None,
+        Some(user.username.clone()),
+    )
     .await?;
 
-    Ok((axum::http::StatusCode::CREATED, Json(user_to_response(user))))
+    if let Some(key) = &idempotency_key {
+        idempotency::save(&mut tx, auth.user_id, "user", key, &request_hash, user.id).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(created(format!("/api/users/{}", user.id), user_to_response(user)))
+}
+
+/// Most users a single import may create
+const MAX_IMPORT_ROWS: usize = 500;
+
+/// Reads an import body: CSV with a header row when sent as `text/csv`, a JSON array otherwise.
+/// A row that cannot be read fails on its own instead of failing the whole upload.
+fn parse_import_rows(
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<Vec<Result<ImportUserRow, String>>, AppError> {
+    let is_csv = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/csv"));
+
+    if is_csv {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(body);
+        return Ok(reader
+            .deserialize()
+            .map(|row| row.map_err(|e| format!("Invalid row: {}", e)))
+            .collect());
+    }
+
+    let values: Vec<serde_json::Value> = serde_json::from_slice(body).map_err(|e| {
+        AppError::BadRequest(
+            codes::INVALID_IMPORT_BODY,
+            format!("Expected a JSON array of users or text/csv: {}", e),
+        )
+    })?;
+    Ok(values
+        .into_iter()
+        .map(|v| serde_json::from_value(v).map_err(|e| format!("Invalid row: {}", e)))
+        .collect())
+}
+
+/// Every validation message of a row, for the per-row report
+fn validation_reason(errors: &ValidationErrors) -> String {
+    let mut messages: Vec<String> = errors
+        .field_errors()
+        .into_iter()
+        .flat_map(|(field, errs)| {
+            errs.iter().map(move |e| match &e.message {
+                Some(message) => message.to_string(),
+                None => format!("Invalid {}", field),
+            })
+        })
+        .collect();
+    messages.sort();
+    messages.join("; ")
+}
+
+/// Create many users at once from CSV or a JSON array (admin only).
+/// Each user gets a random initial password, returned only in this response.
+#[utoipa::path(
+    post,
+    path = "/api/users/import",
+    params(
+        ("mode" = Option<UserImportMode>, Query, description = "atomic (default): create nothing if any row fails; continue: create the valid rows")
+    ),
+    request_body(
+        description = "Users with username, email, full_name and role; CSV needs a header row",
+        content(
+            (Vec<ImportUserRow> = "application/json"),
+            (String = "text/csv")
+        )
+    ),
+    responses(
+        (status = 200, description = "Per-row outcome, in upload order", body = Vec<UserImportResult>),
+        (status = 400, description = "Unreadable body or too many rows"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Users"
+)]
+pub async fn import_users(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<UserImportParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Vec<UserImportResult>>, AppError> {
+    require_admin(&auth)?;
+
+    let mode = params.mode.unwrap_or_default();
+    let rows = parse_import_rows(&headers, &body)?;
+    if rows.is_empty() || rows.len() > MAX_IMPORT_ROWS {
+        return Err(AppError::BadRequest(
+            codes::INVALID_BATCH_SIZE,
+            format!(
+                "Provide 1-{} users to import",
+                MAX_IMPORT_ROWS
+            ),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+    let mut results = Vec::with_capacity(rows.len());
+    // In atomic mode the first failure dooms the batch, so later rows are only checked
+    let mut doomed = false;
+
+    for (i, row) in rows.into_iter().enumerate() {
+        let failed = |username: Option<String>, reason: String| UserImportResult {
+            row: i + 1,
+            username,
+            success: false,
+            user_id: None,
+            initial_password: None,
+            reason: Some(reason),
+        };
+
+        let row = match row {
+            Ok(row) => row,
+            Err(reason) => {
+                results.push(failed(None, reason));
+                doomed |= mode == UserImportMode::Atomic;
+                continue;
+            }
+        };
+        if let Err(e) = row.validate() {
+            results.push(failed(Some(row.username), validation_reason(&e)));
+            doomed |= mode == UserImportMode::Atomic;
+            continue;
+        }
+        // Rows created earlier in this batch are visible here, so duplicates within the upload fail too
+        match ensure_identity_free(&mut tx, &row.username, &row.email, Uuid::nil()).await {
+            Ok(()) => {}
+            Err(AppError::IdentityTaken { username, email }) => {
+                let reason = errors::identity_taken_message(username, email).to_string();
+                results.push(failed(Some(row.username), reason));
+                doomed |= mode == UserImportMode::Atomic;
+                continue;
+            }
+            Err(e) => return Err(e),
+        }
+        if doomed {
+            results.push(failed(
+                Some(row.username),
+                "Not imported: another row failed".to_string(),
+            ));
+            continue;
+        }
+
+        let initial_password = password::generate_password(&state.config.password_policy);
+        let password_hash = password::hash_password(&state.config, &initial_password)?;
+
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (username, email, password_hash, full_name, role)
+             VALUES ($1, $2, $3, $4, $5::user_role)
+             RETURNING id",
+        )
+        .bind(&row.username)
+        .bind(&row.email)
+        .bind(&password_hash)
+        .bind(&row.full_name)
+        .bind(row.role.to_string())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        user_history_handler::record_user_event(
+            &mut tx,
+            user_id,
+            auth.user_id,
+            "created",
+            None,
+            Some(row.username.clone()),
+        )
+        .await?;
+
+        results.push(UserImportResult {
+            row: i + 1,
+            username: Some(row.username),
+            success: true,
+            user_id: Some(user_id),
+            initial_password: Some(initial_password),
+            reason: None,
+        });
+    }
+
+    if !doomed {
+        tx.commit().await?;
+        return Ok(Json(results));
+    }
+
+    tx.rollback().await?;
+    for result in results.iter_mut().filter(|r| r.success) {
+        result.success = false;
+        result.user_id = None;
+        result.initial_password = None;
+        result.reason = Some("Not imported: another row failed".to_string());
+    }
+    Ok(Json(results))
 }
 
 /// Update a user (admin only)
@@ -173,8 +540,9 @@ pub async fn create_user(
     request_body = UpdateUserRequest,
     responses(
         (status = 200, description = "User updated", body = UserResponse),
-        (status = 400, description = "Validation error"),
+        (status = 400, description = "Validation error or the last active admin would be lost"),
         (status = 404, description = "User not found"),
+        (status = 409, description = "Username and/or email already exist (USERNAME_TAKEN, EMAIL_TAKEN or USERNAME_AND_EMAIL_TAKEN, with `fields`)"),
         (status = 403, description = "Forbidden")
     ),
     security(("bearer_auth" = [])),
@@ -189,33 +557,48 @@ pub async fn update_user(
     require_admin(&auth)?;
     payload.validate()?;
 
+    // Hash before opening the transaction: the breach check may call out over the network
+    let password_hash = match &payload.password {
+        Some(new_password) => {
+            password::validate_password_strength(
+                &state.config.password_policy,
+                "password",
+                new_password,
+            )?;
+            password::ensure_not_breached(&state, new_password).await?;
+            Some(password::hash_password(&state.config, new_password)?)
+        }
+        None => None,
+    };
+
+    let mut tx = state.db.begin().await?;
+
     let existing: User = sqlx::query_as(
-        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at
-         FROM users WHERE id = $1",
+        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at
+         FROM users WHERE id = $1
+         FOR UPDATE",
     )
     .bind(id)
-    .fetch_optional(&state.db)
+    .fetch_optional(&mut *tx)
     .await?
-    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()))?;
 
-    let new_username = payload.username.unwrap_or(existing.username);
-    let new_email = payload.email.unwrap_or(existing.email);
-    let new_full_name = payload.full_name.unwrap_or(existing.full_name);
-    let new_role = payload.role.unwrap_or(existing.role);
+    let new_username = payload.username.unwrap_or(existing.username.clone());
+    let new_email = payload.email.unwrap_or(existing.email.clone());
+    let new_full_name = payload.full_name.unwrap_or(existing.full_name.clone());
+    let new_role = payload.role.unwrap_or(existing.role.clone());
     let new_is_active = payload.is_active.unwrap_or(existing.is_active);
+    let password_changed = password_hash.is_some();
+    let new_password_hash = password_hash.unwrap_or(existing.password_hash);
 
-    let new_password_hash = if let Some(new_password) = payload.password {
-        use argon2::PasswordHasher;
-        let salt = argon2::password_hash::SaltString::generate(
-            &mut argon2::password_hash::rand_core::OsRng,
-        );
-        argon2::Argon2::default()
-            .hash_password(new_password.as_bytes(), &salt)
-            .map_err(|e| AppError::Internal(format!("Password hash error: {}", e)))?
-            .to_string()
-    } else {
-        existing.password_hash
-    };
+    ensure_identity_free(&mut tx, &new_username, &new_email, id).await?;
+
+    let was_active_admin = existing.role == UserRole::Admin && existing.is_active;
+    let stays_active_admin = new_role == UserRole::Admin && new_is_active;
+    if was_active_admin && !stays_active_admin {
+        ensure_not_last_admin(&mut tx, id).await?;
+    }
 
     let role_str = new_role.to_string();
     let user: User = sqlx::query_as(
@@ -223,7 +606,8 @@ pub async fn update_user(
                           full_name = $4, role = $5::user_role, is_active = $6,
                           updated_at = NOW()
          WHERE id = $7
-         RETURNING id, username, email, password_hash, full_name, role, is_active, created_at, updated_at",
+         RETURNING id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at",
     )
     .bind(&new_username)
     .bind(&new_email)
@@ -232,12 +616,67 @@ pub async fn update_user(
     .bind(&role_str)
     .bind(new_is_active)
     .bind(id)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
+    let changes = [
+        ("username", existing.username, user.username.clone()),
+        ("email", existing.email, user.email.clone()),
+        ("full_name", existing.full_name, user.full_name.clone()),
+        ("role", existing.role.to_string(), user.role.to_string()),
+        ("is_active", existing.is_active.to_string(), user.is_active.to_string()),
+    ];
+    user_history_handler::record_user_changes(&mut tx, id, auth.user_id, changes).await?;
+
+    // Whoever knew the old password must not stay signed in with it
+    if password_changed {
+        revoke_all_sessions(&mut tx, id).await?;
+        user_history_handler::record_user_event(&mut tx, id, auth.user_id, "password", None, None)
+            .await?;
+    }
+
+    tx.commit().await?;
+
     Ok(Json(user_to_response(user)))
 }
 
+/// Sign a user out of every session: all access and refresh tokens stop working (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/revoke-sessions",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 204, description = "All sessions revoked"),
+        (status = 404, description = "User not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Users"
+)]
+pub async fn revoke_user_sessions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<axum::http::StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if !exists {
+        return Err(AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()));
+    }
+
+    revoke_all_sessions(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
 /// Delete a user (admin only)
 #[utoipa::path(
     delete,
@@ -245,6 +684,7 @@ pub async fn update_user(
     params(("id" = Uuid, Path, description = "User ID")),
     responses(
         (status = 204, description = "User deleted"),
+        (status = 400, description = "Own account or the last active admin"),
         (status = 404, description = "User not found"),
         (status = 403, description = "Forbidden")
     ),
@@ -260,18 +700,32 @@ pub async fn delete_user(
 
     if id == auth.user_id {
         return Err(AppError::BadRequest(
+            codes::CANNOT_DELETE_SELF,
             "Cannot delete your own account".to_string(),
         ));
     }
 
-    let result = sqlx::query("DELETE FROM users WHERE id = $1")
+    let mut tx = state.db.begin().await?;
+
+    ensure_not_last_admin(&mut tx, id).await?;
+
+    let username: String = sqlx::query_scalar("DELETE FROM users WHERE id = $1 RETURNING username")
         .bind(id)
-        .execute(&state.db)
-        .await?;
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()))?;
 
-    if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("User not found".to_string()));
-    }
+    user_history_handler::record_user_event(
+        &mut tx,
+        id,
+        auth.user_id,
+        "deleted",
+        Some(username),
+        None,
+    )
+    .await?;
+
+    tx.commit().await?;
 
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
@@ -291,13 +745,239 @@ pub async fn get_me(
     auth: AuthUser,
 ) -> Result<Json<UserResponse>, AppError> {
     let user: User = sqlx::query_as(
-        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at
+        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at
          FROM users WHERE id = $1",
     )
     .bind(auth.user_id)
     .fetch_optional(&state.db)
     .await?
-    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()))?;
 
     Ok(Json(user_to_response(user)))
 }
+
+/// Update own profile: full name and email
+#[utoipa::path(
+    patch,
+    path = "/api/users/me",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = UserResponse),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Role or active flag in the request"),
+        (status = 409, description = "Email already exists (EMAIL_TAKEN)")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Users"
+)]
+pub async fn update_me(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateProfileRequest>,
+) -> Result<Json<UserResponse>, AppError> {
+    if payload.role.is_some() || payload.is_active.is_some() {
+        return Err(AppError::Forbidden(
+            codes::ADMIN_ONLY,
+            "Role and active status can only be changed by an administrator".to_string(),
+        ));
+    }
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let existing: User = sqlx::query_as(
+        "SELECT id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at
+         FROM users WHERE id = $1
+         FOR UPDATE",
+    )
+    .bind(auth.user_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()))?;
+
+    let new_email = payload.email.unwrap_or(existing.email.clone());
+    let new_full_name = payload.full_name.unwrap_or(existing.full_name.clone());
+
+    ensure_identity_free(&mut tx, &existing.username, &new_email, auth.user_id).await?;
+
+    let user: User = sqlx::query_as(
+        "UPDATE users SET email = $1, full_name = $2, updated_at = NOW()
+         WHERE id = $3
+         RETURNING id, username, email, password_hash, full_name, role, is_active, created_at, updated_at,
+                last_login_at",
+    )
+    .bind(&new_email)
+    .bind(&new_full_name)
+    .bind(auth.user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let changes = [
+        ("email", existing.email, user.email.clone()),
+        ("full_name", existing.full_name, user.full_name.clone()),
+    ];
+    user_history_handler::record_user_changes(&mut tx, auth.user_id, auth.user_id, changes).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(user_to_response(user)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, create_user};
+    use serde_json::json;
+    use sqlx::PgPool;
+
+    async fn update(
+        state: &AppState,
+        admin: Uuid,
+        id: Uuid,
+        body: serde_json::Value,
+    ) -> Result<UserResponse, AppError> {
+        let auth = test_support::auth(admin, UserRole::Admin);
+        let payload = serde_json::from_value(body).unwrap();
+        update_user(State(state.clone()), auth, Path(id), Json(payload)).await.map(|r| r.0)
+    }
+
+    fn is_last_admin(result: Result<impl Sized, AppError>) -> bool {
+        matches!(result, Err(AppError::BadRequest(codes::LAST_ADMIN, _)))
+    }
+
+    #[sqlx::test]
+    async fn the_only_admin_cannot_be_demoted_or_deactivated(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let admin = create_user(&pool, "admin", UserRole::Admin).await;
+
+        assert!(is_last_admin(update(&state, admin, admin, json!({ "role": "tester" })).await));
+        assert!(is_last_admin(update(&state, admin, admin, json!({ "is_active": false })).await));
+        // Edits that keep them an active admin still go through
+        assert!(update(&state, admin, admin, json!({ "full_name": "Root" })).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn a_second_admin_can_be_removed_but_not_the_last(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let first = create_user(&pool, "first", UserRole::Admin).await;
+        let second = create_user(&pool, "second", UserRole::Admin).await;
+
+        assert!(update(&state, first, second, json!({ "role": "manager" })).await.is_ok());
+        assert!(is_last_admin(update(&state, first, first, json!({ "role": "manager" })).await));
+
+        // An inactive admin does not count
+        let dormant = create_user(&pool, "dormant", UserRole::Admin).await;
+        assert!(update(&state, first, dormant, json!({ "is_active": false })).await.is_ok());
+        let mut conn = pool.acquire().await.unwrap();
+        assert!(is_last_admin(ensure_not_last_admin(&mut conn, first).await));
+        assert!(ensure_not_last_admin(&mut conn, second).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn deleting_the_other_admin_is_checked(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let first = create_user(&pool, "first", UserRole::Admin).await;
+        let second = create_user(&pool, "second", UserRole::Admin).await;
+        let auth = test_support::auth(first, UserRole::Admin);
+
+        // `first` deactivated elsewhere but still holding a token: `second` is the last one
+        sqlx::query("UPDATE users SET is_active = FALSE WHERE id = $1")
+            .bind(first)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(is_last_admin(delete_user(State(state.clone()), auth.clone(), Path(second)).await));
+
+        sqlx::query("UPDATE users SET is_active = TRUE WHERE id = $1")
+            .bind(first)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(delete_user(State(state), auth, Path(second)).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn identity_check_names_what_is_taken(pool: PgPool) {
+        create_user(&pool, "alice", UserRole::Developer).await;
+        let bob = create_user(&pool, "bob", UserRole::Developer).await;
+        let mut conn = pool.acquire().await.unwrap();
+
+        async fn taken(
+            conn: &mut sqlx::PgConnection,
+            username: &str,
+            email: &str,
+            user_id: Uuid,
+        ) -> Option<&'static str> {
+            match ensure_identity_free(conn, username, email, user_id).await {
+                Ok(()) => None,
+                Err(e @ AppError::IdentityTaken { .. }) => Some(e.code()),
+                Err(_) => panic!("unexpected error"),
+            }
+        }
+
+        let code = taken(&mut conn, "ALICE", "new@example.com", bob).await;
+        assert_eq!(code, Some(codes::USERNAME_TAKEN));
+        let code = taken(&mut conn, "carol", "alice@example.com", bob).await;
+        assert_eq!(code, Some(codes::EMAIL_TAKEN));
+        let code = taken(&mut conn, "alice", "alice@example.com", bob).await;
+        assert_eq!(code, Some(codes::USERNAME_AND_EMAIL_TAKEN));
+        // The user's own username and email do not collide with themselves
+        assert_eq!(taken(&mut conn, "bob", "bob@example.com", bob).await, None);
+    }
+
+    #[sqlx::test]
+    async fn update_cannot_collide_two_users(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let admin = create_user(&pool, "admin", UserRole::Admin).await;
+        create_user(&pool, "alice", UserRole::Developer).await;
+        let bob = create_user(&pool, "bob", UserRole::Developer).await;
+
+        let err = update(&state, admin, bob, json!({ "email": "alice@example.com" })).await;
+        assert!(matches!(err, Err(AppError::IdentityTaken { username: false, email: true })));
+        let err = update(&state, admin, bob, json!({ "username": "Alice" })).await;
+        assert!(matches!(err, Err(AppError::IdentityTaken { username: true, email: false })));
+
+        let email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+            .bind(bob)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(email, "bob@example.com");
+    }
+
+    #[sqlx::test]
+    async fn self_update_cannot_change_role_or_active_status(pool: PgPool) {
+        let state = test_support::state(pool.clone());
+        let tester = create_user(&pool, "tst", UserRole::Tester).await;
+        let auth = test_support::auth(tester, UserRole::Tester);
+        let update_me = |body: serde_json::Value| {
+            let payload = serde_json::from_value(body).unwrap();
+            super::update_me(State(state.clone()), auth.clone(), Json(payload))
+        };
+
+        for body in [
+            json!({ "role": "admin" }),
+            json!({ "full_name": "Tess", "role": "tester" }),
+            json!({ "is_active": true }),
+        ] {
+            let err = update_me(body).await.err().unwrap();
+            assert!(matches!(err, AppError::Forbidden(codes::ADMIN_ONLY, _)));
+        }
+        let (role, full_name): (UserRole, String) =
+            sqlx::query_as("SELECT role, full_name FROM users WHERE id = $1")
+                .bind(tester)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((role, full_name.as_str()), (UserRole::Tester, "tst"));
+
+        let Json(user) = update_me(json!({ "full_name": "Tess", "email": "tess@example.com" }))
+            .await
+            .unwrap();
+        assert_eq!(user.role, UserRole::Tester);
+        assert_eq!(user.full_name, "Tess");
+        assert_eq!(user.email, "tess@example.com");
+    }
+}
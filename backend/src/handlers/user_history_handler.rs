@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::dto::UserEventResponse;
+use crate::errors::{codes, AppError};
+use crate::models::UserEvent;
+use crate::AppState;
+
+/// Appends a user history entry; call inside the same transaction as the change itself.
+pub async fn record_user_event(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    actor_id: Uuid,
+    field: &str,
+    old_value: Option<String>,
+    new_value: Option<String>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO user_events (user_id, actor_id, field, old_value, new_value)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(user_id)
+    .bind(actor_id)
+    .bind(field)
+    .bind(old_value)
+    .bind(new_value)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Records each `(field, old, new)` whose value actually changed
+pub async fn record_user_changes<'a>(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    actor_id: Uuid,
+    changes: impl IntoIterator<Item = (&'a str, String, String)>,
+) -> Result<(), AppError> {
+    for (field, old_value, new_value) in changes {
+        if old_value != new_value {
+            record_user_event(conn, user_id, actor_id, field, Some(old_value), Some(new_value))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub fn event_to_response(e: UserEvent) -> UserEventResponse {
+    UserEventResponse {
+        id: e.id,
+        user_id: e.user_id,
+        actor_id: e.actor_id,
+        actor_name: e.actor_name,
+        field: e.field,
+        old_value: e.old_value,
+        new_value: e.new_value,
+        created_at: e.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+/// Get the administration history of a user, oldest first; deleted users keep theirs (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/history",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "User history", body = Vec<UserEventResponse>),
+        (status = 404, description = "User not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Users"
+)]
+pub async fn get_user_history(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<Vec<UserEventResponse>>, AppError> {
+    if !auth.is_admin() {
+        return Err(AppError::Forbidden(
+            codes::ADMIN_ONLY,
+            "Only administrators can manage users".to_string(),
+        ));
+    }
+
+    let events: Vec<UserEvent> = sqlx::query_as(
+        "SELECT e.id, e.user_id, e.actor_id, u.full_name AS actor_name,
+                e.field, e.old_value, e.new_value, e.created_at
+         FROM user_events e
+         LEFT JOIN users u ON u.id = e.actor_id
+         WHERE e.user_id = $1
+         ORDER BY e.created_at, e.id",
+    )
+    .bind(user_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    if events.is_empty() {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+            .bind(user_id)
+            .fetch_one(&state.db)
+            .await?;
+        if !exists {
+            return Err(AppError::NotFound(codes::USER_NOT_FOUND, "User not found".to_string()));
+        }
+    }
+
+    Ok(Json(events.into_iter().map(event_to_response).collect()))
+}
@@ -0,0 +1,185 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::AuthUser;
+use crate::dto::{CreateViewRequest, SavedViewResponse, TaskFilterParams};
+use crate::errors::{codes, AppError};
+use crate::handlers::task_handler;
+use crate::models::SavedView;
+use crate::AppState;
+
+fn view_to_response(v: SavedView) -> Result<SavedViewResponse, AppError> {
+    let filters: TaskFilterParams = serde_json::from_value(v.filters)
+        .map_err(|e| AppError::Internal(format!("Corrupted saved view filters: {}", e)))?;
+
+    Ok(SavedViewResponse {
+        id: v.id,
+        name: v.name,
+        filters,
+        created_at: v.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    })
+}
+
+/// Apply the caller's saved view underneath the explicitly passed filters.
+/// Explicit query parameters always win over the stored ones.
+pub async fn resolve_filters(
+    db: &sqlx::PgPool,
+    user_id: Uuid,
+    params: TaskFilterParams,
+) -> Result<TaskFilterParams, AppError> {
+    let Some(view_id) = params.view else {
+        return Ok(params);
+    };
+
+    let view: SavedView = sqlx::query_as(
+        "SELECT id, name, filters, created_at
+         FROM saved_views WHERE id = $1 AND user_id = $2",
+    )
+    .bind(view_id)
+    .bind(user_id)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::VIEW_NOT_FOUND, "Saved view not found".to_string()))?;
+
+    let saved = view_to_response(view)?.filters;
+
+    Ok(TaskFilterParams {
+        page: params.page.or(saved.page),
+        per_page: params.per_page.or(saved.per_page),
+        status: params.status.or(saved.status),
+        urgency: params.urgency.or(saved.urgency),
+        tester_id: params.tester_id.or(saved.tester_id),
+        assigned_by: params.assigned_by.or(saved.assigned_by),
+        task_number: params.task_number.or(saved.task_number),
+        due_before: params.due_before.or(saved.due_before),
+        due_after: params.due_after.or(saved.due_after),
+        created_after: params.created_after.or(saved.created_after),
+        created_before: params.created_before.or(saved.created_before),
+        tags: params.tags.or(saved.tags),
+        tags_match: params.tags_match.or(saved.tags_match),
+        include_deleted: params.include_deleted.or(saved.include_deleted),
+        include_duplicates: params.include_duplicates.or(saved.include_duplicates),
+        team_id: params.team_id.or(saved.team_id),
+        sort_by: params.sort_by.or(saved.sort_by),
+        order: params.order.or(saved.order),
+        after: params.after,
+        view: None,
+    })
+}
+
+/// Get the current user's saved views
+#[utoipa::path(
+    get,
+    path = "/api/views",
+    responses(
+        (status = 200, description = "List of saved views", body = Vec<SavedViewResponse>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Views"
+)]
+pub async fn get_views(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<SavedViewResponse>>, AppError> {
+    let views: Vec<SavedView> = sqlx::query_as(
+        "SELECT id, name, filters, created_at
+         FROM saved_views WHERE user_id = $1 ORDER BY name",
+    )
+    .bind(auth.user_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let response = views
+        .into_iter()
+        .map(view_to_response)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Json(response))
+}
+
+/// Save a named set of task filters
+#[utoipa::path(
+    post,
+    path = "/api/views",
+    request_body = CreateViewRequest,
+    responses(
+        (status = 201, description = "View saved", body = SavedViewResponse),
+        (status = 400, description = "Validation error or filters the task list would reject"),
+        (status = 403, description = "include_deleted by a non-manager"),
+        (status = 409, description = "A view with this name already exists")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Views"
+)]
+pub async fn create_view(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateViewRequest>,
+) -> Result<(axum::http::StatusCode, Json<SavedViewResponse>), AppError> {
+    payload.validate()?;
+    task_handler::validate_filters(&state.config, &auth, &payload.filters)?;
+
+    let existing: Option<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM saved_views WHERE user_id = $1 AND name = $2")
+            .bind(auth.user_id)
+            .bind(&payload.name)
+            .fetch_optional(&state.db)
+            .await?;
+
+    if existing.is_some() {
+        return Err(AppError::Conflict(
+            codes::VIEW_NAME_TAKEN,
+            "A view with this name already exists".to_string(),
+        ));
+    }
+
+    let filters = serde_json::to_value(&payload.filters)
+        .map_err(|e| AppError::Internal(format!("Filter serialization error: {}", e)))?;
+
+    let view: SavedView = sqlx::query_as(
+        "INSERT INTO saved_views (user_id, name, filters)
+         VALUES ($1, $2, $3)
+         RETURNING id, name, filters, created_at",
+    )
+    .bind(auth.user_id)
+    .bind(&payload.name)
+    .bind(&filters)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok((axum::http::StatusCode::CREATED, Json(view_to_response(view)?)))
+}
+
+/// Delete one of the current user's saved views
+#[utoipa::path(
+    delete,
+    path = "/api/views/{id}",
+    params(("id" = Uuid, Path, description = "View ID")),
+    responses(
+        (status = 204, description = "View deleted"),
+        (status = 404, description = "View not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Views"
+)]
+pub async fn delete_view(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<axum::http::StatusCode, AppError> {
+    let result = sqlx::query("DELETE FROM saved_views WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(auth.user_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(codes::VIEW_NOT_FOUND, "Saved view not found".to_string()));
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,116 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::errors::{codes, AppError};
+use crate::handlers::task_handler::visibility_scope;
+use crate::AppState;
+
+/// Makes each user follow the task; following it already is fine
+pub async fn add_watchers(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    user_ids: &[Uuid],
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO task_watchers (task_id, user_id)
+         SELECT $1, unnest($2::uuid[])
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(task_id)
+    .bind(user_ids)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn is_watching(db: &sqlx::PgPool, task_id: Uuid, user_id: Uuid) -> Result<bool, AppError> {
+    let watching: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM task_watchers WHERE task_id = $1 AND user_id = $2)",
+    )
+    .bind(task_id)
+    .bind(user_id)
+    .fetch_one(db)
+    .await?;
+    Ok(watching)
+}
+
+/// Tasks outside the caller's scope, and soft-deleted ones, are reported as missing, like in
+/// the task list. Every endpoint under `/api/tasks/{id}/...` checks this first.
+pub async fn ensure_task_visible(
+    state: &AppState,
+    auth: &AuthUser,
+    task_id: Uuid,
+) -> Result<(), AppError> {
+    let visible: bool = sqlx::query_scalar(
+        "SELECT EXISTS(
+             SELECT 1 FROM tasks
+             WHERE id = $1 AND deleted_at IS NULL
+               AND ($2::uuid IS NULL OR tester_id = $2 OR assigned_by = $2)
+         )",
+    )
+    .bind(task_id)
+    .bind(visibility_scope(auth))
+    .fetch_one(&state.db)
+    .await?;
+
+    if !visible {
+        return Err(AppError::NotFound(codes::TASK_NOT_FOUND, "Task not found".to_string()));
+    }
+    Ok(())
+}
+
+/// Follow a task: its comments and status changes show up in the caller's notifications
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/watch",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 204, description = "Watching the task (also if already watching)"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn watch_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+    add_watchers(&mut *state.db.acquire().await?, task_id, &[auth.user_id]).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Stop following a task, including one the caller created or tests
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{id}/watch",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 204, description = "Not watching the task (also if never watched)"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tasks"
+)]
+pub async fn unwatch_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(task_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    ensure_task_visible(&state, &auth, task_id).await?;
+    sqlx::query("DELETE FROM task_watchers WHERE task_id = $1 AND user_id = $2")
+        .bind(task_id)
+        .bind(auth.user_id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
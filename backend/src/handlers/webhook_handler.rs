@@ -0,0 +1,182 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::auth::AuthUser;
+use crate::dto::{CreateWebhookRequest, UpdateWebhookRequest, WebhookResponse};
+use crate::errors::{codes, AppError};
+use crate::models::{Webhook, WebhookEvent};
+use crate::AppState;
+
+fn require_admin(auth: &AuthUser) -> Result<(), AppError> {
+    if !auth.is_admin() {
+        return Err(AppError::Forbidden(
+            codes::ADMIN_ONLY,
+            "Only administrators can manage webhooks".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn webhook_to_response(w: Webhook) -> WebhookResponse {
+    WebhookResponse {
+        id: w.id,
+        url: w.url,
+        event_types: w.event_types,
+        is_active: w.is_active,
+        created_at: w.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        updated_at: w.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+fn event_names(events: &[WebhookEvent]) -> Vec<String> {
+    let mut names: Vec<String> = events.iter().map(|e| e.to_string()).collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Get all webhook subscriptions (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    responses(
+        (status = 200, description = "List of webhooks", body = Vec<WebhookResponse>),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Webhooks"
+)]
+pub async fn get_webhooks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<WebhookResponse>>, AppError> {
+    require_admin(&auth)?;
+
+    let webhooks: Vec<Webhook> = sqlx::query_as(
+        "SELECT id, url, event_types, is_active, created_at, updated_at
+         FROM webhooks ORDER BY created_at",
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(webhooks.into_iter().map(webhook_to_response).collect()))
+}
+
+/// Subscribe a URL to task events (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created", body = WebhookResponse),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Webhooks"
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<(axum::http::StatusCode, Json<WebhookResponse>), AppError> {
+    require_admin(&auth)?;
+    payload.validate()?;
+
+    let webhook: Webhook = sqlx::query_as(
+        "INSERT INTO webhooks (url, event_types, secret)
+         VALUES ($1, $2, $3)
+         RETURNING id, url, event_types, is_active, created_at, updated_at",
+    )
+    .bind(&payload.url)
+    .bind(event_names(&payload.event_types))
+    .bind(&payload.secret)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(webhook_to_response(webhook)),
+    ))
+}
+
+/// Update a webhook subscription (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/webhooks/{id}",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook updated", body = WebhookResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Webhook not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Webhooks"
+)]
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, AppError> {
+    require_admin(&auth)?;
+    payload.validate()?;
+
+    let webhook: Webhook = sqlx::query_as(
+        "UPDATE webhooks SET url = COALESCE($1, url),
+                             event_types = COALESCE($2, event_types),
+                             secret = COALESCE($3, secret),
+                             is_active = COALESCE($4, is_active),
+                             updated_at = NOW()
+         WHERE id = $5
+         RETURNING id, url, event_types, is_active, created_at, updated_at",
+    )
+    .bind(&payload.url)
+    .bind(payload.event_types.as_deref().map(event_names))
+    .bind(&payload.secret)
+    .bind(payload.is_active)
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(codes::WEBHOOK_NOT_FOUND, "Webhook not found".to_string()))?;
+
+    Ok(Json(webhook_to_response(webhook)))
+}
+
+/// Delete a webhook subscription (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    responses(
+        (status = 204, description = "Webhook deleted"),
+        (status = 404, description = "Webhook not found"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Webhooks"
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<axum::http::StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(codes::WEBHOOK_NOT_FOUND, "Webhook not found".to_string()));
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
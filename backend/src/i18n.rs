@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use axum::{extract::Request, http::header, middleware::Next, response::Response};
+
+/// Language the messages are written in; needs no catalog
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Embedded catalogs: English message -> translation. `{}` in a key matches any text,
+/// which is carried over to the `{}` of the translation in the same order.
+const CATALOG_SOURCES: &[(&str, &str)] = &[("ru", include_str!("../locales/ru.json"))];
+
+struct Catalog {
+    exact: HashMap<String, String>,
+    templates: Vec<(String, String)>,
+}
+
+static CATALOGS: LazyLock<HashMap<&'static str, Catalog>> = LazyLock::new(|| {
+    CATALOG_SOURCES
+        .iter()
+        .map(|(locale, source)| {
+            let entries: HashMap<String, String> = serde_json::from_str(source)
+                .unwrap_or_else(|e| panic!("Invalid message catalog for '{}': {}", locale, e));
+            let (templates, exact): (HashMap<_, _>, HashMap<_, _>) =
+                entries.into_iter().partition(|(key, _)| key.contains("{}"));
+            (*locale, Catalog { exact, templates: templates.into_iter().collect() })
+        })
+        .collect()
+});
+
+tokio::task_local! {
+    static LOCALE: &'static str;
+}
+
+/// Parses the embedded catalogs, so a broken one fails at startup rather than on an error path
+pub fn init() {
+    LazyLock::force(&CATALOGS);
+}
+
+/// Locale negotiated for the current request; the default outside the middleware
+pub fn current() -> &'static str {
+    LOCALE.try_with(|l| *l).unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Best supported language of an `Accept-Language` value, by quality then order
+fn negotiate(accept_language: &str) -> &'static str {
+    let mut ranges: Vec<(&str, f32)> = accept_language
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';').map(str::trim);
+            let tag = parts.next().filter(|t| !t.is_empty())?;
+            let quality = parts
+                .find_map(|p| p.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse().ok())?;
+            Some((tag, quality))
+        })
+        .filter(|(_, q)| *q > 0.0)
+        .collect();
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges
+        .into_iter()
+        .find_map(|(tag, _)| {
+            let language = tag.split('-').next().unwrap_or(tag).to_ascii_lowercase();
+            if language == DEFAULT_LOCALE || language == "*" {
+                return Some(DEFAULT_LOCALE);
+            }
+            CATALOGS.get_key_value(language.as_str()).map(|(locale, _)| *locale)
+        })
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Matches `message` against a `{}` template and fills the translation with the captured parts
+fn fill_template(key: &str, translation: &str, message: &str) -> Option<String> {
+    let mut parts = key.split("{}");
+    let mut rest = message.strip_prefix(parts.next()?)?;
+    let mut args = Vec::new();
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            args.push(rest.strip_suffix(part)?);
+            rest = "";
+        } else {
+            let end = rest.find(part)?;
+            args.push(&rest[..end]);
+            rest = &rest[end + part.len()..];
+        }
+    }
+
+    let mut out = String::new();
+    let mut args = args.into_iter();
+    for (i, piece) in translation.split("{}").enumerate() {
+        if i > 0 {
+            out.push_str(args.next().unwrap_or_default());
+        }
+        out.push_str(piece);
+    }
+    Some(out)
+}
+
+/// `message` in the current request's language, or unchanged if it has no translation
+pub fn translate(message: &str) -> String {
+    let Some(catalog) = CATALOGS.get(current()) else {
+        return message.to_string();
+    };
+    if let Some(translated) = catalog.exact.get(message) {
+        return translated.clone();
+    }
+    catalog
+        .templates
+        .iter()
+        .find_map(|(key, translation)| fill_template(key, translation, message))
+        .unwrap_or_else(|| message.to_string())
+}
+
+/// Picks the response language from `Accept-Language` for the rest of the request
+pub async fn middleware(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(DEFAULT_LOCALE, negotiate);
+
+    LOCALE.scope(locale, next.run(request)).await
+}
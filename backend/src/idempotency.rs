@@ -0,0 +1,123 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::errors::{codes, AppError};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// How long a key keeps returning the original resource
+const KEY_TTL_HOURS: i32 = 24;
+
+/// Optional `Idempotency-Key` header of a create request
+pub struct IdempotencyKey(pub Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for IdempotencyKey {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(IDEMPOTENCY_KEY_HEADER) else {
+            return Ok(Self(None));
+        };
+
+        let key = value
+            .to_str()
+            .ok()
+            .map(str::trim)
+            .filter(|k| !k.is_empty() && k.len() <= 255)
+            .ok_or_else(|| {
+                AppError::BadRequest(
+                    codes::IDEMPOTENCY_KEY_INVALID,
+                    format!(
+                        "{} must be 1-255 visible ASCII characters",
+                        IDEMPOTENCY_KEY_HEADER
+                    ),
+                )
+            })?;
+
+        Ok(Self(Some(key.to_string())))
+    }
+}
+
+/// Fingerprint of a request body, to tell a retry from a different request reusing the key
+pub fn request_hash<T: Serialize>(payload: &T) -> Result<String, AppError> {
+    let body = serde_json::to_vec(payload)
+        .map_err(|e| AppError::Internal(format!("Request serialization failed: {}", e)))?;
+    Ok(format!("{:x}", Sha256::digest(&body)))
+}
+
+/// Resource created earlier with this key, if the key is still live.
+/// A live key sent with a different body is a client bug and yields 409.
+pub async fn find(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    scope: &str,
+    key: &str,
+    request_hash: &str,
+) -> Result<Option<Uuid>, AppError> {
+    let stored: Option<(String, Uuid)> = sqlx::query_as(
+        "SELECT request_hash, resource_id FROM idempotency_keys
+         WHERE user_id = $1 AND scope = $2 AND key = $3 AND expires_at > NOW()",
+    )
+    .bind(user_id)
+    .bind(scope)
+    .bind(key)
+    .fetch_optional(conn)
+    .await?;
+
+    match stored {
+        Some((hash, _)) if hash != request_hash => Err(AppError::Conflict(
+            codes::IDEMPOTENCY_KEY_REUSED,
+            format!(
+                "{} was already used with a different request body",
+                IDEMPOTENCY_KEY_HEADER
+            ),
+        )),
+        Some((_, resource_id)) => Ok(Some(resource_id)),
+        None => Ok(None),
+    }
+}
+
+/// Records the key in the transaction that created `resource_id`. If a concurrent
+/// request with the same key committed first, this fails with 409 and the caller's
+/// transaction (and its duplicate) is rolled back.
+pub async fn save(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    scope: &str,
+    key: &str,
+    request_hash: &str,
+    resource_id: Uuid,
+) -> Result<(), AppError> {
+    // An expired row may linger until cleanup; it is simply taken over
+    let saved = sqlx::query(
+        "INSERT INTO idempotency_keys (user_id, scope, key, request_hash, resource_id, expires_at)
+         VALUES ($1, $2, $3, $4, $5, NOW() + make_interval(hours => $6))
+         ON CONFLICT (user_id, scope, key) DO UPDATE
+             SET request_hash = EXCLUDED.request_hash,
+                 resource_id = EXCLUDED.resource_id,
+                 created_at = NOW(),
+                 expires_at = EXCLUDED.expires_at
+             WHERE idempotency_keys.expires_at <= NOW()",
+    )
+    .bind(user_id)
+    .bind(scope)
+    .bind(key)
+    .bind(request_hash)
+    .bind(resource_id)
+    .bind(KEY_TTL_HOURS)
+    .execute(conn)
+    .await?;
+
+    if saved.rows_affected() == 0 {
+        return Err(AppError::Conflict(
+            codes::IDEMPOTENCY_KEY_IN_USE,
+            format!(
+                "A request with this {} was processed concurrently; retry to get its result",
+                IDEMPOTENCY_KEY_HEADER
+            ),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::dto::TaskResponse;
+use crate::models::WebhookEvent;
+
+/// Recent events kept for clients that reconnect with `last_event_id`
+const REPLAY_BUFFER: usize = 256;
+
+/// A task change as pushed to `/ws/tasks` clients (before per-client filtering)
+#[derive(Debug, Serialize)]
+pub struct LiveEvent {
+    /// Increases with every event, also across restarts (see `LiveTasks::new`)
+    pub id: u64,
+    pub event: WebhookEvent,
+    pub occurred_at: String,
+    pub task: TaskResponse,
+}
+
+/// What a new subscriber starts from
+pub struct Subscription {
+    /// Buffered events after the client's `last_event_id`, oldest first
+    pub replay: Vec<Arc<LiveEvent>>,
+    /// False when events after `last_event_id` have already left the buffer, so the
+    /// client has to reload instead of relying on the replay
+    pub complete: bool,
+    pub receiver: broadcast::Receiver<Arc<LiveEvent>>,
+}
+
+struct Buffer {
+    next_id: u64,
+    recent: VecDeque<Arc<LiveEvent>>,
+}
+
+/// Fan-out of task events to live subscribers, with a short replay buffer
+#[derive(Clone)]
+pub struct LiveTasks {
+    sender: broadcast::Sender<Arc<LiveEvent>>,
+    buffer: Arc<Mutex<Buffer>>,
+}
+
+impl LiveTasks {
+    /// Ids start at the current Unix time in milliseconds, so a client holding an id from
+    /// before a restart is told to resync instead of silently missing events.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(REPLAY_BUFFER);
+        let next_id = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        Self {
+            sender,
+            buffer: Arc::new(Mutex::new(Buffer {
+                next_id,
+                recent: VecDeque::with_capacity(REPLAY_BUFFER),
+            })),
+        }
+    }
+
+    pub fn publish(&self, event: WebhookEvent, task: TaskResponse) {
+        // Ids are assigned and sent under the lock, so subscribers see them in order
+        let mut buffer = self.buffer.lock().expect("live buffer poisoned");
+        let event = Arc::new(LiveEvent {
+            id: buffer.next_id,
+            event,
+            occurred_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            task,
+        });
+        buffer.next_id += 1;
+        if buffer.recent.len() == REPLAY_BUFFER {
+            buffer.recent.pop_front();
+        }
+        buffer.recent.push_back(event.clone());
+        // No receivers is fine: nobody is listening right now
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> Subscription {
+        let buffer = self.buffer.lock().expect("live buffer poisoned");
+        let receiver = self.sender.subscribe();
+
+        let Some(last) = last_event_id else {
+            return Subscription {
+                replay: Vec::new(),
+                complete: true,
+                receiver,
+            };
+        };
+
+        let oldest = buffer.recent.front().map_or(buffer.next_id, |e| e.id);
+        Subscription {
+            replay: buffer
+                .recent
+                .iter()
+                .filter(|e| e.id > last)
+                .cloned()
+                .collect(),
+            complete: last + 1 >= oldest && last < buffer.next_id,
+            receiver,
+        }
+    }
+}
@@ -2,66 +2,262 @@ mod auth;
 mod config;
 mod dto;
 mod errors;
+mod etag;
+mod export;
 mod handlers;
+mod i18n;
+mod idempotency;
+mod live;
 mod models;
+mod notifications;
+mod notify;
+mod password;
+mod rate_limit;
+mod request_id;
+mod scheduler;
+mod telemetry;
+mod timeout;
+#[cfg(test)]
+mod test_support;
+mod totp;
+mod user_names;
+mod webhooks;
 
 use axum::{
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    handler::Handler,
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use sqlx::PgPool;
-use tower_http::cors::{Any, CorsLayer};
+use axum::http::{header, HeaderValue};
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowHeaders, AllowMethods, Any, CorsLayer};
+use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::trace::TraceLayer;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::config::AppConfig;
-use crate::handlers::{auth_handler, task_handler, user_handler};
+use crate::notify::Mailer;
+use crate::live::LiveTasks;
+use crate::notifications::NotificationHub;
+use crate::rate_limit::LoginRateLimiter;
+use crate::request_id::REQUEST_ID_HEADER;
+use crate::handlers::{
+    attachment_handler, auth_handler, checklist_handler, comment_handler, dependency_handler, health_handler, history_handler,
+    live_handler, notification_handler, sync_handler, tag_handler, task_handler, team_handler, template_handler, time_entry_handler, two_factor_handler,
+    user_export_handler, user_handler, user_history_handler, view_handler, watcher_handler, webhook_handler,
+};
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
     pub config: AppConfig,
+    pub http: reqwest::Client,
+    pub login_limiter: LoginRateLimiter,
+    pub mailer: Option<Mailer>,
+    pub live: LiveTasks,
+    pub notifications: NotificationHub,
 }
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
+        health_handler::health,
+        health_handler::ready,
         auth_handler::login,
+        auth_handler::jwks,
+        auth_handler::refresh,
+        auth_handler::logout,
+        auth_handler::forgot_password,
+        auth_handler::reset_password,
         user_handler::get_users,
         user_handler::get_user,
         user_handler::get_me,
+        user_handler::update_me,
+        user_handler::revoke_user_sessions,
+        two_factor_handler::enable_two_factor,
+        two_factor_handler::verify_two_factor,
         user_handler::create_user,
+        user_handler::import_users,
+        user_history_handler::get_user_history,
+        user_export_handler::export_user_data,
         user_handler::update_user,
         user_handler::delete_user,
         task_handler::get_tasks,
+        live_handler::tasks_socket,
+        task_handler::get_my_tasks,
+        task_handler::get_task_summary,
+        task_handler::get_overdue_tasks,
+        task_handler::export_tasks,
         task_handler::get_task,
+        task_handler::get_task_by_number,
         task_handler::create_task,
+        task_handler::clone_task,
         task_handler::update_task,
+        task_handler::assign_task,
+        task_handler::reopen_task,
+        task_handler::mark_duplicate,
+        task_handler::unmark_duplicate,
+        task_handler::bulk_update_status,
         task_handler::delete_task,
+        task_handler::restore_task,
+        history_handler::get_task_history,
+        dependency_handler::add_dependency,
+        dependency_handler::remove_dependency,
+        tag_handler::add_tag,
+        tag_handler::remove_tag,
+        checklist_handler::get_checklist,
+        checklist_handler::add_checklist_item,
+        checklist_handler::update_checklist_item,
+        checklist_handler::reorder_checklist,
+        checklist_handler::delete_checklist_item,
+        watcher_handler::watch_task,
+        watcher_handler::unwatch_task,
+        time_entry_handler::get_time_entries,
+        time_entry_handler::log_time,
         task_handler::get_employee_stats,
+        task_handler::export_employee_stats,
+        task_handler::get_urgency_stats,
+        time_entry_handler::get_time_stats,
+        task_handler::get_timeseries_stats,
+        comment_handler::get_comments,
+        comment_handler::create_comment,
+        comment_handler::delete_comment,
+        attachment_handler::get_attachments,
+        attachment_handler::upload_attachment,
+        attachment_handler::download_attachment,
+        view_handler::get_views,
+        view_handler::create_view,
+        view_handler::delete_view,
+        task_handler::get_view_tasks,
+        template_handler::get_templates,
+        template_handler::get_template,
+        template_handler::create_template,
+        template_handler::update_template,
+        template_handler::delete_template,
+        template_handler::instantiate_template,
+        webhook_handler::get_webhooks,
+        webhook_handler::create_webhook,
+        webhook_handler::update_webhook,
+        webhook_handler::delete_webhook,
+        notification_handler::get_notifications,
+        notification_handler::mark_notification_read,
+        notification_handler::mark_all_notifications_read,
+        notification_handler::notification_stream,
+        team_handler::get_teams,
+        team_handler::get_team,
+        team_handler::create_team,
+        team_handler::update_team,
+        team_handler::delete_team,
+        team_handler::add_team_member,
+        team_handler::remove_team_member,
+        sync_handler::sync_tasks,
     ),
     components(schemas(
+        dto::HealthResponse,
         dto::LoginRequest,
         dto::LoginResponse,
+        dto::RefreshRequest,
+        dto::RefreshResponse,
+        dto::LogoutRequest,
+        dto::ForgotPasswordRequest,
+        dto::ResetPasswordRequest,
+        dto::MessageResponse,
         dto::UserResponse,
+        dto::UserFilterParams,
+        dto::TwoFactorSetupResponse,
+        dto::VerifyTwoFactorRequest,
+        dto::RecoveryCodesResponse,
+        dto::UserSortField,
         dto::CreateUserRequest,
+        dto::ImportUserRow,
+        dto::UserImportMode,
+        dto::UserImportResult,
+        dto::UserEventResponse,
         dto::UpdateUserRequest,
+        dto::UpdateProfileRequest,
         dto::TaskResponse,
         dto::TaskListItem,
+        dto::OverdueTaskItem,
         dto::CreateTaskRequest,
         dto::UpdateTaskRequest,
+        dto::AssignTaskRequest,
+        dto::ReopenTaskRequest,
+        dto::MarkDuplicateParams,
+        dto::BulkStatusUpdateRequest,
+        dto::BulkStatusUpdateResult,
+        dto::TaskEventResponse,
+        dto::AddTagRequest,
+        dto::AddDependencyRequest,
+        dto::TagMatch,
+        dto::AddChecklistItemRequest,
+        dto::UpdateChecklistItemRequest,
+        dto::ReorderChecklistRequest,
+        dto::ChecklistItemResponse,
+        dto::CreateCommentRequest,
+        dto::CommentResponse,
+        dto::AttachmentResponse,
+        dto::LogTimeRequest,
+        dto::TimeEntryResponse,
         dto::EmployeeStats,
+        dto::EmployeeStatsParams,
+        dto::EmployeeTimeStats,
+        dto::UrgencyBreakdown,
+        dto::TimeBucket,
+        dto::TimeseriesPoint,
+        dto::TaskSummaryResponse,
+        dto::StatusCounts,
+        dto::UrgencyCounts,
+        dto::TaskFilterParams,
+        dto::MyTaskRole,
+        dto::MyTasksParams,
+        dto::TaskSortField,
+        dto::SortOrder,
+        dto::CreateViewRequest,
+        dto::SavedViewResponse,
+        dto::CreateTemplateRequest,
+        dto::UpdateTemplateRequest,
+        dto::TemplateResponse,
+        dto::CreateWebhookRequest,
+        dto::UpdateWebhookRequest,
+        dto::WebhookResponse,
+        dto::CreateTeamRequest,
+        dto::UpdateTeamRequest,
+        dto::AddTeamMemberRequest,
+        dto::TeamResponse,
+        dto::SyncTasksParams,
+        dto::SyncChange,
+        dto::SyncTask,
+        dto::SyncTaskChange,
+        dto::SyncTasksPage,
+        dto::NotificationResponse,
+        dto::NotificationFilterParams,
+        dto::NotificationListResponse,
+        dto::UnreadCountResponse,
         models::UserRole,
         models::TaskStatus,
         models::TaskUrgency,
+        models::WebhookEvent,
+        models::NotificationKind,
+        models::TemplateRecurrence,
     )),
     modifiers(&SecurityAddon),
     tags(
+        (name = "Health", description = "Liveness and readiness probes"),
         (name = "Authentication", description = "Login and token management"),
         (name = "Users", description = "User CRUD (admin only)"),
         (name = "Tasks", description = "Task management"),
-        (name = "Statistics", description = "Employee statistics (manager/admin)")
+        (name = "Comments", description = "Task discussion threads"),
+        (name = "Attachments", description = "Files attached to tasks"),
+        (name = "Statistics", description = "Employee statistics (manager/admin)"),
+        (name = "Views", description = "Saved task filter views"),
+        (name = "Webhooks", description = "Outbound task event subscriptions (admin only)"),
+        (name = "Teams", description = "User teams for task filtering and statistics"),
+        (name = "Notifications", description = "Per-user notifications about tasks"),
+        (name = "Task templates", description = "Reusable and recurring tasks (manager/admin)"),
+        (name = "Sync", description = "Incremental task change feed (sync accounts only)")
     ),
     info(
         title = "TestFlow API",
@@ -88,76 +284,303 @@ impl utoipa::Modify for SecurityAddon {
     }
 }
 
+/// OpenAPI document with the deployment-specific server, contact and license.
+/// Without `PUBLIC_BASE_URL` no server is listed and Swagger UI calls the API relative to itself.
+fn api_doc(config: &AppConfig) -> utoipa::openapi::OpenApi {
+    use utoipa::openapi::{ContactBuilder, LicenseBuilder, Server};
+
+    let mut doc = ApiDoc::openapi();
+    if let Some(url) = &config.public_base_url {
+        doc.servers = Some(vec![Server::new(url)]);
+    }
+    if config.api_contact_name.is_some() || config.api_contact_email.is_some() {
+        doc.info.contact = Some(
+            ContactBuilder::new()
+                .name(config.api_contact_name.clone())
+                .email(config.api_contact_email.clone())
+                .build(),
+        );
+    }
+    if let Some(name) = &config.api_license_name {
+        doc.info.license = Some(
+            LicenseBuilder::new()
+                .name(name)
+                .url(config.api_license_url.clone())
+                .build(),
+        );
+    }
+    doc
+}
+
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().ok();
 
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "testflow=debug,tower_http=debug".parse().unwrap()),
-        )
-        .init();
+    let tracer_provider = telemetry::init();
 
     let db = config::create_db_pool().await;
     let app_config = config::load_config();
+    i18n::init();
 
     // Run migrations
     tracing::info!("Running database migrations...");
     run_migrations(&db).await;
     tracing::info!("Migrations completed.");
 
-    // Seed default admin if no users exist
-    seed_admin(&db).await;
+    // Seed the first admin if no users exist
+    seed_admin(&db, &app_config).await;
+
+    auth::spawn_token_cleanup(db.clone());
 
     let state = AppState {
+        login_limiter: LoginRateLimiter::new(
+            app_config.login_max_failures,
+            std::time::Duration::from_secs(app_config.login_failure_window_secs),
+        ),
+        mailer: Mailer::from_config(&app_config),
         db,
         config: app_config,
+        http: reqwest::Client::new(),
+        live: LiveTasks::new(),
+        notifications: NotificationHub::default(),
     };
 
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
+    scheduler::spawn_template_scheduler(state.clone());
+
+    let cors = build_cors_layer(&state.config);
+    // Streamed bodies (CSV export, attachments) are compressed chunk by chunk as they are produced
+    let compression = CompressionLayer::new()
+        .gzip(state.config.compression_enabled)
+        .br(state.config.compression_enabled);
+    let db = state.db.clone();
 
     let app = Router::new()
+        // Health probes (no auth)
+        .route("/health", get(health_handler::health))
+        .route("/health/ready", get(health_handler::ready))
         // Auth
+        .route("/.well-known/jwks.json", get(auth_handler::jwks))
         .route("/api/auth/login", post(auth_handler::login))
+        .route("/api/auth/refresh", post(auth_handler::refresh))
+        .route("/api/auth/logout", post(auth_handler::logout))
+        .route("/api/auth/forgot-password", post(auth_handler::forgot_password))
+        .route("/api/auth/reset-password", post(auth_handler::reset_password))
         // Users
         .route(
             "/api/users",
             get(user_handler::get_users).post(user_handler::create_user),
         )
-        .route("/api/users/me", get(user_handler::get_me))
+        .route("/api/users/import", post(user_handler::import_users))
+        .route(
+            "/api/users/me",
+            get(user_handler::get_me).patch(user_handler::update_me),
+        )
+        .route(
+            "/api/users/me/2fa/enable",
+            post(two_factor_handler::enable_two_factor),
+        )
+        .route(
+            "/api/users/me/2fa/verify",
+            post(two_factor_handler::verify_two_factor),
+        )
         .route(
             "/api/users/{id}",
             get(user_handler::get_user)
                 .put(user_handler::update_user)
                 .delete(user_handler::delete_user),
         )
+        .route(
+            "/api/users/{id}/history",
+            get(user_history_handler::get_user_history),
+        )
+        .route(
+            "/api/users/{id}/export",
+            get(user_export_handler::export_user_data),
+        )
+        .route(
+            "/api/users/{id}/revoke-sessions",
+            post(user_handler::revoke_user_sessions),
+        )
         // Tasks
         .route(
             "/api/tasks",
             get(task_handler::get_tasks).post(task_handler::create_task),
         )
+        .route("/api/tasks/mine", get(task_handler::get_my_tasks))
+        .route("/ws/tasks", get(live_handler::tasks_socket))
+        .route("/api/tasks/summary", get(task_handler::get_task_summary))
+        .route("/api/tasks/overdue", get(task_handler::get_overdue_tasks))
+        .route(
+            "/api/tasks/by-number/{task_number}",
+            get(task_handler::get_task_by_number),
+        )
+        .route("/api/tasks/export", get(task_handler::export_tasks))
+        .route("/api/tasks/bulk", patch(task_handler::bulk_update_status))
         .route(
             "/api/tasks/{id}",
             get(task_handler::get_task)
                 .put(task_handler::update_task)
                 .delete(task_handler::delete_task),
         )
+        .route("/api/tasks/{id}/assign", patch(task_handler::assign_task))
+        .route("/api/tasks/{id}/reopen", post(task_handler::reopen_task))
+        .route("/api/tasks/{id}/clone", post(task_handler::clone_task))
+        .route("/api/tasks/{id}/duplicate-of", delete(task_handler::unmark_duplicate))
+        .route(
+            "/api/tasks/{id}/duplicate-of/{other_id}",
+            post(task_handler::mark_duplicate),
+        )
+        .route("/api/tasks/{id}/restore", post(task_handler::restore_task))
+        .route("/api/tasks/{id}/history", get(history_handler::get_task_history))
+        .route("/api/tasks/{id}/dependencies", post(dependency_handler::add_dependency))
+        .route(
+            "/api/tasks/{id}/dependencies/{depends_on_id}",
+            delete(dependency_handler::remove_dependency),
+        )
+        .route("/api/tasks/{id}/tags", post(tag_handler::add_tag))
+        .route("/api/tasks/{id}/tags/{tag}", delete(tag_handler::remove_tag))
+        .route(
+            "/api/tasks/{id}/checklist",
+            get(checklist_handler::get_checklist).post(checklist_handler::add_checklist_item),
+        )
+        .route(
+            "/api/tasks/{id}/checklist/order",
+            put(checklist_handler::reorder_checklist),
+        )
+        .route(
+            "/api/tasks/{id}/checklist/{item_id}",
+            patch(checklist_handler::update_checklist_item)
+                .delete(checklist_handler::delete_checklist_item),
+        )
+        .route(
+            "/api/tasks/{id}/watch",
+            post(watcher_handler::watch_task).delete(watcher_handler::unwatch_task),
+        )
+        .route(
+            "/api/tasks/{id}/time",
+            get(time_entry_handler::get_time_entries).post(time_entry_handler::log_time),
+        )
+        .route(
+            "/api/tasks/{id}/comments",
+            get(comment_handler::get_comments).post(comment_handler::create_comment),
+        )
+        .route(
+            "/api/tasks/{id}/comments/{comment_id}",
+            delete(comment_handler::delete_comment),
+        )
+        .route("/api/tasks/{id}/attachments", get(attachment_handler::get_attachments))
+        .route(
+            "/api/tasks/{id}/attachments/{attachment_id}",
+            get(attachment_handler::download_attachment),
+        )
         // Statistics
         .route(
             "/api/statistics/employees",
             get(task_handler::get_employee_stats),
         )
+        .route(
+            "/api/statistics/employees/export",
+            get(task_handler::export_employee_stats),
+        )
+        .route(
+            "/api/statistics/urgency",
+            get(task_handler::get_urgency_stats),
+        )
+        .route("/api/statistics/time", get(time_entry_handler::get_time_stats))
+        .route(
+            "/api/statistics/timeseries",
+            get(task_handler::get_timeseries_stats),
+        )
+        // Saved views
+        .route(
+            "/api/views",
+            get(view_handler::get_views).post(view_handler::create_view),
+        )
+        .route("/api/views/{id}", delete(view_handler::delete_view))
+        .route("/api/views/{id}/tasks", get(task_handler::get_view_tasks))
+        // Task templates
+        .route(
+            "/api/task-templates",
+            get(template_handler::get_templates).post(template_handler::create_template),
+        )
+        .route(
+            "/api/task-templates/{id}",
+            get(template_handler::get_template)
+                .put(template_handler::update_template)
+                .delete(template_handler::delete_template),
+        )
+        .route(
+            "/api/task-templates/{id}/instantiate",
+            post(template_handler::instantiate_template),
+        )
+        // Webhooks
+        .route(
+            "/api/webhooks",
+            get(webhook_handler::get_webhooks).post(webhook_handler::create_webhook),
+        )
+        .route(
+            "/api/webhooks/{id}",
+            put(webhook_handler::update_webhook).delete(webhook_handler::delete_webhook),
+        )
+        // Teams
+        .route(
+            "/api/teams",
+            get(team_handler::get_teams).post(team_handler::create_team),
+        )
+        .route(
+            "/api/teams/{id}",
+            get(team_handler::get_team)
+                .put(team_handler::update_team)
+                .delete(team_handler::delete_team),
+        )
+        .route("/api/teams/{id}/members", post(team_handler::add_team_member))
+        .route(
+            "/api/teams/{id}/members/{user_id}",
+            delete(team_handler::remove_team_member),
+        )
+        // Sync
+        .route("/api/sync/tasks", get(sync_handler::sync_tasks))
+        // Notifications
+        .route("/api/notifications", get(notification_handler::get_notifications))
+        .route(
+            "/api/notifications/read-all",
+            post(notification_handler::mark_all_notifications_read),
+        )
+        .route(
+            "/api/notifications/{id}/read",
+            post(notification_handler::mark_notification_read),
+        )
+        .route(
+            "/api/notifications/stream",
+            get(notification_handler::notification_stream),
+        )
+        // Routes above these layers reject bodies over REQUEST_BODY_MAX_SIZE_BYTES and
+        // time out after REQUEST_TIMEOUT_SECS; uploads of large files are exempt from both
+        .layer(RequestBodyLimitLayer::new(state.config.request_body_max_size_bytes))
+        .layer(axum::middleware::from_fn_with_state(state.clone(), timeout::middleware))
+        .route(
+            "/api/tasks/{id}/attachments",
+            post(
+                attachment_handler::upload_attachment
+                    // The handler enforces ATTACHMENT_MAX_SIZE_BYTES while streaming
+                    .layer(DefaultBodyLimit::disable()),
+            ),
+        )
         // Swagger UI
         .merge(
             SwaggerUi::new("/swagger-ui")
-                .url("/api-docs/openapi.json", ApiDoc::openapi())
+                .url("/api-docs/openapi.json", api_doc(&state.config))
         )
+        .layer(compression)
         .layer(cors)
-        .layer(TraceLayer::new_for_http())
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(telemetry::request_span)
+                .on_response(telemetry::record_response),
+        )
+        .layer(axum::middleware::from_fn(i18n::middleware))
+        // Outermost, so the id is set before the trace span and on every response
+        .layer(axum::middleware::from_fn(request_id::middleware))
         .with_state(state);
 
     let addr = "0.0.0.0:3000";
@@ -165,52 +588,177 @@ async fn main() {
     tracing::info!("Swagger UI: http://localhost:3000/swagger-ui/");
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    // Peer address is needed by the login rate limiter
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await
+    .unwrap();
+
+    // In-flight requests have finished; release the DB connections
+    db.close().await;
+    if let Some(provider) = tracer_provider
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!("Failed to flush traces: {}", e);
+    }
+    tracing::info!("Shutdown complete.");
 }
 
+/// Resolves on Ctrl+C (SIGINT) or SIGTERM, after which the server stops
+/// accepting connections and waits for active requests.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutdown signal received, draining in-flight requests...");
+}
+
+/// Versioned migrations from `migrations/` (`NNN_name.sql`), embedded at build time.
+/// Applied ones are recorded in `_sqlx_migrations` with a checksum, so each runs once, in
+/// order, and an applied file must never be edited: schema changes go in a new file.
+static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!();
+
 async fn run_migrations(db: &PgPool) {
-    // Split migration file into individual statements and execute them
-    let migration_sql = include_str!("../migrations/001_init.sql");
-    
-    // Execute the entire migration as a simple query (not prepared statement)
-    sqlx::raw_sql(migration_sql)
-        .execute(db)
+    // Schema changes may legitimately run past DB_STATEMENT_TIMEOUT_SECS, so they get
+    // their own unbounded session, which is closed afterwards rather than pooled
+    let mut conn = db.acquire().await.expect("Failed to acquire a connection for migrations");
+    conn.close_on_drop();
+    sqlx::raw_sql("SET statement_timeout = 0")
+        .execute(&mut *conn)
         .await
         .expect("Failed to run migrations");
+
+    // Holds an advisory lock, so replicas starting together apply them one at a time
+    MIGRATOR.run(&mut *conn).await.expect("Failed to run migrations");
 }
 
-async fn seed_admin(db: &PgPool) {
-    let count: Option<i64> =
-        sqlx::query_scalar("SELECT COUNT(*) FROM users")
-            .fetch_one(db)
-            .await
-            .unwrap_or(Some(0));
+fn build_cors_layer(config: &AppConfig) -> CorsLayer {
+    match &config.cors_allowed_origins {
+        Some(origins) => {
+            let origins: Vec<HeaderValue> = origins
+                .iter()
+                .map(|o| {
+                    o.parse()
+                        .unwrap_or_else(|_| panic!("Invalid origin in CORS_ALLOWED_ORIGINS: {}", o))
+                })
+                .collect();
+            tracing::info!("CORS restricted to {} origin(s)", origins.len());
 
-    if count.unwrap_or(0) == 0 {
-        tracing::info!("No users found. Creating default admin...");
+            // Wildcards are not allowed together with credentials, so mirror the preflight instead
+            CorsLayer::new()
+                .allow_origin(origins)
+                .allow_methods(AllowMethods::mirror_request())
+                .allow_headers(AllowHeaders::mirror_request())
+                .allow_credentials(true)
+                .expose_headers([REQUEST_ID_HEADER.clone(), header::LOCATION, header::ETAG])
+        }
+        None => CorsLayer::new()
+            .allow_origin(Any)
+            .allow_methods(Any)
+            .allow_headers(Any)
+            .expose_headers([REQUEST_ID_HEADER.clone(), header::LOCATION, header::ETAG]),
+    }
+}
 
-        use argon2::PasswordHasher;
-        let salt = argon2::password_hash::SaltString::generate(
-            &mut argon2::password_hash::rand_core::OsRng,
-        );
-        let password_hash = argon2::Argon2::default()
-            .hash_password(b"admin123", &salt)
-            .expect("Failed to hash password")
-            .to_string();
-
-        sqlx::query(
-            "INSERT INTO users (username, email, password_hash, full_name, role)
-             VALUES ($1, $2, $3, $4, $5::user_role)",
-        )
-        .bind("admin")
-        .bind("admin@testflow.local")
-        .bind(&password_hash)
-        .bind("System Administrator")
-        .bind("admin")
-        .execute(db)
+/// Arbitrary key of the advisory lock serializing `seed_admin` across instances
+const SEED_ADMIN_LOCK_KEY: i64 = 0x7465_7374_666c_6f77;
+
+/// Creates the first administrator when there are no users. The password comes from
+/// `SEED_ADMIN_PASSWORD`; the well-known `admin123` is only used with `ALLOW_DEFAULT_ADMIN=true`.
+async fn seed_admin(db: &PgPool, config: &AppConfig) {
+    // Replicas booting together would all see an empty table and race to insert. The
+    // check and the insert share a transaction holding an advisory lock, so the others
+    // wait for the first one to commit and then find its admin.
+    let mut tx = db.begin().await.expect("Failed to start the admin seed transaction");
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(SEED_ADMIN_LOCK_KEY)
+        .execute(&mut *tx)
         .await
-        .expect("Failed to create default admin");
+        .expect("Failed to take the admin seed lock");
 
-        tracing::info!("Default admin created: username='admin', password='admin123'");
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut *tx)
+        .await
+        .expect("Failed to count users");
+
+    if count != 0 {
+        return;
     }
+
+    let username = std::env::var("SEED_ADMIN_USERNAME").unwrap_or_else(|_| "admin".to_string());
+    let email =
+        std::env::var("SEED_ADMIN_EMAIL").unwrap_or_else(|_| "admin@testflow.local".to_string());
+    let allow_default: bool = std::env::var("ALLOW_DEFAULT_ADMIN")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse()
+        .expect("ALLOW_DEFAULT_ADMIN must be true or false");
+
+    let password = match std::env::var("SEED_ADMIN_PASSWORD").ok().filter(|p| !p.is_empty()) {
+        Some(password) => {
+            if let Err(e) = password::validate_password_strength(
+                &config.password_policy,
+                "SEED_ADMIN_PASSWORD",
+                &password,
+            ) {
+                panic!("SEED_ADMIN_PASSWORD does not meet the password policy: {}", e);
+            }
+            tracing::info!("No users found. Creating admin '{}' from SEED_ADMIN_PASSWORD", username);
+            password
+        }
+        None if allow_default => {
+            tracing::warn!(
+                "No users found. Creating admin '{}' with the default password 'admin123' \
+                 (ALLOW_DEFAULT_ADMIN=true); do not use this outside development",
+                username
+            );
+            "admin123".to_string()
+        }
+        None => {
+            tracing::warn!(
+                "No users found, but no admin was created: set SEED_ADMIN_PASSWORD \
+                 (or ALLOW_DEFAULT_ADMIN=true for a development admin123)"
+            );
+            return;
+        }
+    };
+
+    let password_hash = password::hash_password(config, &password).expect("Failed to hash password");
+
+    sqlx::query(
+        "INSERT INTO users (username, email, password_hash, full_name, role)
+         VALUES ($1, $2, $3, $4, $5::user_role)",
+    )
+    .bind(&username)
+    .bind(&email)
+    .bind(&password_hash)
+    .bind("System Administrator")
+    .bind("admin")
+    .execute(&mut *tx)
+    .await
+    .expect("Failed to create the initial admin");
+    tx.commit().await.expect("Failed to create the initial admin");
+
+    tracing::info!("Admin '{}' created", username);
 }
@@ -21,6 +21,10 @@ pub enum UserRole {
     #[sqlx(rename = "developer")]
     #[serde(rename = "developer")]
     Developer,
+    /// Service account limited to the sync feed
+    #[sqlx(rename = "sync")]
+    #[serde(rename = "sync")]
+    Sync,
 }
 
 impl std::fmt::Display for UserRole {
@@ -30,6 +34,22 @@ impl std::fmt::Display for UserRole {
             UserRole::Manager => write!(f, "manager"),
             UserRole::Tester => write!(f, "tester"),
             UserRole::Developer => write!(f, "developer"),
+            UserRole::Sync => write!(f, "sync"),
+        }
+    }
+}
+
+impl std::str::FromStr for UserRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(UserRole::Admin),
+            "manager" => Ok(UserRole::Manager),
+            "tester" => Ok(UserRole::Tester),
+            "developer" => Ok(UserRole::Developer),
+            "sync" => Ok(UserRole::Sync),
+            other => Err(format!("Unknown role: {}", other)),
         }
     }
 }
@@ -48,6 +68,7 @@ pub struct User {
     pub is_active: bool,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub last_login_at: Option<NaiveDateTime>,
 }
 
 // ── Task urgency ──
@@ -114,6 +135,33 @@ impl std::fmt::Display for TaskStatus {
     }
 }
 
+impl TaskStatus {
+    /// Allowed workflow edges. Staying in the same status is always allowed.
+    ///
+    /// new → in_progress → testing → done → closed, plus the way back:
+    /// in_progress → new, testing → in_progress (failed test),
+    /// done/closed → in_progress (reopen).
+    pub fn can_transition_to(&self, next: &TaskStatus) -> bool {
+        use TaskStatus::*;
+
+        if self == next {
+            return true;
+        }
+
+        matches!(
+            (self, next),
+            (New, InProgress)
+                | (InProgress, New)
+                | (InProgress, Testing)
+                | (Testing, InProgress)
+                | (Testing, Done)
+                | (Done, Closed)
+                | (Done, InProgress)
+                | (Closed, InProgress)
+        )
+    }
+}
+
 // ── Task ──
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
@@ -131,4 +179,303 @@ pub struct Task {
     pub acceptance_criteria: Option<String>,
     pub evaluation_criteria: Option<String>,
     pub comment: Option<String>,
+    pub due_date: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub duplicate_of: Option<Uuid>,
+    pub updated_at: NaiveDateTime,
+    pub estimate_hours: Option<f64>,
+}
+
+/// Task row joined with the assigner's and tester's display names
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskWithNames {
+    #[sqlx(flatten)]
+    pub task: Task,
+    pub assigned_by_name: Option<String>,
+    pub tester_name: Option<String>,
+    pub tags: Vec<String>,
+    pub blocked_by: Vec<Uuid>,
+    pub blocks: Vec<Uuid>,
+    /// Sum of the task's time entries
+    pub logged_hours: f64,
+    /// Checklist items in display order
+    pub checklist: sqlx::types::Json<Vec<ChecklistItem>>,
+}
+
+// ── Task comment ──
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskComment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub author_id: Uuid,
+    pub author_name: Option<String>,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+}
+
+// ── Checklist item ──
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChecklistItem {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub text: String,
+    pub done: bool,
+    pub position: i32,
+}
+
+// ── Time entry ──
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: Option<String>,
+    pub hours: f64,
+    pub note: Option<String>,
+    pub logged_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+// ── Task template ──
+
+/// How often the scheduler creates a task from a template
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "template_recurrence", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateRecurrence {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl TemplateRecurrence {
+    /// The run after `at`; a monthly run on the 31st falls back to the month's last day
+    pub fn next_after(self, at: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            TemplateRecurrence::Daily => at + chrono::Duration::days(1),
+            TemplateRecurrence::Weekly => at + chrono::Duration::weeks(1),
+            TemplateRecurrence::Monthly => at
+                .checked_add_months(chrono::Months::new(1))
+                .expect("next month is within chrono's range"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskTemplate {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub urgency: TaskUrgency,
+    pub acceptance_criteria: Option<String>,
+    pub evaluation_criteria: Option<String>,
+    pub tester_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub recurrence: Option<TemplateRecurrence>,
+    pub next_run_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+// ── Task attachment ──
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskAttachment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub uploaded_by_name: Option<String>,
+    pub filename: String,
+    pub content_type: String,
+    pub size: i64,
+    pub storage_key: Uuid,
+    pub created_at: NaiveDateTime,
+}
+
+// ── Task event ──
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskEvent {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+// ── User event ──
+
+#[derive(Debug, Clone, FromRow)]
+pub struct UserEvent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+// ── Saved view ──
+
+#[derive(Debug, Clone, FromRow)]
+pub struct SavedView {
+    pub id: Uuid,
+    pub name: String,
+    pub filters: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+// ── Notification ──
+
+/// What a notification is about
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "notification_kind")]
+pub enum NotificationKind {
+    /// The recipient became the task's tester
+    #[sqlx(rename = "task_assigned")]
+    #[serde(rename = "task_assigned")]
+    Assigned,
+    /// Someone commented on a task the recipient watches
+    #[sqlx(rename = "task_commented")]
+    #[serde(rename = "task_commented")]
+    Commented,
+    /// A task the recipient watches was reopened
+    #[sqlx(rename = "task_reopened")]
+    #[serde(rename = "task_reopened")]
+    Reopened,
+    /// A task the recipient watches moved to another status
+    #[sqlx(rename = "task_status_changed")]
+    #[serde(rename = "task_status_changed")]
+    StatusChanged,
+}
+
+impl std::fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationKind::Assigned => write!(f, "task_assigned"),
+            NotificationKind::Commented => write!(f, "task_commented"),
+            NotificationKind::Reopened => write!(f, "task_reopened"),
+            NotificationKind::StatusChanged => write!(f, "task_status_changed"),
+        }
+    }
+}
+
+/// The `payload` column: what the recipient needs to know about the task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPayload {
+    pub task_id: Uuid,
+    pub task_number: i32,
+    pub title: String,
+    pub actor_id: Uuid,
+    pub reason: Option<String>,
+    /// Status the task moved to, for status changes and reopens
+    pub status: Option<TaskStatus>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: NotificationKind,
+    pub payload: sqlx::types::Json<NotificationPayload>,
+    pub read_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+// ── Webhook ──
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum WebhookEvent {
+    #[serde(rename = "task.created")]
+    Created,
+    #[serde(rename = "task.updated")]
+    Updated,
+    #[serde(rename = "task.closed")]
+    Closed,
+}
+
+impl std::fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebhookEvent::Created => write!(f, "task.created"),
+            WebhookEvent::Updated => write!(f, "task.updated"),
+            WebhookEvent::Closed => write!(f, "task.closed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+// ── Team ──
+
+/// Team row with its members' ids
+#[derive(Debug, Clone, FromRow)]
+pub struct Team {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub member_ids: Vec<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TaskStatus::{self, *};
+
+    const ALL: [TaskStatus; 5] = [New, InProgress, Testing, Done, Closed];
+
+    /// Workflow edges besides staying put; every other pair must be refused
+    const ALLOWED: &[(TaskStatus, TaskStatus)] = &[
+        (New, InProgress),
+        (InProgress, New),
+        (InProgress, Testing),
+        (Testing, InProgress),
+        (Testing, Done),
+        (Done, Closed),
+        (Done, InProgress),
+        (Closed, InProgress),
+    ];
+
+    #[test]
+    fn transitions_follow_the_workflow() {
+        for from in &ALL {
+            for to in &ALL {
+                let expected = from == to || ALLOWED.contains(&(from.clone(), to.clone()));
+                assert_eq!(
+                    from.can_transition_to(to),
+                    expected,
+                    "{} -> {}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn shortcuts_and_backward_jumps_are_refused() {
+        assert!(!New.can_transition_to(&Closed));
+        assert!(!New.can_transition_to(&Done));
+        assert!(!Closed.can_transition_to(&New));
+        assert!(!Closed.can_transition_to(&Done));
+        assert!(!Testing.can_transition_to(&Closed));
+    }
 }
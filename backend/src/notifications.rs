@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::dto::NotificationResponse;
+use crate::handlers::notification_handler;
+use crate::models::{Notification, NotificationKind, NotificationPayload, Task};
+use crate::AppState;
+
+/// Notifications a slow stream may fall behind by before it starts losing them
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Per-user channels behind `GET /api/notifications/stream`; a user has one only while
+/// at least one of their streams is open
+#[derive(Clone, Default)]
+pub struct NotificationHub {
+    channels: Arc<Mutex<HashMap<Uuid, broadcast::Sender<Arc<NotificationResponse>>>>>,
+}
+
+impl NotificationHub {
+    pub fn subscribe(&self, user_id: Uuid) -> broadcast::Receiver<Arc<NotificationResponse>> {
+        let mut channels = self.channels.lock().expect("notification channels poisoned");
+        // Drop the channels of users whose streams have all closed
+        channels.retain(|_, sender| sender.receiver_count() > 0);
+        channels
+            .entry(user_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    fn push(&self, user_id: Uuid, notification: Arc<NotificationResponse>) {
+        let channels = self.channels.lock().expect("notification channels poisoned");
+        if let Some(sender) = channels.get(&user_id) {
+            // Every stream may have closed since; then there is nobody to tell
+            let _ = sender.send(notification);
+        }
+    }
+}
+
+fn payload(task: &Task, actor_id: Uuid) -> NotificationPayload {
+    NotificationPayload {
+        task_id: task.id,
+        task_number: task.task_number,
+        title: task.title.clone(),
+        actor_id,
+        reason: None,
+        status: None,
+    }
+}
+
+/// Stores `kind` in the inbox of each recipient except the actor, who caused it, and pushes
+/// it to their open streams. Called after the change is committed, so a failure is logged
+/// instead of failing the request.
+async fn notify(
+    state: &AppState,
+    kind: NotificationKind,
+    mut recipients: Vec<Uuid>,
+    payload: NotificationPayload,
+) {
+    recipients.retain(|id| *id != payload.actor_id);
+    recipients.sort();
+    recipients.dedup();
+    if recipients.is_empty() {
+        return;
+    }
+
+    let task_id = payload.task_id;
+    let stored: Result<Vec<Notification>, sqlx::Error> = sqlx::query_as(
+        "INSERT INTO notifications (user_id, kind, payload)
+         SELECT unnest($1::uuid[]), $2, $3
+         RETURNING id, user_id, kind, payload, read_at, created_at",
+    )
+    .bind(&recipients)
+    .bind(kind)
+    .bind(sqlx::types::Json(payload))
+    .fetch_all(&state.db)
+    .await;
+
+    match stored {
+        Ok(rows) => {
+            for row in rows {
+                let user_id = row.user_id;
+                let notification =
+                    notification_handler::notification_to_response(row, &state.config);
+                state.notifications.push(user_id, Arc::new(notification));
+            }
+        }
+        Err(e) => tracing::error!(
+            "Failed to store {} notifications for task {}: {}",
+            kind,
+            task_id,
+            e
+        ),
+    }
+}
+
+/// Active watchers of `task` who can still see it: a tester stops hearing about a task once
+/// they are neither its tester nor its creator, even if they still follow it
+async fn watchers(state: &AppState, task: &Task) -> Vec<Uuid> {
+    let watchers = sqlx::query_scalar(
+        "SELECT w.user_id FROM task_watchers w
+         JOIN users u ON u.id = w.user_id
+         WHERE w.task_id = $1 AND u.is_active
+           AND (u.role <> 'tester' OR w.user_id = $2 OR w.user_id = $3)",
+    )
+    .bind(task.id)
+    .bind(task.tester_id)
+    .bind(task.assigned_by)
+    .fetch_all(&state.db)
+    .await;
+
+    watchers.unwrap_or_else(|e| {
+        tracing::error!("Failed to load watchers of task {}: {}", task.id, e);
+        Vec::new()
+    })
+}
+
+/// Tells the new tester of `task` that it was assigned to them
+pub async fn task_assigned(state: &AppState, tester_id: Uuid, actor_id: Uuid, task: &Task) {
+    notify(state, NotificationKind::Assigned, vec![tester_id], payload(task, actor_id)).await;
+}
+
+/// Tells the watchers of `task` about a new comment on it
+pub async fn task_commented(state: &AppState, author_id: Uuid, task: &Task) {
+    let recipients = watchers(state, task).await;
+    notify(state, NotificationKind::Commented, recipients, payload(task, author_id)).await;
+}
+
+/// Tells the watchers of `task` that its status changed; `task` is the updated task
+pub async fn task_status_changed(state: &AppState, actor_id: Uuid, task: &Task) {
+    let recipients = watchers(state, task).await;
+    let payload = NotificationPayload {
+        status: Some(task.status.clone()),
+        ..payload(task, actor_id)
+    };
+    notify(state, NotificationKind::StatusChanged, recipients, payload).await;
+}
+
+/// Tells the watchers of `task` that it was reopened, and why
+pub async fn task_reopened(state: &AppState, actor_id: Uuid, task: &Task, reason: &str) {
+    let recipients = watchers(state, task).await;
+    let payload = NotificationPayload {
+        reason: Some(reason.to_string()),
+        status: Some(task.status.clone()),
+        ..payload(task, actor_id)
+    };
+    notify(state, NotificationKind::Reopened, recipients, payload).await;
+}
@@ -0,0 +1,129 @@
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::models::TaskUrgency;
+
+/// SMTP sender for user notifications
+#[derive(Clone)]
+pub struct Mailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl Mailer {
+    /// Builds the mailer from `SMTP_*` settings; `None` when notifications are disabled.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        if !config.notifications_enabled {
+            return None;
+        }
+
+        let smtp = &config.smtp;
+        let builder = match smtp.tls.as_str() {
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host),
+            "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host),
+            "none" => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &smtp.host,
+            )),
+            other => panic!("SMTP_TLS must be tls, starttls or none, got '{}'", other),
+        }
+        .expect("Invalid SMTP_HOST");
+
+        let mut builder = builder.port(smtp.port);
+        if let Some(username) = &smtp.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                smtp.password.clone().unwrap_or_default(),
+            ));
+        }
+
+        Some(Self {
+            transport: builder.build(),
+            from: smtp.from.parse().expect("SMTP_FROM must be a valid mailbox"),
+        })
+    }
+
+    async fn send(&self, to: Mailbox, subject: String, body: String) -> Result<(), String> {
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(subject)
+            .body(body)
+            .map_err(|e| e.to_string())?;
+
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Task fields quoted in notification emails
+pub struct TaskSummary {
+    pub task_number: i32,
+    pub title: String,
+    pub urgency: TaskUrgency,
+}
+
+/// Emails the tester that a task was assigned to them. Runs in the background:
+/// the request never waits for SMTP, and failures are only logged.
+pub fn tester_assigned(
+    mailer: Option<&Mailer>,
+    db: &PgPool,
+    tester_id: Uuid,
+    task: TaskSummary,
+) {
+    let Some(mailer) = mailer.cloned() else {
+        return;
+    };
+    let db = db.clone();
+
+    tokio::spawn(async move {
+        let lookup: Result<Option<(String, String)>, _> = sqlx::query_as(
+            "SELECT email, full_name FROM users WHERE id = $1 AND is_active = TRUE",
+        )
+        .bind(tester_id)
+        .fetch_optional(&db)
+        .await;
+
+        let recipient = match lookup {
+            Ok(recipient) => recipient,
+            Err(e) => {
+                tracing::error!("Assignment notification: tester lookup failed: {:?}", e);
+                return;
+            }
+        };
+        let Some((email, full_name)) = recipient else {
+            return;
+        };
+
+        let to = match email.parse() {
+            Ok(address) => Mailbox::new(Some(full_name.clone()), address),
+            Err(e) => {
+                tracing::warn!("Assignment notification: bad address {}: {}", email, e);
+                return;
+            }
+        };
+
+        let subject = format!(
+            "[TestFlow] Task #{} assigned to you: {}",
+            task.task_number, task.title
+        );
+        let body = format!(
+            "Hello {},\n\n\
+             You have been assigned as the tester of task #{}.\n\n\
+             Title: {}\n\
+             Urgency: {}\n\n\
+             -- TestFlow\n",
+            full_name, task.task_number, task.title, task.urgency
+        );
+
+        if let Err(e) = mailer.send(to, subject, body).await {
+            tracing::error!("Assignment notification to {} failed: {}", email, e);
+        }
+    });
+}
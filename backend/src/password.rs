@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use sha1::{Digest, Sha1};
+use validator::{ValidationError, ValidationErrors};
+
+use crate::config::{AppConfig, PasswordPolicy};
+use crate::errors::AppError;
+use crate::AppState;
+
+/// What is actually hashed: the password followed by `PASSWORD_PEPPER`, if set
+fn peppered(config: &AppConfig, password: &str) -> Vec<u8> {
+    let mut input = password.as_bytes().to_vec();
+    if let Some(pepper) = &config.password_pepper {
+        input.extend_from_slice(pepper.as_bytes());
+    }
+    input
+}
+
+pub fn hash_password(config: &AppConfig, password: &str) -> Result<String, AppError> {
+    use argon2::PasswordHasher;
+    let salt =
+        argon2::password_hash::SaltString::generate(&mut argon2::password_hash::rand_core::OsRng);
+    argon2::Argon2::default()
+        .hash_password(&peppered(config, password), &salt)
+        .map(|h| h.to_string())
+        .map_err(|e| AppError::Internal(format!("Password hash error: {}", e)))
+}
+
+/// Whether `password` matches a hash made by `hash_password` under the same pepper
+pub fn verify_password(config: &AppConfig, password: &str, hash: &str) -> Result<bool, AppError> {
+    use argon2::PasswordVerifier;
+    let parsed_hash = argon2::password_hash::PasswordHash::new(hash)
+        .map_err(|_| AppError::Internal("Password hash error".to_string()))?;
+    Ok(argon2::Argon2::default()
+        .verify_password(&peppered(config, password), &parsed_hash)
+        .is_ok())
+}
+
+/// Checks `password` against the configured policy, reporting every unmet
+/// requirement under `field` so the client can show them all at once.
+pub fn validate_password_strength(
+    policy: &PasswordPolicy,
+    field: &'static str,
+    password: &str,
+) -> Result<(), AppError> {
+    let mut unmet: Vec<(&'static str, String)> = Vec::new();
+
+    if password.chars().count() < policy.min_length {
+        unmet.push((
+            "password_too_short",
+            format!("Password must be at least {} characters", policy.min_length),
+        ));
+    }
+    if policy.require_uppercase && !password.chars().any(char::is_uppercase) {
+        unmet.push((
+            "password_no_uppercase",
+            "Password must contain an uppercase letter".to_string(),
+        ));
+    }
+    if policy.require_lowercase && !password.chars().any(char::is_lowercase) {
+        unmet.push((
+            "password_no_lowercase",
+            "Password must contain a lowercase letter".to_string(),
+        ));
+    }
+    if policy.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+        unmet.push((
+            "password_no_digit",
+            "Password must contain a digit".to_string(),
+        ));
+    }
+    if policy.require_symbol && password.chars().all(char::is_alphanumeric) {
+        unmet.push((
+            "password_no_symbol",
+            "Password must contain a symbol".to_string(),
+        ));
+    }
+
+    if unmet.is_empty() {
+        return Ok(());
+    }
+
+    let mut errors = ValidationErrors::new();
+    for (code, message) in unmet {
+        let mut error = ValidationError::new(code);
+        error.message = Some(message.into());
+        errors.add(field, error);
+    }
+    Err(errors.into())
+}
+
+/// Rejects passwords found in the breach corpus behind `PASSWORD_BREACH_API_URL`.
+///
+/// Only the first five hex chars of the SHA-1 are sent (k-anonymity range query).
+/// The check fails open: if the service is unreachable the password is accepted
+/// and a warning is logged, so an outage cannot block account management.
+pub async fn ensure_not_breached(state: &AppState, password: &str) -> Result<(), AppError> {
+    if !state.config.password_breach_check {
+        return Ok(());
+    }
+
+    let digest = format!("{:X}", Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = digest.split_at(5);
+
+    let body = match fetch_range(state, prefix).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!("Password breach check unavailable, allowing password: {}", e);
+            return Ok(());
+        }
+    };
+
+    let breached = body
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .any(|(hash_suffix, count)| {
+            hash_suffix.trim().eq_ignore_ascii_case(suffix) && count.trim() != "0"
+        });
+
+    if breached {
+        let mut error = ValidationError::new("breached_password");
+        error.message = Some(
+            "This password has appeared in a known data breach, choose a different one".into(),
+        );
+        let mut errors = ValidationErrors::new();
+        errors.add("password", error);
+        return Err(errors.into());
+    }
+
+    Ok(())
+}
+
+async fn fetch_range(state: &AppState, prefix: &str) -> Result<String, reqwest::Error> {
+    state
+        .http
+        .get(format!("{}{}", state.config.password_breach_api_url, prefix))
+        .timeout(Duration::from_secs(state.config.password_breach_timeout_secs))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await
+}
+
+/// Random initial password that satisfies `policy`: at least 16 characters,
+/// always mixing upper and lower case, digits and symbols.
+pub fn generate_password(policy: &PasswordPolicy) -> String {
+    use argon2::password_hash::rand_core::{OsRng, RngCore};
+
+    // Look-alike characters (I, l, O, 0, 1) are left out, since these get read aloud or retyped
+    const UPPER: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+    const LOWER: &[u8] = b"abcdefghijkmnopqrstuvwxyz";
+    const DIGITS: &[u8] = b"23456789";
+    const SYMBOLS: &[u8] = b"!@#$%^&*-_=+?";
+
+    let pick = |set: &[u8]| set[OsRng.next_u32() as usize % set.len()] as char;
+    let all = [UPPER, LOWER, DIGITS, SYMBOLS].concat();
+
+    let mut chars = vec![pick(UPPER), pick(LOWER), pick(DIGITS), pick(SYMBOLS)];
+    while chars.len() < policy.min_length.max(16) {
+        chars.push(pick(&all));
+    }
+    // Fisher-Yates, so the guaranteed classes do not always lead
+    for i in (1..chars.len()).rev() {
+        let j = OsRng.next_u32() as usize % (i + 1);
+        chars.swap(i, j);
+    }
+    chars.into_iter().collect()
+}
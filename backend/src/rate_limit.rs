@@ -0,0 +1,80 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// In-memory sliding window of failed login attempts.
+///
+/// Keys are opaque strings (the login handler uses one per client IP and one per
+/// username); a key is blocked once it has `max_failures` failures inside `window`.
+#[derive(Clone)]
+pub struct LoginRateLimiter {
+    max_failures: usize,
+    window: Duration,
+    failures: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+}
+
+impl LoginRateLimiter {
+    /// `max_failures == 0` disables the limiter.
+    pub fn new(max_failures: usize, window: Duration) -> Self {
+        Self {
+            max_failures,
+            window,
+            failures: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Time until the most restricted of `keys` may try again, or `None` if none is blocked.
+    pub fn retry_after(&self, keys: &[String]) -> Option<Duration> {
+        if self.max_failures == 0 {
+            return None;
+        }
+
+        let now = Instant::now();
+        let mut failures = self.failures.lock().unwrap();
+
+        keys.iter()
+            .filter_map(|key| {
+                let attempts = failures.get_mut(key)?;
+                prune(attempts, now, self.window);
+                if attempts.len() < self.max_failures {
+                    return None;
+                }
+                // The window frees up when the oldest counted failure expires
+                let oldest = attempts[attempts.len() - self.max_failures];
+                Some((oldest + self.window).saturating_duration_since(now))
+            })
+            .max()
+    }
+
+    pub fn record_failure(&self, keys: &[String]) {
+        if self.max_failures == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut failures = self.failures.lock().unwrap();
+
+        // Forget keys whose failures have all expired so the map does not grow forever
+        failures.retain(|_, attempts| {
+            prune(attempts, now, self.window);
+            !attempts.is_empty()
+        });
+
+        for key in keys {
+            failures.entry(key.clone()).or_default().push_back(now);
+        }
+    }
+
+    pub fn clear(&self, key: &str) {
+        self.failures.lock().unwrap().remove(key);
+    }
+}
+
+fn prune(attempts: &mut VecDeque<Instant>, now: Instant, window: Duration) {
+    while attempts
+        .front()
+        .is_some_and(|t| now.saturating_duration_since(*t) >= window)
+    {
+        attempts.pop_front();
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::handlers::template_handler;
+use crate::models::TemplateRecurrence;
+use crate::AppState;
+
+/// How often due task templates are looked for
+const TICK: Duration = Duration::from_secs(60);
+
+/// Creates tasks from recurring templates whose `next_run_at` has passed. A run missed while
+/// the server was down is made up once, not once per missed period.
+pub fn spawn_template_scheduler(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_due_templates(&state).await {
+                tracing::warn!("Task template scheduler failed: {:?}", e);
+            }
+        }
+    });
+}
+
+async fn run_due_templates(state: &AppState) -> Result<(), sqlx::Error> {
+    let due: Vec<(Uuid, Uuid, TemplateRecurrence, NaiveDateTime)> = sqlx::query_as(
+        "SELECT id, created_by, recurrence, next_run_at FROM task_templates
+         WHERE recurrence IS NOT NULL AND next_run_at <= NOW()
+         ORDER BY next_run_at",
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let now = chrono::Utc::now().naive_utc();
+    for (id, created_by, recurrence, run_at) in due {
+        let mut next = recurrence.next_after(run_at);
+        while next <= now {
+            next = recurrence.next_after(next);
+        }
+
+        // Claiming the run by moving `next_run_at` keeps other instances from repeating it
+        let claimed = sqlx::query(
+            "UPDATE task_templates SET next_run_at = $1 WHERE id = $2 AND next_run_at = $3",
+        )
+        .bind(next)
+        .bind(id)
+        .bind(run_at)
+        .execute(&state.db)
+        .await?;
+        if claimed.rows_affected() == 0 {
+            continue;
+        }
+
+        match template_handler::instantiate(state, id, created_by).await {
+            Ok(task) => tracing::info!(
+                "Created task {} from template {}",
+                task.display_number,
+                id
+            ),
+            Err(e) => tracing::warn!("Scheduled run of template {} failed: {:?}", id, e),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use axum::{extract::Request, response::Response};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tower_http::trace::{DefaultOnResponse, OnResponse};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::request_id::RequestId;
+
+/// Installs the global subscriber: human-readable lines by default, one JSON object per
+/// event with `LOG_FORMAT=json` (for ELK/Loki). Levels come from `RUST_LOG`.
+///
+/// With `OTEL_EXPORTER_OTLP_ENDPOINT` set, spans are also exported over OTLP/HTTP; the
+/// returned provider must be shut down on exit to flush the last batch.
+pub fn init() -> Option<SdkTracerProvider> {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "testflow=debug,tower_http=debug".parse().unwrap());
+    let format = std::env::var("LOG_FORMAT").unwrap_or_else(|_| "pretty".to_string());
+    let fmt_layer = match format.as_str() {
+        "json" => tracing_subscriber::fmt::layer().json().boxed(),
+        "pretty" => tracing_subscriber::fmt::layer().boxed(),
+        other => panic!("LOG_FORMAT must be json or pretty, got '{}'", other),
+    };
+
+    let provider = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .ok()
+        .filter(|e| !e.is_empty())
+        .map(|_| otlp_provider());
+    let otel_layer = provider
+        .as_ref()
+        .map(|p| tracing_opentelemetry::layer().with_tracer(p.tracer("testflow")));
+
+    tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(otel_layer)
+        .with(filter)
+        .init();
+
+    if provider.is_some() {
+        tracing::info!("Exporting traces over OTLP");
+    }
+    provider
+}
+
+/// Batch exporter to the collector; the endpoint and headers are read by the exporter
+/// itself from the standard `OTEL_EXPORTER_OTLP_*` variables
+fn otlp_provider() -> SdkTracerProvider {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .build()
+        .expect("Failed to create the OTLP span exporter");
+    let service_name =
+        std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "testflow".to_string());
+
+    // Incoming `traceparent` headers continue the caller's trace (see `request_span`)
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+    SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build()
+}
+
+/// Access log span of a request; `status` is filled in once the response is ready
+pub fn request_span(request: &Request) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map_or("-", |id| id.0.as_str());
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        path = %request.uri().path(),
+        request_id = %request_id,
+        status = tracing::field::Empty,
+    );
+
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    // Fails only when spans are not exported, in which case there is nothing to link
+    let _ = span.set_parent(parent);
+    span
+}
+
+pub fn record_response(response: &Response, latency: Duration, span: &Span) {
+    span.record("status", response.status().as_u16());
+    DefaultOnResponse::new().on_response(response, latency, span);
+}
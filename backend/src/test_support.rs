@@ -0,0 +1,148 @@
+//! Fixtures for tests that run against a database. Those tests use `#[sqlx::test]`, which
+//! creates a fresh database per test on the server in `DATABASE_URL` and applies `migrations/`.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::{AuthUser, JwtKeys};
+use crate::config::{AppConfig, PasswordPolicy, SmtpConfig};
+use crate::live::LiveTasks;
+use crate::models::UserRole;
+use crate::notifications::NotificationHub;
+use crate::password;
+use crate::rate_limit::LoginRateLimiter;
+use crate::AppState;
+
+/// Password of every user made by [`create_user`]
+pub const PASSWORD: &str = "password1";
+
+/// The defaults of `load_config`, without reading the environment
+pub fn config() -> AppConfig {
+    AppConfig {
+        jwt_keys: JwtKeys::hs256("test-secret"),
+        jwt_expiration_hours: 24,
+        jwt_expiration_hours_by_role: Vec::new(),
+        refresh_token_expiration_days: 30,
+        password_reset_token_minutes: 30,
+        task_field_visibility: HashMap::new(),
+        password_policy: PasswordPolicy {
+            min_length: 6,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_symbol: false,
+        },
+        password_breach_check: false,
+        password_breach_api_url: String::new(),
+        password_breach_timeout_secs: 3,
+        password_pepper: None,
+        cors_allowed_origins: None,
+        request_timeout_secs: 60,
+        compression_enabled: false,
+        login_max_failures: 0,
+        login_failure_window_secs: 300,
+        account_lockout_threshold: 5,
+        account_lockout_minutes: 15,
+        attachments_dir: std::env::temp_dir(),
+        attachment_max_size_bytes: 10 * 1024 * 1024,
+        request_body_max_size_bytes: 1024 * 1024,
+        notifications_enabled: false,
+        smtp: SmtpConfig {
+            host: "localhost".to_string(),
+            port: 587,
+            username: None,
+            password: None,
+            from: "TestFlow <noreply@testflow.local>".to_string(),
+            tls: "none".to_string(),
+        },
+        webhook_timeout_secs: 5,
+        webhook_max_retries: 0,
+        totp_encryption_key: [0; 32],
+        totp_issuer: "TestFlow".to_string(),
+        dependencies_block_completion: false,
+        checklist_blocks_completion: false,
+        admins_can_manage_tasks: false,
+        strict_pagination: false,
+        task_number_prefix: "TF-".to_string(),
+        public_base_url: None,
+        api_contact_name: None,
+        api_contact_email: None,
+        api_license_name: None,
+        api_license_url: None,
+    }
+}
+
+pub fn state(db: PgPool) -> AppState {
+    state_with(db, config())
+}
+
+pub fn state_with(db: PgPool, config: AppConfig) -> AppState {
+    AppState {
+        login_limiter: LoginRateLimiter::new(
+            config.login_max_failures,
+            Duration::from_secs(config.login_failure_window_secs),
+        ),
+        mailer: None,
+        db,
+        config,
+        http: reqwest::Client::new(),
+        live: LiveTasks::new(),
+        notifications: NotificationHub::default(),
+    }
+}
+
+/// Active user with [`PASSWORD`] and the email `<username>@example.com`
+pub async fn create_user(db: &PgPool, username: &str, role: UserRole) -> Uuid {
+    create_user_with_email(db, username, &format!("{}@example.com", username), role).await
+}
+
+pub async fn create_user_with_email(
+    db: &PgPool,
+    username: &str,
+    email: &str,
+    role: UserRole,
+) -> Uuid {
+    // Hashing is slow in debug builds, so every user shares one hash
+    static HASH: OnceLock<String> = OnceLock::new();
+    let hash = HASH.get_or_init(|| password::hash_password(&config(), PASSWORD).unwrap());
+
+    sqlx::query_scalar(
+        "INSERT INTO users (username, email, password_hash, full_name, role)
+         VALUES ($1, $2, $3, $1, $4)
+         RETURNING id",
+    )
+    .bind(username)
+    .bind(email)
+    .bind(hash)
+    .bind(role)
+    .fetch_one(db)
+    .await
+    .unwrap()
+}
+
+/// Task with status `new`, created by `assigned_by`
+pub async fn create_task(db: &PgPool, assigned_by: Uuid, tester_id: Option<Uuid>) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO tasks (title, assigned_by, tester_id) VALUES ('Task', $1, $2) RETURNING id",
+    )
+    .bind(assigned_by)
+    .bind(tester_id)
+    .fetch_one(db)
+    .await
+    .unwrap()
+}
+
+/// What the extractor yields for a valid token of this user
+pub fn auth(user_id: Uuid, role: UserRole) -> AuthUser {
+    AuthUser {
+        user_id,
+        username: String::new(),
+        role,
+        jti: None,
+        token_exp: usize::MAX,
+    }
+}
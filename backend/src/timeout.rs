@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::errors::{codes, AppError};
+use crate::AppState;
+
+/// Answers 503 when a handler has not produced a response within `REQUEST_TIMEOUT_SECS`;
+/// the handler future is dropped, rolling back any open transaction. Only the wait for
+/// the response head is bounded, so streamed bodies (CSV exports, downloads) run to the end.
+pub async fn middleware(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let limit = state.config.request_timeout_secs;
+    if limit == 0 {
+        return next.run(request).await;
+    }
+
+    let method = request.method().clone();
+    let uri = request.uri().clone();
+    match tokio::time::timeout(Duration::from_secs(limit), next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("{} {} timed out after {}s", method, uri, limit);
+            AppError::ServiceUnavailable(
+                codes::REQUEST_TIMEOUT,
+                format!("Request timed out after {} seconds", limit),
+            )
+                .into_response()
+        }
+    }
+}
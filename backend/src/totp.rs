@@ -0,0 +1,92 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::errors::AppError;
+
+/// Recovery codes handed out when 2FA is switched on
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+const NONCE_LEN: usize = 12;
+
+/// Standard authenticator-app parameters; one step of clock skew is tolerated.
+pub fn build(secret: Vec<u8>, issuer: &str, account: &str) -> Result<TOTP, AppError> {
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        secret,
+        Some(issuer.replace(':', "_")),
+        account.replace(':', "_"),
+    )
+    .map_err(|e| AppError::Internal(format!("TOTP setup error: {}", e)))
+}
+
+pub fn generate_secret() -> Result<Vec<u8>, AppError> {
+    Secret::generate_secret()
+        .to_bytes()
+        .map_err(|e| AppError::Internal(format!("TOTP secret error: {:?}", e)))
+}
+
+/// Checks a 6-digit code against the current time window
+pub fn check_code(totp: &TOTP, code: &str) -> Result<bool, AppError> {
+    totp.check_current(code)
+        .map_err(|e| AppError::Internal(format!("System clock error: {}", e)))
+}
+
+/// Encrypts a TOTP secret for storage as hex of `nonce || ciphertext`
+pub fn encrypt_secret(key: &[u8; 32], secret: &[u8]) -> Result<String, AppError> {
+    let cipher = Aes256Gcm::new(key.into());
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), secret)
+        .map_err(|_| AppError::Internal("TOTP secret encryption failed".to_string()))?;
+
+    Ok(nonce
+        .iter()
+        .chain(ciphertext.iter())
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+pub fn decrypt_secret(key: &[u8; 32], stored: &str) -> Result<Vec<u8>, AppError> {
+    let corrupt = || AppError::Internal("Stored TOTP secret cannot be decrypted".to_string());
+
+    let bytes = (0..stored.len())
+        .step_by(2)
+        .map(|i| stored.get(i..i + 2).and_then(|h| u8::from_str_radix(h, 16).ok()))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(corrupt)?;
+    if bytes.len() <= NONCE_LEN {
+        return Err(corrupt());
+    }
+
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    Aes256Gcm::new(key.into())
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| corrupt())
+}
+
+/// Fresh recovery codes in `xxxxx-xxxxx` form (lowercase hex)
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let mut bytes = [0u8; 5];
+            OsRng.fill_bytes(&mut bytes);
+            let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            format!("{}-{}", &hex[..5], &hex[5..])
+        })
+        .collect()
+}
+
+/// Recovery codes are compared ignoring case, whitespace and dashes
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Display names of the given users in one query, for enriching rows in memory where a
+/// JOIN does not fit (e.g. ids stored as text). Unknown ids are simply absent from the map.
+pub async fn fetch_user_names<'e, E: sqlx::PgExecutor<'e>>(
+    db: E,
+    ids: &[Uuid],
+) -> Result<HashMap<Uuid, String>, AppError> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(Uuid, String)> =
+        sqlx::query_as("SELECT id, full_name FROM users WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(db)
+            .await?;
+
+    Ok(rows.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserRole;
+    use crate::test_support::create_user;
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn maps_known_ids_and_skips_missing_ones(pool: PgPool) {
+        let alice = create_user(&pool, "alice", UserRole::Tester).await;
+        let bob = create_user(&pool, "bob", UserRole::Manager).await;
+        let missing = Uuid::new_v4();
+
+        // Duplicates and order in the input do not matter
+        let names = fetch_user_names(&pool, &[missing, bob, alice, bob]).await.unwrap();
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[&alice], "alice");
+        assert_eq!(names[&bob], "bob");
+        assert!(!names.contains_key(&missing));
+
+        assert!(fetch_user_names(&pool, &[missing]).await.unwrap().is_empty());
+        assert!(fetch_user_names(&pool, &[]).await.unwrap().is_empty());
+    }
+}
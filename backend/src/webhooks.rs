@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::dto::TaskResponse;
+use crate::models::WebhookEvent;
+use crate::AppState;
+
+/// Header carrying `sha256=<hex HMAC of the raw body>` keyed with the webhook secret
+pub const SIGNATURE_HEADER: &str = "X-TestFlow-Signature";
+pub const EVENT_HEADER: &str = "X-TestFlow-Event";
+
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+/// Sends `event` for `task` to every active subscription in the background.
+/// Each delivery is retried `WEBHOOK_MAX_RETRIES` times; failures are only logged.
+pub fn dispatch(state: &AppState, event: WebhookEvent, task: &TaskResponse) {
+    let body = serde_json::json!({
+        "event": event,
+        "occurred_at": chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        "task": task,
+    });
+    let body = match serde_json::to_vec(&body) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("Webhook payload serialization failed: {}", e);
+            return;
+        }
+    };
+
+    let db = state.db.clone();
+    let http = state.http.clone();
+    let timeout = Duration::from_secs(state.config.webhook_timeout_secs);
+    let max_retries = state.config.webhook_max_retries;
+
+    tokio::spawn(async move {
+        let targets: Vec<(String, String)> = match sqlx::query_as(
+            "SELECT url, secret FROM webhooks WHERE is_active = TRUE AND $1 = ANY(event_types)",
+        )
+        .bind(event.to_string())
+        .fetch_all(&db)
+        .await
+        {
+            Ok(targets) => targets,
+            Err(e) => {
+                tracing::error!("Webhook lookup failed: {:?}", e);
+                return;
+            }
+        };
+
+        for (url, secret) in targets {
+            let http = http.clone();
+            let body = body.clone();
+            let signature = sign(&secret, &body);
+
+            tokio::spawn(async move {
+                for attempt in 0..=max_retries {
+                    if attempt > 0 {
+                        tokio::time::sleep(Duration::from_secs(1 << (attempt - 1))).await;
+                    }
+
+                    let result = http
+                        .post(&url)
+                        .timeout(timeout)
+                        .header(reqwest::header::CONTENT_TYPE, "application/json")
+                        .header(SIGNATURE_HEADER, &signature)
+                        .header(EVENT_HEADER, event.to_string())
+                        .body(body.clone())
+                        .send()
+                        .await
+                        .and_then(|r| r.error_for_status());
+
+                    match result {
+                        Ok(_) => return,
+                        Err(e) => tracing::warn!(
+                            "Webhook {} delivery to {} failed (attempt {}/{}): {}",
+                            event,
+                            url,
+                            attempt + 1,
+                            max_retries + 1,
+                            e
+                        ),
+                    }
+                }
+                tracing::error!("Webhook {} delivery to {} gave up", event, url);
+            });
+        }
+    });
+}